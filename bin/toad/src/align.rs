@@ -0,0 +1,89 @@
+//! Lost-work protection for `toad ggit align`.
+//!
+//! Aligning checks a submodule out at the commit its parent records. Commits
+//! that only the current checkout reaches, and tracked changes in its working
+//! tree, would be lost by that checkout, so they are listed first and kept
+//! under a backup ref before anything moves.
+
+use crate::git;
+use anyhow::{bail, Result};
+use std::path::{Path, PathBuf};
+
+pub const BACKUP_PREFIX: &str = "refs/toad/align-backup";
+
+#[derive(Debug, Clone, Default)]
+pub struct AlignCheck {
+    pub name: String,
+    pub path: PathBuf,
+    /// Commit recorded in the parent repository.
+    pub expected: Option<String>,
+    /// Commit currently checked out, `None` when not initialized.
+    pub actual: Option<String>,
+    /// `%h %s` lines for commits no branch, tag or remote would still reach.
+    pub unreachable: Vec<String>,
+    /// `status --porcelain` lines for tracked changes the checkout discards.
+    pub uncommitted: Vec<String>,
+}
+
+impl AlignCheck {
+    pub fn is_aligned(&self) -> bool {
+        self.actual.is_some() && self.expected == self.actual && self.uncommitted.is_empty()
+    }
+
+    pub fn loses_work(&self) -> bool {
+        !self.unreachable.is_empty() || !self.uncommitted.is_empty()
+    }
+}
+
+/// Inspects a submodule checkout against the commit its parent records.
+pub fn inspect(name: &str, path: &Path, expected: Option<&str>) -> AlignCheck {
+    let mut check = AlignCheck {
+        name: name.to_string(),
+        path: path.to_path_buf(),
+        expected: expected.map(String::from),
+        ..Default::default()
+    };
+    if !path.join(".git").exists() {
+        return check;
+    }
+    check.actual = git::probe(path, &["rev-parse", "HEAD"]);
+    let Some(actual) = check.actual.clone() else {
+        return check;
+    };
+
+    check.uncommitted = git::probe(path, &["status", "--porcelain", "--untracked-files=no"])
+        .map(|out| out.lines().map(String::from).collect())
+        .unwrap_or_default();
+
+    if check.expected.as_deref() != Some(actual.as_str()) {
+        let mut args = vec!["log", "--format=%h %s", actual.as_str(), "--not"];
+        if let Some(expected) =
+            expected.filter(|e| git::ref_exists(path, &format!("{}^{{commit}}", e)))
+        {
+            args.push(expected);
+        }
+        args.extend(["--branches", "--tags", "--remotes"]);
+        check.unreachable = git::probe(path, &args)
+            .map(|out| out.lines().map(String::from).collect())
+            .unwrap_or_default();
+    }
+    check
+}
+
+/// Records the current checkout under `refs/toad/align-backup/<ts>`. Tracked
+/// changes are captured with `git stash create`, whose commit has the
+/// checked-out HEAD as its parent, so one ref preserves both.
+pub fn backup(path: &Path, timestamp: u64) -> Result<String> {
+    let Some(head) = git::probe(path, &["rev-parse", "HEAD"]) else {
+        bail!("{:?} has no commit to back up", path);
+    };
+    let target = git::probe(path, &["stash", "create", "toad align backup"])
+        .filter(|stash| !stash.is_empty())
+        .unwrap_or(head);
+    let refname = format!("{}/{}", BACKUP_PREFIX, timestamp);
+    git::output(path, &["update-ref", &refname, &target])?;
+    Ok(refname)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,76 @@
+use super::*;
+use crate::test_support::{commit, git};
+use std::fs;
+use tempfile::tempdir;
+
+/// A hub with one submodule checked out detached at its recorded commit.
+/// Returns the hub path and the recorded commit.
+fn fixture(root: &Path) -> (PathBuf, String) {
+    let core = root.join("core");
+    let hub = root.join("hub");
+    fs::create_dir_all(&core).unwrap();
+    git(&core, &["init", "-q", "-b", "main"]);
+    commit(&core, "lib.rs");
+    fs::create_dir_all(&hub).unwrap();
+    git(&hub, &["init", "-q", "-b", "main"]);
+    git(
+        &hub,
+        &["submodule", "add", "-q", core.to_str().unwrap(), "core"],
+    );
+    git(&hub, &["commit", "-q", "-m", "add core"]);
+    let sub = hub.join("core");
+    git(&sub, &["checkout", "-q", "--detach"]);
+    let expected = git::output(&sub, &["rev-parse", "HEAD"]).unwrap();
+    (hub, expected)
+}
+
+#[test]
+fn test_inspect_aligned_checkout() {
+    let dir = tempdir().unwrap();
+    let (hub, expected) = fixture(dir.path());
+
+    let check = inspect("core", &hub.join("core"), Some(&expected));
+    assert!(check.is_aligned());
+    assert!(!check.loses_work());
+}
+
+#[test]
+fn test_inspect_reports_work_that_would_be_lost() {
+    let dir = tempdir().unwrap();
+    let (hub, expected) = fixture(dir.path());
+    let sub = hub.join("core");
+    commit(&sub, "detached.rs");
+    fs::write(sub.join("lib.rs"), "edited").unwrap();
+
+    let check = inspect("core", &sub, Some(&expected));
+    assert_eq!(check.unreachable.len(), 1);
+    assert!(check.unreachable[0].ends_with("detached.rs"));
+    assert_eq!(check.uncommitted, vec![" M lib.rs".to_string()]);
+    assert!(check.loses_work());
+
+    // Commits kept by a branch survive the checkout.
+    git(&sub, &["checkout", "-q", "-b", "wip"]);
+    let check = inspect("core", &sub, Some(&expected));
+    assert!(check.unreachable.is_empty());
+}
+
+#[test]
+fn test_backup_keeps_commits_and_changes() {
+    let dir = tempdir().unwrap();
+    let (hub, _) = fixture(dir.path());
+    let sub = hub.join("core");
+    commit(&sub, "detached.rs");
+    let head = git::output(&sub, &["rev-parse", "HEAD"]).unwrap();
+    fs::write(sub.join("lib.rs"), "edited").unwrap();
+
+    let refname = backup(&sub, 1_700_000_000).unwrap();
+    assert_eq!(refname, "refs/toad/align-backup/1700000000");
+    assert_eq!(
+        git::output(&sub, &["rev-parse", &format!("{}^1", refname)]).unwrap(),
+        head
+    );
+    assert_eq!(
+        git::output(&sub, &["show", &format!("{}:lib.rs", refname)]).unwrap(),
+        "edited"
+    );
+}
@@ -0,0 +1,142 @@
+//! Gitignore hygiene for `toad audit-ignore`.
+//!
+//! Strategies already know each stack's artifact directories. A repository is
+//! flagged when files under one of them are tracked, or when untracked files
+//! under one, at any depth, are not ignored. Artifact directories a project
+//! has never produced are left alone so unused stacks stay quiet.
+
+use crate::git;
+use anyhow::Result;
+use std::fs;
+use std::path::{Path, PathBuf};
+use toad_core::GitOpResult;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Finding {
+    pub dir: String,
+    /// Tracked files under the artifact directory, anywhere in the repository.
+    pub tracked: Vec<String>,
+    /// Untracked files under the artifact directory that no rule ignores.
+    pub unignored: Vec<String>,
+    /// Whether the ignore rules cover the directory everywhere it appears.
+    pub ignored: bool,
+}
+
+impl Finding {
+    /// The `.gitignore` line that covers this directory at any depth.
+    pub fn entry(&self) -> String {
+        format!("{}/", self.dir)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RepoAudit {
+    pub name: String,
+    pub path: PathBuf,
+    pub findings: Vec<Finding>,
+}
+
+impl RepoAudit {
+    pub fn is_empty(&self) -> bool {
+        self.findings.is_empty()
+    }
+
+    /// `.gitignore` lines to append, in strategy order.
+    pub fn missing(&self) -> Vec<String> {
+        self.findings
+            .iter()
+            .filter(|f| !f.ignored)
+            .map(Finding::entry)
+            .collect()
+    }
+
+    pub fn tracked(&self) -> usize {
+        self.findings.iter().map(|f| f.tracked.len()).sum()
+    }
+}
+
+fn pathspec(dir: &str) -> String {
+    format!(":(glob)**/{}/**", dir)
+}
+
+fn ls_files(path: &Path, args: &[&str]) -> Vec<String> {
+    let mut full = vec!["ls-files", "-z"];
+    full.extend(args);
+    git::probe(path, &full)
+        .unwrap_or_default()
+        .split('\0')
+        .filter(|f| !f.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+pub fn scan(name: &str, path: &Path, artifact_dirs: &[String]) -> RepoAudit {
+    let mut findings = Vec::new();
+    for dir in artifact_dirs {
+        let spec = pathspec(dir);
+        let tracked = ls_files(path, &["--", &spec]);
+        let unignored = ls_files(path, &["-o", "--exclude-standard", "--", &spec]);
+        if tracked.is_empty() && unignored.is_empty() {
+            continue;
+        }
+        // `--no-index` asks whether the rules match, even for tracked paths.
+        let ignored = unignored.is_empty()
+            && git::probe(
+                path,
+                &["check-ignore", "-q", "--no-index", &format!("{}/", dir)],
+            )
+            .is_some();
+        findings.push(Finding {
+            dir: dir.clone(),
+            tracked,
+            unignored,
+            ignored,
+        });
+    }
+    RepoAudit {
+        name: name.to_string(),
+        path: path.to_path_buf(),
+        findings,
+    }
+}
+
+/// Appends `entries` to the repository's `.gitignore`, creating it if needed.
+pub fn append_entries(repo: &Path, entries: &[String]) -> Result<()> {
+    if entries.is_empty() {
+        return Ok(());
+    }
+    let path = repo.join(".gitignore");
+    let mut content = fs::read_to_string(&path).unwrap_or_default();
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    for entry in entries {
+        content.push_str(entry);
+        content.push('\n');
+    }
+    fs::write(&path, content)?;
+    Ok(())
+}
+
+/// Removes tracked artifacts from the index, leaving the files on disk.
+pub fn untrack(audit: &RepoAudit) -> Result<GitOpResult> {
+    let mut args = vec![
+        "rm".to_string(),
+        "-r".to_string(),
+        "--cached".to_string(),
+        "--quiet".to_string(),
+        "--".to_string(),
+    ];
+    args.extend(
+        audit
+            .findings
+            .iter()
+            .filter(|f| !f.tracked.is_empty())
+            .map(|f| pathspec(&f.dir)),
+    );
+    let args: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
+    toad_git::run_git(&audit.path, &args, &audit.name)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,64 @@
+use super::*;
+use crate::test_support::git;
+use tempfile::tempdir;
+
+fn dirs(names: &[&str]) -> Vec<String> {
+    names.iter().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn test_scan_flags_tracked_and_unignored_artifacts() {
+    let dir = tempdir().unwrap();
+    let repo = dir.path();
+    git(repo, &["init", "-q", "-b", "main"]);
+    fs::write(repo.join(".gitignore"), "node_modules/\n").unwrap();
+    fs::create_dir_all(repo.join("crates/core/target/debug")).unwrap();
+    fs::write(repo.join("crates/core/target/debug/app"), "bin").unwrap();
+    fs::create_dir_all(repo.join("node_modules/left-pad")).unwrap();
+    fs::write(repo.join("node_modules/left-pad/index.js"), "pad").unwrap();
+    git(repo, &["add", "-A"]);
+    git(repo, &["commit", "-q", "-m", "init"]);
+    fs::create_dir_all(repo.join("web/dist")).unwrap();
+    fs::write(repo.join("web/dist/bundle.js"), "js").unwrap();
+    // Empty, so there is nothing git could pick up.
+    fs::create_dir(repo.join("build")).unwrap();
+
+    let audit = scan(
+        "pond",
+        repo,
+        &dirs(&["target", "node_modules", "dist", "build"]),
+    );
+    let flagged: Vec<&str> = audit.findings.iter().map(|f| f.dir.as_str()).collect();
+    // `node_modules` is ignored and untracked.
+    assert_eq!(flagged, vec!["target", "dist"]);
+    assert_eq!(audit.findings[1].unignored, vec!["web/dist/bundle.js"]);
+    assert_eq!(
+        audit.findings[0].tracked,
+        vec!["crates/core/target/debug/app"]
+    );
+    assert_eq!(audit.missing(), vec!["target/", "dist/"]);
+    assert_eq!(audit.tracked(), 1);
+}
+
+#[test]
+fn test_fix_appends_entries_and_untracks() {
+    let dir = tempdir().unwrap();
+    let repo = dir.path();
+    git(repo, &["init", "-q", "-b", "main"]);
+    fs::write(repo.join(".gitignore"), "*.log").unwrap();
+    fs::create_dir(repo.join("target")).unwrap();
+    fs::write(repo.join("target/app"), "bin").unwrap();
+    git(repo, &["add", "-A"]);
+    git(repo, &["commit", "-q", "-m", "init"]);
+
+    let audit = scan("pond", repo, &dirs(&["target"]));
+    append_entries(repo, &audit.missing()).unwrap();
+    assert_eq!(
+        fs::read_to_string(repo.join(".gitignore")).unwrap(),
+        "*.log\ntarget/\n"
+    );
+    assert!(untrack(&audit).unwrap().success);
+    // The file stays on disk but is no longer tracked.
+    assert!(repo.join("target/app").exists());
+    assert!(scan("pond", repo, &dirs(&["target"])).is_empty());
+}
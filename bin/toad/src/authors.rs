@@ -0,0 +1,131 @@
+//! Contribution analytics for `toad stats --authors`.
+//!
+//! Computed from local history only: `git log --numstat` over a window,
+//! grouped by author email (after `.mailmap`). The bus factor is the smallest
+//! number of authors who together made more than half of the window's
+//! commits; a project with a bus factor of 1 depends on a single person.
+
+use crate::git;
+use anyhow::Result;
+use std::collections::HashMap;
+use std::path::Path;
+
+const RECORD: char = '\x1e';
+const FIELD: char = '\x1f';
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AuthorStats {
+    pub name: String,
+    pub email: String,
+    pub commits: usize,
+    pub added: usize,
+    pub removed: usize,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RepoAuthors {
+    /// Most commits first.
+    pub authors: Vec<AuthorStats>,
+}
+
+impl RepoAuthors {
+    pub fn commits(&self) -> usize {
+        self.authors.iter().map(|a| a.commits).sum()
+    }
+
+    pub fn bus_factor(&self) -> usize {
+        let total = self.commits();
+        let mut covered = 0;
+        for (i, a) in self.authors.iter().enumerate() {
+            covered += a.commits;
+            if covered * 2 > total {
+                return i + 1;
+            }
+        }
+        0
+    }
+
+    /// Share of commits made by one author, as a percentage.
+    pub fn share(&self, author: &AuthorStats) -> f64 {
+        match self.commits() {
+            0 => 0.0,
+            total => author.commits as f64 * 100.0 / total as f64,
+        }
+    }
+}
+
+/// Parses `git log --format=<RECORD>%aN<FIELD>%aE --numstat` output.
+pub fn parse(log: &str) -> RepoAuthors {
+    let mut by_email: HashMap<String, AuthorStats> = HashMap::new();
+    for record in log.split(RECORD).filter(|r| !r.trim().is_empty()) {
+        let mut lines = record.lines();
+        let Some((name, email)) = lines.next().and_then(|l| l.split_once(FIELD)) else {
+            continue;
+        };
+        let entry = by_email
+            .entry(email.to_lowercase())
+            .or_insert_with(|| AuthorStats {
+                name: name.to_string(),
+                email: email.to_string(),
+                ..Default::default()
+            });
+        entry.commits += 1;
+        for line in lines {
+            let mut cols = line.split('\t');
+            // Binary files report "-" for both counts.
+            let added = cols.next().and_then(|n| n.parse::<usize>().ok());
+            let removed = cols.next().and_then(|n| n.parse::<usize>().ok());
+            entry.added += added.unwrap_or(0);
+            entry.removed += removed.unwrap_or(0);
+        }
+    }
+    let mut authors: Vec<AuthorStats> = by_email.into_values().collect();
+    authors.sort_by(|a, b| b.commits.cmp(&a.commits).then_with(|| a.name.cmp(&b.name)));
+    RepoAuthors { authors }
+}
+
+/// Non-merge commits on any local branch since `since` (any date git accepts).
+pub fn collect(repo: &Path, since: &str) -> Result<RepoAuthors> {
+    let log = git::output(
+        repo,
+        &[
+            "log",
+            "--branches",
+            "--no-merges",
+            &format!("--since={}", since),
+            &format!("--format={}%aN{}%aE", RECORD, FIELD),
+            "--numstat",
+        ],
+    )?;
+    Ok(parse(&log))
+}
+
+/// Folds several repositories' authors into one ecosystem-wide list, with the
+/// number of repositories each author touched.
+pub fn merge<'a>(repos: impl IntoIterator<Item = &'a RepoAuthors>) -> Vec<(AuthorStats, usize)> {
+    let mut by_email: HashMap<String, (AuthorStats, usize)> = HashMap::new();
+    for repo in repos {
+        for a in &repo.authors {
+            let entry = by_email.entry(a.email.to_lowercase()).or_insert_with(|| {
+                (
+                    AuthorStats {
+                        name: a.name.clone(),
+                        email: a.email.clone(),
+                        ..Default::default()
+                    },
+                    0,
+                )
+            });
+            entry.0.commits += a.commits;
+            entry.0.added += a.added;
+            entry.0.removed += a.removed;
+            entry.1 += 1;
+        }
+    }
+    let mut merged: Vec<_> = by_email.into_values().collect();
+    merged.sort_by(|(a, _), (b, _)| b.commits.cmp(&a.commits).then_with(|| a.name.cmp(&b.name)));
+    merged
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,71 @@
+use super::*;
+use crate::test_support::{git, init};
+use std::fs;
+use tempfile::tempdir;
+
+fn commit_as(dir: &Path, who: &str, file: &str, lines: usize) {
+    fs::write(dir.join(file), "line\n".repeat(lines)).unwrap();
+    let name = format!("user.name={}", who);
+    let email = format!("user.email={}@example.com", who.to_lowercase());
+    git(dir, &["add", "-A"]);
+    git(
+        dir,
+        &["-c", &name, "-c", &email, "commit", "-q", "-m", file],
+    );
+}
+
+#[test]
+fn test_collect_groups_by_author_and_counts_lines() {
+    let dir = tempdir().unwrap();
+    let repo = dir.path();
+    init(repo);
+    commit_as(repo, "Ada", "a.rs", 3);
+    commit_as(repo, "Ada", "b.rs", 2);
+    commit_as(repo, "Bo", "c.rs", 5);
+
+    let stats = collect(repo, "1 year ago").unwrap();
+    assert_eq!(stats.commits(), 3);
+    assert_eq!(stats.authors[0].name, "Ada");
+    assert_eq!((stats.authors[0].commits, stats.authors[0].added), (2, 5));
+    assert_eq!(stats.authors[1].added, 5);
+    assert_eq!(stats.bus_factor(), 1);
+}
+
+#[test]
+fn test_bus_factor_and_merge() {
+    let author = |name: &str, commits| AuthorStats {
+        name: name.to_string(),
+        email: format!("{}@example.com", name),
+        commits,
+        ..Default::default()
+    };
+    let shared = RepoAuthors {
+        authors: vec![author("ada", 4), author("bo", 3), author("cy", 3)],
+    };
+    assert_eq!(shared.bus_factor(), 2);
+    assert_eq!(shared.share(&shared.authors[0]), 40.0);
+    assert_eq!(RepoAuthors::default().bus_factor(), 0);
+
+    let solo = RepoAuthors {
+        authors: vec![author("bo", 6)],
+    };
+    let merged = merge([&shared, &solo]);
+    assert_eq!(merged[0].0.name, "bo");
+    assert_eq!((merged[0].0.commits, merged[0].1), (9, 2));
+}
+
+#[test]
+fn test_parse_skips_binary_counts() {
+    let log = "\x1eAda\x1fada@example.com\n\n3\t1\tsrc/lib.rs\n-\t-\tlogo.png\n";
+    let stats = parse(log);
+    assert_eq!(
+        stats.authors,
+        vec![AuthorStats {
+            name: "Ada".to_string(),
+            email: "ada@example.com".to_string(),
+            commits: 1,
+            added: 3,
+            removed: 1,
+        }]
+    );
+}
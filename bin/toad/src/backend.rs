@@ -0,0 +1,226 @@
+//! Read backends for repository state.
+//!
+//! `CliBackend` spawns `git` for every query and is always available. Building
+//! with `--features libgit2` adds `Git2Backend`, which answers the same reads
+//! in-process and falls back to the CLI for anything libgit2 cannot open.
+//! `TOAD_GIT_BACKEND=cli` forces the CLI backend at runtime.
+//!
+//! The binary's own reads (ggit status, submodule trees and drift, change
+//! status) go through a backend. Project discovery and the write paths still
+//! use `toad_discovery` and `toad_git`, which spawn `git` themselves.
+
+use crate::git;
+use anyhow::Result;
+use std::path::Path;
+
+/// A submodule's recorded and checked-out commits.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubmoduleState {
+    pub name: String,
+    /// Path relative to the parent repository.
+    pub path: String,
+    /// Commit recorded in the parent's HEAD tree.
+    pub expected: Option<String>,
+    /// Commit checked out in the submodule's working tree.
+    pub actual: Option<String>,
+}
+
+/// Working tree changes, as listed by `status --porcelain`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DirtyFiles {
+    /// Modified, staged, deleted or conflicted tracked files.
+    pub changed: usize,
+    /// Untracked files not covered by an ignore rule.
+    pub untracked: usize,
+}
+
+pub trait GitBackend: Send + Sync {
+    /// Checked-out branch, or `None` for a detached HEAD.
+    fn current_branch(&self, path: &Path) -> Result<Option<String>>;
+    fn dirty_files(&self, path: &Path) -> Result<DirtyFiles>;
+    /// Commits ahead of and behind the upstream, or `None` without an upstream.
+    fn ahead_behind(&self, path: &Path) -> Result<Option<(usize, usize)>>;
+    /// Commits only in `a` and only in `b`, or `None` if either does not resolve.
+    fn left_right(&self, path: &Path, a: &str, b: &str) -> Result<Option<(usize, usize)>>;
+    fn submodules(&self, path: &Path) -> Result<Vec<SubmoduleState>>;
+}
+
+/// Picks the in-process backend when compiled in, unless overridden.
+pub fn select() -> Box<dyn GitBackend> {
+    if std::env::var("TOAD_GIT_BACKEND").is_ok_and(|v| v == "cli") {
+        return Box::new(CliBackend);
+    }
+    #[cfg(feature = "libgit2")]
+    {
+        Box::new(Git2Backend)
+    }
+    #[cfg(not(feature = "libgit2"))]
+    {
+        Box::new(CliBackend)
+    }
+}
+
+pub struct CliBackend;
+
+impl GitBackend for CliBackend {
+    fn current_branch(&self, path: &Path) -> Result<Option<String>> {
+        Ok(git::probe(path, &["symbolic-ref", "--short", "-q", "HEAD"]))
+    }
+
+    fn dirty_files(&self, path: &Path) -> Result<DirtyFiles> {
+        let mut files = DirtyFiles::default();
+        for line in git::output(path, &["status", "--porcelain"])?.lines() {
+            if line.starts_with("??") {
+                files.untracked += 1;
+            } else {
+                files.changed += 1;
+            }
+        }
+        Ok(files)
+    }
+
+    fn ahead_behind(&self, path: &Path) -> Result<Option<(usize, usize)>> {
+        self.left_right(path, "HEAD", "@{u}")
+    }
+
+    fn left_right(&self, path: &Path, a: &str, b: &str) -> Result<Option<(usize, usize)>> {
+        let Some(counts) = git::probe(
+            path,
+            &[
+                "rev-list",
+                "--left-right",
+                "--count",
+                &format!("{}...{}", a, b),
+            ],
+        ) else {
+            return Ok(None);
+        };
+        let mut parts = counts.split_whitespace().map(|n| n.parse().unwrap_or(0));
+        Ok(Some((parts.next().unwrap_or(0), parts.next().unwrap_or(0))))
+    }
+
+    fn submodules(&self, path: &Path) -> Result<Vec<SubmoduleState>> {
+        if !path.join(".gitmodules").exists() {
+            return Ok(Vec::new());
+        }
+        let declared = git::probe(
+            path,
+            &[
+                "config",
+                "-f",
+                ".gitmodules",
+                "--get-regexp",
+                r"^submodule\..*\.path$",
+            ],
+        )
+        .unwrap_or_default();
+
+        Ok(declared
+            .lines()
+            .filter_map(|line| {
+                let (key, sub_path) = line.split_once(' ')?;
+                let name = key.strip_prefix("submodule.")?.strip_suffix(".path")?;
+                Some(SubmoduleState {
+                    name: name.to_string(),
+                    path: sub_path.to_string(),
+                    expected: git::probe(path, &["rev-parse", &format!("HEAD:{}", sub_path)]),
+                    actual: git::probe(&path.join(sub_path), &["rev-parse", "HEAD"]),
+                })
+            })
+            .collect())
+    }
+}
+
+#[cfg(feature = "libgit2")]
+pub struct Git2Backend;
+
+#[cfg(feature = "libgit2")]
+impl Git2Backend {
+    fn open(path: &Path) -> Option<git2::Repository> {
+        git2::Repository::open(path).ok()
+    }
+}
+
+#[cfg(feature = "libgit2")]
+impl GitBackend for Git2Backend {
+    fn current_branch(&self, path: &Path) -> Result<Option<String>> {
+        let Some(repo) = Self::open(path) else {
+            return CliBackend.current_branch(path);
+        };
+        let head = repo.find_reference("HEAD")?;
+        Ok(head
+            .symbolic_target()
+            .and_then(|t| t.strip_prefix("refs/heads/"))
+            .map(String::from))
+    }
+
+    fn dirty_files(&self, path: &Path) -> Result<DirtyFiles> {
+        let Some(repo) = Self::open(path) else {
+            return CliBackend.dirty_files(path);
+        };
+        let mut opts = git2::StatusOptions::new();
+        opts.include_untracked(true).include_ignored(false);
+        let mut files = DirtyFiles::default();
+        for entry in repo.statuses(Some(&mut opts))?.iter() {
+            if entry.status() == git2::Status::WT_NEW {
+                files.untracked += 1;
+            } else {
+                files.changed += 1;
+            }
+        }
+        Ok(files)
+    }
+
+    fn ahead_behind(&self, path: &Path) -> Result<Option<(usize, usize)>> {
+        let Some(repo) = Self::open(path) else {
+            return CliBackend.ahead_behind(path);
+        };
+        let Ok(head) = repo.head() else {
+            return Ok(None);
+        };
+        if !head.is_branch() {
+            return Ok(None);
+        }
+        let branch = git2::Branch::wrap(head);
+        let (Ok(upstream), Some(local)) = (branch.upstream(), branch.get().target()) else {
+            return Ok(None);
+        };
+        let Some(remote) = upstream.get().target() else {
+            return Ok(None);
+        };
+        Ok(Some(repo.graph_ahead_behind(local, remote)?))
+    }
+
+    fn left_right(&self, path: &Path, a: &str, b: &str) -> Result<Option<(usize, usize)>> {
+        let Some(repo) = Self::open(path) else {
+            return CliBackend.left_right(path, a, b);
+        };
+        let resolve = |rev: &str| -> Option<git2::Oid> {
+            Some(repo.revparse_single(rev).ok()?.peel_to_commit().ok()?.id())
+        };
+        let (Some(a), Some(b)) = (resolve(a), resolve(b)) else {
+            return Ok(None);
+        };
+        Ok(Some(repo.graph_ahead_behind(a, b)?))
+    }
+
+    fn submodules(&self, path: &Path) -> Result<Vec<SubmoduleState>> {
+        let Some(repo) = Self::open(path) else {
+            return CliBackend.submodules(path);
+        };
+        let states = repo
+            .submodules()?
+            .iter()
+            .map(|sub| SubmoduleState {
+                name: sub.name().unwrap_or_default().to_string(),
+                path: sub.path().to_string_lossy().to_string(),
+                expected: sub.head_id().map(|id| id.to_string()),
+                actual: sub.workdir_id().map(|id| id.to_string()),
+            })
+            .collect();
+        Ok(states)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,138 @@
+use super::*;
+use crate::test_support::{commit, git, init};
+use std::fs;
+use std::path::PathBuf;
+use std::time::Instant;
+use tempfile::tempdir;
+
+/// A repository one commit ahead of and one behind its upstream, with a
+/// dirty file, an untracked file and one submodule.
+fn fixture(root: &Path) -> PathBuf {
+    let remote = root.join("remote.git");
+    let work = root.join("work");
+    let other = root.join("other");
+    let core = root.join("core");
+    git(root, &["init", "-q", "--bare", "-b", "main", "remote.git"]);
+    git(root, &["clone", "-q", remote.to_str().unwrap(), "work"]);
+    commit(&work, "a.txt");
+    git(&work, &["push", "-q", "-u", "origin", "main"]);
+
+    git(root, &["clone", "-q", remote.to_str().unwrap(), "other"]);
+    commit(&other, "b.txt");
+    git(&other, &["push", "-q", "origin", "main"]);
+    git(&work, &["fetch", "-q"]);
+
+    fs::create_dir_all(&core).unwrap();
+    git(&core, &["init", "-q", "-b", "main"]);
+    commit(&core, "lib.rs");
+    git(
+        &work,
+        &["submodule", "add", "-q", core.to_str().unwrap(), "core"],
+    );
+    git(&work, &["commit", "-q", "-m", "add core"]);
+
+    fs::write(work.join("a.txt"), "changed").unwrap();
+    fs::write(work.join("new.txt"), "new").unwrap();
+    work
+}
+
+fn assert_backend(backend: &dyn GitBackend, work: &Path) {
+    assert_eq!(
+        backend.current_branch(work).unwrap().as_deref(),
+        Some("main")
+    );
+    assert_eq!(
+        backend.dirty_files(work).unwrap(),
+        DirtyFiles {
+            changed: 1,
+            untracked: 1
+        }
+    );
+    assert_eq!(backend.ahead_behind(work).unwrap(), Some((1, 1)));
+    assert_eq!(
+        backend.left_right(work, "HEAD~1", "HEAD").unwrap(),
+        Some((0, 1))
+    );
+    assert_eq!(
+        backend.left_right(work, "HEAD", "no-such-ref").unwrap(),
+        None
+    );
+    let subs = backend.submodules(work).unwrap();
+    assert_eq!(subs.len(), 1);
+    assert_eq!(subs[0].path, "core");
+    assert!(subs[0].expected.is_some());
+    assert_eq!(subs[0].expected, subs[0].actual);
+}
+
+#[test]
+fn test_cli_backend_reads() {
+    let dir = tempdir().unwrap();
+    let work = fixture(dir.path());
+    assert_backend(&CliBackend, &work);
+}
+
+#[cfg(feature = "libgit2")]
+#[test]
+fn test_git2_backend_matches_cli() {
+    let dir = tempdir().unwrap();
+    let work = fixture(dir.path());
+    assert_backend(&Git2Backend, &work);
+}
+
+/// Times both backends, and the `toad_git` calls they replace, over a
+/// generated pond:
+/// `cargo test -p toad --features libgit2 -- --ignored --nocapture bench_backends`
+/// (`TOAD_BENCH_REPOS` sets the pond size, default 300).
+#[test]
+#[ignore]
+fn bench_backends() {
+    let count: usize = std::env::var("TOAD_BENCH_REPOS")
+        .ok()
+        .and_then(|n| n.parse().ok())
+        .unwrap_or(300);
+    let dir = tempdir().unwrap();
+    let repos: Vec<PathBuf> = (0..count)
+        .map(|i| {
+            let repo = dir.path().join(format!("repo-{}", i));
+            init(&repo);
+            commit(&repo, "README.md");
+            repo
+        })
+        .collect();
+
+    let backends: Vec<(&str, Box<dyn GitBackend>)> = vec![
+        ("cli", Box::new(CliBackend)),
+        #[cfg(feature = "libgit2")]
+        ("libgit2", Box::new(Git2Backend)),
+    ];
+
+    let start = Instant::now();
+    for repo in &repos {
+        toad_git::branch::current_branch(repo).unwrap();
+        toad_git::commit::is_dirty(repo).unwrap();
+        toad_git::run_git(
+            repo,
+            &["rev-list", "--left-right", "--count", "HEAD...@{u}"],
+            "bench",
+        )
+        .unwrap();
+        toad_git::run_git(repo, &["submodule", "status"], "bench").unwrap();
+    }
+    println!(
+        "{:<8} {} repos in {:?}",
+        "toad_git",
+        repos.len(),
+        start.elapsed()
+    );
+
+    for (name, backend) in backends {
+        let start = Instant::now();
+        for repo in &repos {
+            backend.current_branch(repo).unwrap();
+            backend.dirty_files(repo).unwrap();
+            backend.ahead_behind(repo).unwrap();
+            backend.submodules(repo).unwrap();
+        }
+        println!("{:<8} {} repos in {:?}", name, repos.len(), start.elapsed());
+    }
+}
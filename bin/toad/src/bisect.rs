@@ -0,0 +1,442 @@
+//! Ecosystem bisect for `toad bisect`.
+//!
+//! A combined state maps each repository (by path relative to the workspace
+//! root, `.` for the Hub) to a commit. Between a good and a bad state the
+//! candidate states form a timeline: either the Hub's first-parent commits,
+//! each carrying its recorded submodule pointers, or every commit of every
+//! changed repository merged in committer-date order. The timeline is
+//! searched with a binary search that, like `git bisect run`, treats exit code
+//! 125 as "cannot test this state".
+
+use crate::{git, lock};
+use anyhow::{bail, Result};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use toad_core::ProjectDetail;
+
+/// Repository path (relative to the root, `.` for the Hub) to commit.
+pub type State = BTreeMap<String, String>;
+
+pub const HUB: &str = ".";
+
+/// Exit code a test command uses to skip a state it cannot judge.
+const SKIP_CODE: i32 = 125;
+
+#[derive(Debug, Clone)]
+pub struct Step {
+    /// Repository whose commit this step introduces.
+    pub repo: String,
+    pub commit: String,
+    pub summary: String,
+    /// The full combined state after the step.
+    pub state: State,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Verdict {
+    Good,
+    Bad,
+    Skip,
+}
+
+impl Verdict {
+    /// Maps a test exit code: 0 is good, 125 skips, anything else (including
+    /// death by signal) is bad.
+    pub fn from_code(code: Option<i32>) -> Self {
+        match code {
+            Some(0) => Verdict::Good,
+            Some(SKIP_CODE) => Verdict::Skip,
+            _ => Verdict::Bad,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Outcome {
+    /// Index of the first bad step.
+    pub first_bad: usize,
+    /// Skipped steps before `first_bad` that could also be the culprit.
+    pub ambiguous: Vec<usize>,
+    pub tested: usize,
+}
+
+/// The good and bad states of a bisect.
+#[derive(Debug)]
+pub struct Endpoints<'a> {
+    pub good: State,
+    pub bad: State,
+    /// The two Hub refs when bisecting Hub history rather than lockfiles.
+    pub hub_refs: Option<(&'a str, &'a str)>,
+}
+
+/// Reads `good` and `bad` as two lockfiles or as two refs of the Hub at `root`.
+pub fn endpoints<'a>(root: &Path, good: &'a str, bad: &'a str) -> Result<Endpoints<'a>> {
+    let is_file = |r: &str| Path::new(r).is_file();
+    match (is_file(good), is_file(bad)) {
+        (true, true) => Ok(Endpoints {
+            good: lock_state(Path::new(good))?,
+            bad: lock_state(Path::new(bad))?,
+            hub_refs: None,
+        }),
+        (false, false) => {
+            if !root.join(".gitmodules").exists() {
+                bail!("{:?} is not a Hub; pass two lockfiles instead", root);
+            }
+            Ok(Endpoints {
+                good: hub_state(root, good)?,
+                bad: hub_state(root, bad)?,
+                hub_refs: Some((good, bad)),
+            })
+        }
+        _ => bail!("--good and --bad must both be lockfiles or both Hub refs"),
+    }
+}
+
+/// The commits a lockfile pins.
+pub fn lock_state(file: &Path) -> Result<State> {
+    Ok(lock::Lockfile::load(file)?
+        .repos
+        .into_iter()
+        .map(|r| (r.path, r.commit))
+        .collect())
+}
+
+/// Every repository either state covers, with its path. The Hub comes first,
+/// so restoring it never moves a submodule back afterwards.
+pub fn repos(root: &Path, good: &State, bad: &State) -> Vec<(String, PathBuf)> {
+    let mut repos: Vec<(String, PathBuf)> = Vec::new();
+    for repo in good.keys().chain(bad.keys()) {
+        if !repos.iter().any(|(name, _)| name == repo) {
+            let path = if repo == HUB {
+                root.to_path_buf()
+            } else {
+                root.join(repo)
+            };
+            repos.push((repo.clone(), path));
+        }
+    }
+    repos.sort_by_key(|(name, _)| name != HUB);
+    repos
+}
+
+/// Where the test command runs: the root, a directory under it, or a named
+/// project. `projects` is only loaded when `project` is not a directory.
+pub fn test_dir(
+    root: &Path,
+    project: Option<&str>,
+    projects: impl FnOnce() -> Result<Vec<ProjectDetail>>,
+) -> Result<PathBuf> {
+    match project {
+        None => Ok(root.to_path_buf()),
+        Some(p) if root.join(p).is_dir() => Ok(root.join(p)),
+        Some(p) => match projects()?.into_iter().find(|d| d.name == p) {
+            Some(d) => Ok(d.path),
+            None => bail!("No project or repository named '{}'", p),
+        },
+    }
+}
+
+/// Repositories that cannot be checked out safely: missing ones, and ones
+/// whose uncommitted changes a checkout would carry along or refuse over.
+pub fn safety_issues(repos: &[(String, PathBuf)]) -> Vec<(String, &'static str)> {
+    repos
+        .iter()
+        .filter_map(|(name, path)| {
+            let issue = if !path.join(".git").exists() {
+                "Repository is missing"
+            } else if git::tracked_changes(path) > 0 {
+                "Uncommitted changes"
+            } else {
+                return None;
+            };
+            Some((name.clone(), issue))
+        })
+        .collect()
+}
+
+/// Puts every repository back where `original` found it. Returns the ones
+/// that could not be restored, with git's error.
+pub fn restore(root: &Path, original: &lock::Lockfile) -> Result<Vec<(String, String)>> {
+    let mut failed = Vec::new();
+    for entry in &original.repos {
+        let res = lock::restore(root, entry)?;
+        if !res.success {
+            failed.push((entry.name.clone(), res.stderr.trim().to_string()));
+        }
+    }
+    Ok(failed)
+}
+
+/// Submodule pointers recorded in a Hub revision, plus the revision itself.
+pub fn hub_state(root: &Path, rev: &str) -> Result<State> {
+    let commit = git::output(
+        root,
+        &["rev-parse", "--verify", &format!("{}^{{commit}}", rev)],
+    )?;
+    let mut state = State::new();
+    for line in git::output(root, &["ls-tree", "-r", &commit])?.lines() {
+        // "<mode> <type> <sha>\t<path>"; gitlinks are type "commit".
+        let Some((meta, path)) = line.split_once('\t') else {
+            continue;
+        };
+        let mut fields = meta.split_whitespace();
+        if let (Some(_), Some("commit"), Some(sha)) = (fields.next(), fields.next(), fields.next())
+        {
+            state.insert(path.to_string(), sha.to_string());
+        }
+    }
+    state.insert(HUB.to_string(), commit);
+    Ok(state)
+}
+
+/// The Hub's first-parent commits after `good` up to and including `bad`.
+pub fn hub_timeline(root: &Path, good: &str, bad: &str) -> Result<Vec<Step>> {
+    let range = format!("{}..{}", good, bad);
+    let log = git::output(
+        root,
+        &[
+            "log",
+            "--first-parent",
+            "--reverse",
+            "--format=%H %s",
+            &range,
+        ],
+    )?;
+    let mut steps = Vec::new();
+    for line in log.lines() {
+        let (commit, summary) = line.split_once(' ').unwrap_or((line, ""));
+        steps.push(Step {
+            repo: HUB.to_string(),
+            commit: commit.to_string(),
+            summary: summary.to_string(),
+            state: hub_state(root, commit)?,
+        });
+    }
+    Ok(steps)
+}
+
+/// Every commit between the good and bad pointer of each changed repository,
+/// applied one at a time in committer-date order on top of `good`.
+pub fn commit_timeline(root: &Path, good: &State, bad: &State) -> Result<Vec<Step>> {
+    let mut commits = Vec::new();
+    for (repo, to) in bad {
+        let Some(from) = good.get(repo).filter(|from| *from != to) else {
+            continue;
+        };
+        let path = root.join(repo);
+        let range = format!("{}..{}", from, to);
+        let log = git::output(
+            &path,
+            &[
+                "log",
+                "--first-parent",
+                "--reverse",
+                "--format=%ct %H %s",
+                &range,
+            ],
+        )?;
+        for line in log.lines() {
+            let mut parts = line.splitn(3, ' ');
+            let time: u64 = parts.next().unwrap_or_default().parse().unwrap_or(0);
+            let commit = parts.next().unwrap_or_default().to_string();
+            let summary = parts.next().unwrap_or_default().to_string();
+            commits.push((time, repo.clone(), commit, summary));
+        }
+    }
+    // Stable, so each repository's own order survives equal timestamps.
+    commits.sort_by_key(|(time, ..)| *time);
+
+    let mut state = good.clone();
+    Ok(commits
+        .into_iter()
+        .map(|(_, repo, commit, summary)| {
+            state.insert(repo.clone(), commit.clone());
+            Step {
+                repo,
+                commit,
+                summary,
+                state: state.clone(),
+            }
+        })
+        .collect())
+}
+
+/// Binary search over states `0..len`, where state 0 is known good and state
+/// `len - 1` known bad. `test` judges one state by index.
+pub fn search(len: usize, mut test: impl FnMut(usize) -> Result<Verdict>) -> Result<Outcome> {
+    if len < 2 {
+        bail!("bisect needs a good and a bad state");
+    }
+    let (mut good, mut bad) = (0, len - 1);
+    let mut skipped: Vec<usize> = Vec::new();
+    let mut tested = 0;
+
+    loop {
+        let candidates: Vec<usize> = (good + 1..bad).filter(|i| !skipped.contains(i)).collect();
+        if candidates.is_empty() {
+            break;
+        }
+        let center = good + (bad - good) / 2;
+        let mid = *candidates
+            .iter()
+            .min_by_key(|&&i| i.abs_diff(center))
+            .expect("candidates is not empty");
+        tested += 1;
+        match test(mid)? {
+            Verdict::Good => good = mid,
+            Verdict::Bad => bad = mid,
+            Verdict::Skip => skipped.push(mid),
+        }
+    }
+
+    let mut ambiguous: Vec<usize> = skipped
+        .into_iter()
+        .filter(|&i| i > good && i < bad)
+        .collect();
+    ambiguous.sort_unstable();
+    Ok(Outcome {
+        first_bad: bad,
+        ambiguous,
+        tested,
+    })
+}
+
+fn describe(step: &Step) -> String {
+    format!(
+        "{} {} {}",
+        step.repo,
+        git::short(&step.commit),
+        step.summary
+    )
+}
+
+/// Bisects from `good` to `bad` and returns the report. `hub_refs` holds the
+/// two Hub refs when bisecting Hub history; a culprit Hub commit is then
+/// narrowed down to a commit in one of the submodules it moved. `judge`
+/// applies a labelled state, runs the test and returns its verdict. The
+/// caller restores the original checkouts afterwards.
+pub fn run(
+    root: &Path,
+    hub_refs: Option<(&str, &str)>,
+    good: &State,
+    bad: &State,
+    mut judge: impl FnMut(&str, &State) -> Result<Verdict>,
+) -> Result<Vec<String>> {
+    if judge("good endpoint", good)? != Verdict::Good {
+        bail!("the --good state does not pass the test");
+    }
+    if judge("bad endpoint", bad)? != Verdict::Bad {
+        bail!("the --bad state does not fail the test");
+    }
+
+    let start = Step {
+        repo: String::new(),
+        commit: String::new(),
+        summary: String::new(),
+        state: good.clone(),
+    };
+    let mut timeline = vec![start.clone()];
+    match hub_refs {
+        Some((good_ref, bad_ref)) => timeline.extend(hub_timeline(root, good_ref, bad_ref)?),
+        None => timeline.extend(commit_timeline(root, good, bad)?),
+    }
+    if timeline.len() < 2 {
+        bail!("no commits between --good and --bad");
+    }
+    let outcome = search(timeline.len(), |i| {
+        judge(&describe(&timeline[i]), &timeline[i].state)
+    })?;
+    let culprit = &timeline[outcome.first_bad];
+    let mut report = vec![format!(
+        "First bad state: {} ({} of {} states tested)",
+        describe(culprit),
+        outcome.tested,
+        timeline.len() - 1
+    )];
+    for i in &outcome.ambiguous {
+        report.push(format!(
+            "Could also be (skipped): {}",
+            describe(&timeline[*i])
+        ));
+    }
+    if hub_refs.is_none() || !outcome.ambiguous.is_empty() {
+        return Ok(report);
+    }
+
+    // Narrow the Hub commit down to a commit in one of the submodules it moved.
+    let parent = &timeline[outcome.first_bad - 1].state;
+    let without_hub = |state: &State| {
+        let mut state = state.clone();
+        state.remove(HUB);
+        state
+    };
+    let mut base = parent.clone();
+    base.insert(HUB.to_string(), culprit.commit.clone());
+    if judge("Hub commit with previous submodule pointers", &base)? == Verdict::Bad {
+        report.push("The Hub commit's own changes are the culprit.".to_string());
+        return Ok(report);
+    }
+    let mut inner = vec![start];
+    inner[0].state = base;
+    for mut step in commit_timeline(root, &without_hub(parent), &without_hub(&culprit.state))? {
+        step.state.insert(HUB.to_string(), culprit.commit.clone());
+        inner.push(step);
+    }
+    if inner.len() < 2 {
+        return Ok(report);
+    }
+    let narrowed = search(inner.len(), |i| {
+        judge(&describe(&inner[i]), &inner[i].state)
+    })?;
+    report.push(format!(
+        "Culprit commit: {}",
+        describe(&inner[narrowed.first_bad])
+    ));
+    for i in &narrowed.ambiguous {
+        report.push(format!("Could also be (skipped): {}", describe(&inner[*i])));
+    }
+    Ok(report)
+}
+
+/// Checks every repository out at its commit in `state`. The Hub goes first so
+/// its submodules can be initialized before they are moved.
+pub fn apply(root: &Path, state: &State) -> Result<()> {
+    if let Some(hub) = state.get(HUB) {
+        if git::probe(root, &["rev-parse", "HEAD"]).as_deref() != Some(hub.as_str()) {
+            git::output(root, &["checkout", "--quiet", "--detach", hub])?;
+        }
+        git::output(
+            root,
+            &["submodule", "update", "--init", "--recursive", "--quiet"],
+        )?;
+    }
+    for (repo, commit) in state.iter().filter(|(repo, _)| *repo != HUB) {
+        let path = root.join(repo);
+        if git::probe(&path, &["rev-parse", "HEAD"]).as_deref() == Some(commit.as_str()) {
+            continue;
+        }
+        if !git::ref_exists(&path, &format!("{}^{{commit}}", commit)) {
+            let _ = git::probe(&path, &["fetch", "--quiet"]);
+        }
+        git::output(&path, &["checkout", "--quiet", "--detach", commit])?;
+    }
+    Ok(())
+}
+
+/// Runs the test command in `dir` and returns its exit code. Its output goes
+/// to the terminal, as with `git bisect run`.
+pub fn run_test(dir: &Path, command: &[String]) -> Result<Option<i32>> {
+    let Some((program, args)) = command.split_first() else {
+        bail!("no test command given");
+    };
+    let status = Command::new(program)
+        .args(args)
+        .current_dir(dir)
+        .stdin(Stdio::null())
+        .status()?;
+    Ok(status.code())
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,163 @@
+use super::*;
+use crate::test_support::{commit_at, git, init};
+use std::path::PathBuf;
+use tempfile::tempdir;
+
+fn repo(root: &Path, name: &str) -> PathBuf {
+    let path = root.join(name);
+    init(&path);
+    path
+}
+
+#[test]
+fn test_search_finds_first_bad() {
+    // States 0..=9, bad from 6 on.
+    let mut seen = Vec::new();
+    let outcome = search(10, |i| {
+        seen.push(i);
+        Ok(if i >= 6 { Verdict::Bad } else { Verdict::Good })
+    })
+    .unwrap();
+    assert_eq!(outcome.first_bad, 6);
+    assert!(outcome.ambiguous.is_empty());
+    assert!(outcome.tested <= 4, "tested {:?}", seen);
+}
+
+#[test]
+fn test_search_reports_skipped_neighbours() {
+    let outcome = search(6, |i| {
+        Ok(match i {
+            3 => Verdict::Skip,
+            i if i >= 4 => Verdict::Bad,
+            _ => Verdict::Good,
+        })
+    })
+    .unwrap();
+    assert_eq!(outcome.first_bad, 4);
+    assert_eq!(outcome.ambiguous, vec![3]);
+}
+
+#[test]
+fn test_verdict_from_code() {
+    assert_eq!(Verdict::from_code(Some(0)), Verdict::Good);
+    assert_eq!(Verdict::from_code(Some(125)), Verdict::Skip);
+    assert_eq!(Verdict::from_code(Some(1)), Verdict::Bad);
+    assert_eq!(Verdict::from_code(None), Verdict::Bad);
+}
+
+#[test]
+fn test_commit_timeline_interleaves_by_date() {
+    let dir = tempdir().unwrap();
+    let core = repo(dir.path(), "core");
+    let api = repo(dir.path(), "api");
+    let core0 = commit_at(&core, "c0", "1700000000 +0000");
+    let api0 = commit_at(&api, "a0", "1700000000 +0000");
+    let core1 = commit_at(&core, "c1", "1700000100 +0000");
+    let api1 = commit_at(&api, "a1", "1700000200 +0000");
+    let core2 = commit_at(&core, "c2", "1700000300 +0000");
+
+    let good = State::from([("api".to_string(), api0), ("core".to_string(), core0)]);
+    let bad = State::from([
+        ("api".to_string(), api1.clone()),
+        ("core".to_string(), core2.clone()),
+    ]);
+    let steps = commit_timeline(dir.path(), &good, &bad).unwrap();
+    let order: Vec<&str> = steps.iter().map(|s| s.summary.as_str()).collect();
+    assert_eq!(order, vec!["c1", "a1", "c2"]);
+    assert_eq!(steps[0].state["core"], core1);
+    assert_eq!(steps.last().unwrap().state, bad);
+}
+
+#[test]
+fn test_hub_state_reads_pointers() {
+    let dir = tempdir().unwrap();
+    let lib = repo(dir.path(), "lib");
+    let pinned = commit_at(&lib, "lib.rs", "1700000000 +0000");
+    let hub = repo(dir.path(), "hub");
+    git(
+        &hub,
+        &[
+            "submodule",
+            "add",
+            "-q",
+            lib.to_str().unwrap(),
+            "crates/lib",
+        ],
+    );
+    git(&hub, &["commit", "-q", "-m", "add lib"]);
+
+    let state = hub_state(&hub, "HEAD").unwrap();
+    assert_eq!(state["crates/lib"], pinned);
+    assert_eq!(
+        state[HUB],
+        git::output(&hub, &["rev-parse", "HEAD"]).unwrap()
+    );
+    assert_eq!(state.len(), 2);
+}
+
+#[test]
+fn test_run_reports_first_bad_commit() {
+    let dir = tempdir().unwrap();
+    let core = repo(dir.path(), "core");
+    let good = commit_at(&core, "c0", "1700000000 +0000");
+    commit_at(&core, "c1", "1700000100 +0000");
+    let broken = commit_at(&core, "c2", "1700000200 +0000");
+    let bad = commit_at(&core, "c3", "1700000300 +0000");
+    let order: Vec<String> = git::output(&core, &["rev-list", "--reverse", "HEAD"])
+        .unwrap()
+        .lines()
+        .map(String::from)
+        .collect();
+
+    let state = |sha: &str| State::from([("core".to_string(), sha.to_string())]);
+    let report = run(dir.path(), None, &state(&good), &state(&bad), |_, s| {
+        let pos = order.iter().position(|c| *c == s["core"]).unwrap();
+        let broken_pos = order.iter().position(|c| *c == broken).unwrap();
+        Ok(if pos >= broken_pos {
+            Verdict::Bad
+        } else {
+            Verdict::Good
+        })
+    })
+    .unwrap();
+    assert!(report[0].starts_with(&format!("First bad state: core {} c2", git::short(&broken))));
+
+    let err = run(dir.path(), None, &state(&bad), &state(&bad), |_, _| {
+        Ok(Verdict::Bad)
+    });
+    assert!(err.is_err());
+}
+
+#[test]
+fn test_repos_put_hub_first_and_flag_unsafe_ones() {
+    let dir = tempdir().unwrap();
+    let lib = repo(dir.path(), "lib");
+    let commit = commit_at(&lib, "lib.rs", "1700000000 +0000");
+    std::fs::write(lib.join("lib.rs"), "changed").unwrap();
+
+    let good = State::from([("lib".to_string(), commit.clone())]);
+    let bad = State::from([
+        ("lib".to_string(), commit.clone()),
+        ("gone".to_string(), commit.clone()),
+        (HUB.to_string(), commit),
+    ]);
+    let repos = repos(dir.path(), &good, &bad);
+    let names: Vec<_> = repos.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, vec![HUB, "lib", "gone"]);
+    assert_eq!(repos[0].1, dir.path());
+
+    let issues = safety_issues(&repos[1..]);
+    assert_eq!(
+        issues,
+        vec![
+            ("lib".to_string(), "Uncommitted changes"),
+            ("gone".to_string(), "Repository is missing"),
+        ]
+    );
+
+    assert_eq!(
+        test_dir(dir.path(), Some("lib"), || unreachable!()).unwrap(),
+        lib
+    );
+    assert!(test_dir(dir.path(), Some("nope"), || Ok(Vec::new())).is_err());
+}
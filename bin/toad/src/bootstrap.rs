@@ -0,0 +1,295 @@
+//! Ecosystem bootstrap for `toad clone <manifest>`.
+//!
+//! The manifest is either a hand-written TOML file or a `toad.lock`:
+//!
+//! ```toml
+//! [context]
+//! name = "pond"
+//!
+//! [[project]]
+//! name = "lily"
+//! remote = "git@github.com:org/lily.git"
+//! branch = "main"
+//! tags = ["backend", "rust"]
+//! path = "services/lily"   # relative to the projects dir, defaults to `name`
+//! ```
+
+use crate::git;
+use crate::lock::Lockfile;
+use crate::mirror;
+use anyhow::{bail, Context, Result};
+use rayon::prelude::*;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use toad_core::{GlobalConfig, TagRegistry};
+
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct Manifest {
+    #[serde(default)]
+    pub context: ContextSpec,
+    #[serde(default, rename = "project")]
+    pub projects: Vec<ManifestProject>,
+    /// Remote of a lockfile's Hub root entry, which is not cloned as a project.
+    #[serde(skip)]
+    pub hub_remote: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct ContextSpec {
+    pub name: Option<String>,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ManifestProject {
+    pub name: String,
+    pub remote: String,
+    pub branch: Option<String>,
+    /// Exact commit to check out after cloning (set for lockfiles).
+    pub commit: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Target subdirectory relative to the projects dir.
+    pub path: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub enum CloneOutcome {
+    Cloned,
+    Exists,
+    Failed(String),
+}
+
+/// Whether the cloned ecosystem can be registered under its context name.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Registration {
+    /// The context already points at this root.
+    Registered,
+    /// The name is taken by a context at another path.
+    Taken(PathBuf),
+    New,
+}
+
+impl ManifestProject {
+    pub fn target(&self, projects_dir: &Path) -> PathBuf {
+        projects_dir.join(self.path.as_deref().unwrap_or(&self.name))
+    }
+}
+
+impl Manifest {
+    /// Reads a manifest, accepting `toad.lock` files as well.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content =
+            fs::read_to_string(path).with_context(|| format!("Could not read {:?}", path))?;
+        let table: toml::Table =
+            toml::from_str(&content).with_context(|| format!("Invalid manifest {:?}", path))?;
+
+        let mut manifest = if table.contains_key("repo") {
+            Self::from_lock(&Lockfile::load(path)?)
+        } else {
+            toml::from_str::<Self>(&content)
+                .with_context(|| format!("Invalid manifest {:?}", path))?
+        };
+        manifest.validate()?;
+
+        // Local remotes are written relative to the manifest, not the caller's cwd.
+        let base = path.parent().unwrap_or(Path::new("."));
+        for p in &mut manifest.projects {
+            let local = base.join(&p.remote);
+            if Path::new(&p.remote).is_relative() && local.exists() {
+                p.remote = fs::canonicalize(local)?.to_string_lossy().to_string();
+            }
+        }
+        Ok(manifest)
+    }
+
+    /// Converts a lockfile into clone targets. Entries nested inside another
+    /// entry are submodules and come along with their parent's clone.
+    ///
+    /// The Hub root entry (`.`) is the workspace itself rather than a project:
+    /// it is not cloned and does not count as a parent, so every top-level
+    /// project is still restored. Its remote is kept in `hub_remote`.
+    pub fn from_lock(lock: &Lockfile) -> Self {
+        let (root, repos): (Vec<_>, Vec<_>) = lock.repos.iter().partition(|r| r.path == ".");
+        let is_nested = |path: &str| {
+            repos
+                .iter()
+                .any(|other| other.path != path && Path::new(path).starts_with(&other.path))
+        };
+
+        let projects = repos
+            .iter()
+            .filter(|r| !is_nested(&r.path))
+            .filter_map(|r| {
+                let remote = r.remote.clone()?;
+                let name = Path::new(&r.path)
+                    .file_name()?
+                    .to_string_lossy()
+                    .to_string();
+                Some(ManifestProject {
+                    name,
+                    remote,
+                    branch: r.branch.clone(),
+                    commit: Some(r.commit.clone()),
+                    tags: Vec::new(),
+                    path: None,
+                })
+            })
+            .collect();
+
+        Self {
+            context: ContextSpec::default(),
+            projects,
+            hub_remote: root.first().and_then(|r| r.remote.clone()),
+        }
+    }
+
+    fn validate(&self) -> Result<()> {
+        let mut seen = HashSet::new();
+        for p in &self.projects {
+            let target = p.path.as_deref().unwrap_or(&p.name);
+            let escapes = Path::new(target)
+                .components()
+                .any(|c| !matches!(c, Component::Normal(_)));
+            if target.is_empty() || escapes {
+                bail!(
+                    "Project '{}' has an invalid target path '{}' (must stay inside the projects dir).",
+                    p.name,
+                    target
+                );
+            }
+            if !seen.insert(target.to_string()) {
+                bail!("Two projects share the target path '{}'.", target);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The ecosystem root and projects directory to clone into: `dest`, else the
+/// current workspace (root, projects dir), else the current directory.
+pub fn target_dirs(
+    dest: Option<&Path>,
+    workspace: Option<(&Path, &Path)>,
+    cwd: &Path,
+) -> (PathBuf, PathBuf) {
+    match (dest, workspace) {
+        (Some(d), _) => (d.to_path_buf(), d.join("projects")),
+        (None, Some((root, projects))) => (root.to_path_buf(), projects.to_path_buf()),
+        (None, None) => (cwd.to_path_buf(), cwd.join("projects")),
+    }
+}
+
+/// `--name`, else the manifest's context name, else the root directory's name.
+pub fn context_name(name: Option<&str>, manifest: &Manifest, root: &Path) -> String {
+    name.map(String::from)
+        .or_else(|| manifest.context.name.clone())
+        .unwrap_or_else(|| {
+            root.file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("default")
+                .to_string()
+        })
+}
+
+pub fn registration(config: &GlobalConfig, name: &str, root: &Path) -> Registration {
+    match config.project_contexts.get(name) {
+        Some(ctx) if ctx.path == root => Registration::Registered,
+        Some(ctx) => Registration::Taken(ctx.path.clone()),
+        None => Registration::New,
+    }
+}
+
+/// Clones every project in parallel; see [`clone_project`].
+pub fn clone_all<'a>(
+    manifest: &'a Manifest,
+    projects_dir: &Path,
+    rewrites: &[String],
+) -> Vec<(&'a ManifestProject, CloneOutcome)> {
+    manifest
+        .projects
+        .par_iter()
+        .map(|p| (p, clone_project(p, projects_dir, rewrites)))
+        .collect()
+}
+
+/// Adds the manifest tags of every project that is on disk after the clone;
+/// returns how many were added.
+pub fn seed_tags(tags: &mut TagRegistry, outcomes: &[(&ManifestProject, CloneOutcome)]) -> usize {
+    let mut added = 0;
+    for (p, outcome) in outcomes {
+        if matches!(outcome, CloneOutcome::Failed(_)) {
+            continue;
+        }
+        for t in &p.tags {
+            tags.add_tag(&p.name, t);
+            added += 1;
+        }
+    }
+    added
+}
+
+/// Clones one project, checks out the pinned commit and initializes submodules.
+/// `rewrites` are [`mirror::rewrite_args`]; objects come from the local
+/// mirrors where possible and the remote is fetched afterwards to catch up.
+pub fn clone_project(
+    project: &ManifestProject,
+    projects_dir: &Path,
+    rewrites: &[String],
+) -> CloneOutcome {
+    let target = project.target(projects_dir);
+    if target.exists() {
+        return CloneOutcome::Exists;
+    }
+    if let Some(parent) = target.parent() {
+        if let Err(e) = fs::create_dir_all(parent) {
+            return CloneOutcome::Failed(e.to_string());
+        }
+    }
+
+    let target_str = target.to_string_lossy().to_string();
+    let mut args = vec!["clone", "--quiet"];
+    if let Some(branch) = &project.branch {
+        args.extend(["--branch", branch]);
+    }
+    args.extend([project.remote.as_str(), target_str.as_str()]);
+    match mirror::run_via(rewrites, projects_dir, &args, &project.name) {
+        Ok(res) if res.success => {}
+        Ok(res) => return CloneOutcome::Failed(res.stderr.trim().to_string()),
+        Err(e) => return CloneOutcome::Failed(e.to_string()),
+    }
+    if !rewrites.is_empty() {
+        // Offline is fine: the mirror's state is what we have.
+        let _ = git::probe(&target, &["fetch", "--quiet"]);
+        if project.commit.is_none() {
+            let _ = git::probe(&target, &["merge", "--ff-only", "--quiet", "@{upstream}"]);
+        }
+    }
+
+    if let Some(commit) = &project.commit {
+        if git::probe(&target, &["rev-parse", "HEAD"]).as_deref() != Some(commit.as_str()) {
+            if let Err(e) = git::output(&target, &["checkout", "--quiet", "--detach", commit]) {
+                return CloneOutcome::Failed(e.to_string());
+            }
+        }
+    }
+
+    if target.join(".gitmodules").exists() {
+        match mirror::run_via(
+            rewrites,
+            &target,
+            &["submodule", "update", "--init", "--recursive", "--quiet"],
+            &project.name,
+        ) {
+            Ok(res) if res.success => {}
+            Ok(res) => return CloneOutcome::Failed(res.stderr.trim().to_string()),
+            Err(e) => return CloneOutcome::Failed(e.to_string()),
+        }
+    }
+    CloneOutcome::Cloned
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,110 @@
+use super::*;
+use crate::lock::LockedRepo;
+
+fn locked(path: &str, remote: Option<&str>) -> LockedRepo {
+    LockedRepo {
+        name: path.to_string(),
+        path: path.to_string(),
+        remote: remote.map(String::from),
+        branch: Some("main".to_string()),
+        commit: "abc123".to_string(),
+    }
+}
+
+#[test]
+fn test_manifest_from_lock_skips_submodules() {
+    let lock = Lockfile {
+        version: 1,
+        repos: vec![
+            locked("projects/lily", Some("https://example.com/lily.git")),
+            locked(
+                "projects/lily/vendor/pad",
+                Some("https://example.com/pad.git"),
+            ),
+            locked("projects/newt", None),
+        ],
+    };
+    let manifest = Manifest::from_lock(&lock);
+    assert_eq!(manifest.projects.len(), 1);
+    assert_eq!(manifest.projects[0].name, "lily");
+    assert_eq!(manifest.projects[0].commit.as_deref(), Some("abc123"));
+}
+
+#[test]
+fn test_manifest_rejects_escaping_and_duplicate_paths() {
+    let project = |name: &str, path: Option<&str>| ManifestProject {
+        name: name.to_string(),
+        remote: "https://example.com/x.git".to_string(),
+        branch: None,
+        commit: None,
+        tags: Vec::new(),
+        path: path.map(String::from),
+    };
+    let escaping = Manifest {
+        projects: vec![project("lily", Some("../lily"))],
+        ..Default::default()
+    };
+    assert!(escaping.validate().is_err());
+
+    let duplicate = Manifest {
+        projects: vec![project("lily", None), project("other", Some("lily"))],
+        ..Default::default()
+    };
+    assert!(duplicate.validate().is_err());
+}
+
+#[test]
+fn test_manifest_from_lock_keeps_projects_under_hub_root() {
+    let lock = Lockfile {
+        version: 1,
+        repos: vec![
+            locked(".", Some("https://example.com/hub.git")),
+            locked("projects/lily", Some("https://example.com/lily.git")),
+            locked(
+                "projects/lily/vendor/pad",
+                Some("https://example.com/pad.git"),
+            ),
+            locked("projects/newt", Some("https://example.com/newt.git")),
+        ],
+    };
+    let manifest = Manifest::from_lock(&lock);
+    let names: Vec<_> = manifest.projects.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(names, vec!["lily", "newt"]);
+    assert_eq!(
+        manifest.hub_remote.as_deref(),
+        Some("https://example.com/hub.git")
+    );
+}
+
+#[test]
+fn test_clone_targets_and_context_name() {
+    let cwd = Path::new("/work");
+    let ws = Some((Path::new("/pond"), Path::new("/pond/projects")));
+    assert_eq!(
+        target_dirs(Some(Path::new("/dest")), ws, cwd),
+        (PathBuf::from("/dest"), PathBuf::from("/dest/projects"))
+    );
+    assert_eq!(
+        target_dirs(None, ws, cwd),
+        (PathBuf::from("/pond"), PathBuf::from("/pond/projects"))
+    );
+    assert_eq!(
+        target_dirs(None, None, cwd),
+        (PathBuf::from("/work"), PathBuf::from("/work/projects"))
+    );
+
+    let mut manifest = Manifest::default();
+    assert_eq!(
+        context_name(None, &manifest, Path::new("/eco/lily")),
+        "lily"
+    );
+    manifest.context.name = Some("pond".to_string());
+    assert_eq!(
+        context_name(None, &manifest, Path::new("/eco/lily")),
+        "pond"
+    );
+    assert_eq!(
+        context_name(Some("newt"), &manifest, Path::new("/eco/lily")),
+        "newt"
+    );
+}
@@ -0,0 +1,92 @@
+//! Generated Hub root commit messages for `ggit commit --cascade`.
+
+use crate::backend::GitBackend;
+use crate::git;
+use std::path::Path;
+
+/// A submodule whose checked-out commit differs from the pointer in the Hub's HEAD.
+#[derive(Debug, Clone)]
+pub struct PointerMove {
+    pub path: String,
+    /// `None` when the submodule is new to the Hub.
+    pub from: Option<String>,
+    pub to: String,
+    /// Commits in `from..to`, merges included; `None` if the range could not
+    /// be resolved.
+    pub commits: Option<usize>,
+    /// One-line log of the non-merge commits in `from..to`.
+    pub shortlog: Vec<String>,
+}
+
+/// Collects the pointer moves the next Hub commit would record.
+pub fn pointer_moves(backend: &dyn GitBackend, hub: &Path) -> Vec<PointerMove> {
+    let mut moves = Vec::new();
+    for sub in backend.submodules(hub).unwrap_or_default() {
+        let (path, from) = (sub.path, sub.expected);
+        let Some(to) = sub.actual else {
+            continue;
+        };
+        if from.as_deref() == Some(to.as_str()) {
+            continue;
+        }
+
+        let sub_path = hub.join(&path);
+        let range = from.as_ref().map(|from| format!("{}..{}", from, to));
+        let commits = range
+            .as_ref()
+            .and_then(|range| git::probe(&sub_path, &["rev-list", "--count", range]))
+            .and_then(|n| n.parse().ok());
+        let shortlog = range
+            .as_ref()
+            .and_then(|range| {
+                git::probe(&sub_path, &["log", "--format=%h %s", "--no-merges", range])
+            })
+            .map(|out| out.lines().map(String::from).collect())
+            .unwrap_or_default();
+
+        moves.push(PointerMove {
+            path,
+            from,
+            to,
+            commits,
+            shortlog,
+        });
+    }
+    moves
+}
+
+/// Builds the Hub commit message: the user's subject plus one section per moved pointer.
+pub fn hub_message(subject: &str, moves: &[PointerMove]) -> String {
+    if moves.is_empty() {
+        return subject.to_string();
+    }
+
+    let mut body = format!("{}\n\nSubmodule updates:\n", subject);
+    for m in moves {
+        let from = m.from.as_deref().map(git::short).unwrap_or("new");
+        let count = match m.commits {
+            Some(1) => " (1 commit)".to_string(),
+            Some(n) => format!(" ({} commits)", n),
+            None => String::new(),
+        };
+        body.push_str(&format!(
+            "\n* {} {}..{}{}\n",
+            m.path,
+            from,
+            git::short(&m.to),
+            count
+        ));
+        if m.from.is_none() {
+            body.push_str("  (new submodule)\n");
+        } else if m.commits.is_none() {
+            body.push_str("  (history not available locally)\n");
+        }
+        for line in &m.shortlog {
+            body.push_str(&format!("  - {}\n", line));
+        }
+    }
+    body.trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,63 @@
+use super::*;
+use crate::backend::CliBackend;
+use crate::test_support::git;
+use std::fs;
+use tempfile::tempdir;
+
+#[test]
+fn test_pointer_moves_and_hub_message() {
+    let dir = tempdir().unwrap();
+    let core = dir.path().join("core");
+    let hub = dir.path().join("hub");
+    fs::create_dir_all(&core).unwrap();
+    fs::create_dir_all(&hub).unwrap();
+
+    git(&core, &["init", "-q", "-b", "main"]);
+    fs::write(core.join("lib.rs"), "").unwrap();
+    git(&core, &["add", "-A"]);
+    git(&core, &["commit", "-q", "-m", "init"]);
+
+    git(&hub, &["init", "-q", "-b", "main"]);
+    git(
+        &hub,
+        &[
+            "submodule",
+            "add",
+            "-q",
+            core.to_str().unwrap(),
+            "crates/core",
+        ],
+    );
+    git(&hub, &["commit", "-q", "-m", "add core"]);
+    assert!(pointer_moves(&CliBackend, &hub).is_empty());
+
+    let sub = hub.join("crates/core");
+    fs::write(sub.join("lib.rs"), "pub fn ribbit() {}").unwrap();
+    git(&sub, &["commit", "-q", "-am", "feat: ribbit"]);
+
+    let moves = pointer_moves(&CliBackend, &hub);
+    assert_eq!(moves.len(), 1);
+    assert_eq!(moves[0].path, "crates/core");
+    assert_eq!(moves[0].shortlog.len(), 1);
+
+    let message = hub_message("chore: bump core", &moves);
+    assert!(message.starts_with("chore: bump core\n\nSubmodule updates:\n"));
+    assert!(message.contains("* crates/core "));
+    assert!(message.contains("(1 commit)\n"));
+    assert!(message.ends_with("feat: ribbit"));
+
+    // Merges count towards the total but stay out of the shortlog.
+    git(&sub, &["checkout", "-q", "-b", "side"]);
+    fs::write(sub.join("side.rs"), "").unwrap();
+    git(&sub, &["add", "-A"]);
+    git(&sub, &["commit", "-q", "-m", "feat: side"]);
+    git(&sub, &["checkout", "-q", "-"]);
+    git(
+        &sub,
+        &["merge", "-q", "--no-ff", "-m", "merge side", "side"],
+    );
+    let moves = pointer_moves(&CliBackend, &hub);
+    assert_eq!(moves[0].commits, Some(3));
+    assert_eq!(moves[0].shortlog.len(), 2);
+    assert!(hub_message("chore: bump core", &moves).contains("(3 commits)"));
+}
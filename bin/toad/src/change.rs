@@ -0,0 +1,359 @@
+//! Cross-repository change sets for `toad change`.
+//!
+//! A change set is one feature branch created in several repositories at
+//! once. Its membership, and the branch each repository started from, is kept
+//! in `changes.json` in the active context's directory so `status` and
+//! `finish` work on the same repositories `start` touched.
+
+use crate::backend::GitBackend;
+use crate::git;
+use anyhow::{anyhow, bail, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use toad_core::{GitOpResult, GlobalConfig, ProjectDetail, Workspace};
+
+const CHANGES_FILE: &str = "changes.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Member {
+    pub name: String,
+    pub path: PathBuf,
+    /// Branch the repository was on when the change started; `finish` merges back into it.
+    pub base: String,
+    /// Commit the change branch was created at.
+    pub start_commit: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeSet {
+    pub name: String,
+    pub branch: String,
+    pub created_at: u64,
+    pub members: Vec<Member>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ChangeRegistry {
+    #[serde(default)]
+    pub changes: BTreeMap<String, ChangeSet>,
+}
+
+impl ChangeRegistry {
+    pub fn path(workspace: &Workspace) -> Result<PathBuf> {
+        let dir = match &workspace.active_context {
+            Some(name) => GlobalConfig::context_dir(name, None)?,
+            None => GlobalConfig::config_dir(None)?,
+        };
+        Ok(dir.join(CHANGES_FILE))
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// The repositories a change starts in: each project's submodules before the
+/// project itself, then the Hub root, kept when a label matches any of `query`.
+pub fn repos(
+    root: &Path,
+    projects: Vec<ProjectDetail>,
+    query: &[String],
+) -> Vec<(String, PathBuf)> {
+    let mut repos = Vec::new();
+    for p in projects {
+        for sub in &p.submodules {
+            repos.push((format!("{} > {}", p.name, sub.name), root.join(&sub.path)));
+        }
+        repos.push((p.name.clone(), p.path.clone()));
+    }
+    if root.join(".gitmodules").exists() && !repos.iter().any(|(_, path)| path == root) {
+        repos.push(("Hub Root".to_string(), root.to_path_buf()));
+    }
+    repos.retain(|(label, _)| {
+        query.is_empty()
+            || query
+                .iter()
+                .any(|q| label.to_lowercase().contains(&q.to_lowercase()))
+    });
+    repos
+}
+
+/// Problems that stop a repository from joining a change set.
+pub fn start_issues(path: &Path, branch: &str) -> Vec<String> {
+    let mut issues = Vec::new();
+    match git::probe(path, &["symbolic-ref", "--short", "-q", "HEAD"]) {
+        None => issues.push("Detached HEAD — check out a base branch first".to_string()),
+        Some(current) if current == branch => {
+            if adopted_base(path, branch).is_none() {
+                issues.push("No default branch to merge back into".to_string());
+            }
+        }
+        Some(_) => {
+            if git::ref_exists(path, &format!("refs/heads/{}", branch)) {
+                issues.push(format!("Branch '{}' already exists", branch));
+            }
+        }
+    }
+    if git::tracked_changes(path) > 0 {
+        issues.push("Uncommitted changes".to_string());
+    }
+    issues
+}
+
+/// Base for a change branch that is already checked out: the default branch,
+/// when it exists locally and is not the change branch itself.
+fn adopted_base(path: &Path, branch: &str) -> Option<String> {
+    crate::prune::default_branch(path)
+        .filter(|base| base != branch && git::ref_exists(path, &format!("refs/heads/{}", base)))
+}
+
+/// Creates the change branch from the current branch, or adopts it when it
+/// is already checked out. `None` for the result means nothing had to run.
+pub fn start(path: &Path, name: &str, branch: &str) -> Result<(Member, Option<GitOpResult>)> {
+    let Some(current) = git::probe(path, &["symbolic-ref", "--short", "-q", "HEAD"]) else {
+        bail!("{} has a detached HEAD", name);
+    };
+    let (base, res) = if current == branch {
+        let Some(base) = adopted_base(path, branch) else {
+            bail!(
+                "{} has no default branch for '{}' to merge back into",
+                name,
+                branch
+            );
+        };
+        (base, None)
+    } else {
+        let res = toad_git::run_git(path, &["checkout", "-q", "-b", branch], name)?;
+        (current, Some(res))
+    };
+    let member = Member {
+        name: name.to_string(),
+        path: path.to_path_buf(),
+        start_commit: git::probe(path, &["merge-base", &base, "HEAD"]),
+        base,
+    };
+    Ok((member, res))
+}
+
+/// Starts change `name` in every repository and records the ones that joined
+/// in `registry`, saved to `registry_path`. Each outcome is `None` when an
+/// already checked-out branch was adopted. The registry is saved even when a
+/// repository errors partway, so branches already created stay tracked.
+pub fn start_all(
+    registry: &mut ChangeRegistry,
+    registry_path: &Path,
+    name: &str,
+    branch: &str,
+    repos: &[(String, PathBuf)],
+) -> Result<Vec<(String, Option<GitOpResult>)>> {
+    let mut members = Vec::new();
+    let mut outcomes = Vec::new();
+    let mut error = None;
+    for (label, path) in repos {
+        match start(path, label, branch) {
+            Ok((member, res)) => {
+                if res.as_ref().is_none_or(|r| r.success) {
+                    members.push(member);
+                }
+                outcomes.push((label.clone(), res));
+            }
+            Err(e) => {
+                error = Some(e);
+                break;
+            }
+        }
+    }
+
+    let joined = members.len();
+    if !members.is_empty() {
+        registry.changes.insert(
+            name.to_string(),
+            ChangeSet {
+                name: name.to_string(),
+                branch: branch.to_string(),
+                created_at: std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .map(|d| d.as_secs())
+                    .unwrap_or(0),
+                members,
+            },
+        );
+        registry.save(registry_path)?;
+    }
+    match error {
+        Some(e) => Err(anyhow!(
+            "{} ({} of {} repositories joined '{}' first)",
+            e,
+            joined,
+            repos.len(),
+            name
+        )),
+        None => Ok(outcomes),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MergeState {
+    /// The change branch no longer exists locally.
+    Missing,
+    /// No commits on the branch yet.
+    Empty,
+    Unmerged,
+    /// Merged into the local base branch.
+    Merged,
+    /// Merged into the base on the remote (e.g. through a pull request).
+    MergedRemote,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PushState {
+    /// No remote copy of the branch.
+    NotPushed,
+    UpToDate,
+    Ahead(usize),
+    Behind(usize),
+    Diverged(usize, usize),
+}
+
+#[derive(Debug, Clone)]
+pub struct MemberStatus {
+    pub checked_out: bool,
+    /// `%h %s` lines for commits on the branch but not on the base.
+    pub commits: Vec<String>,
+    pub push: PushState,
+    pub merge: MergeState,
+}
+
+pub fn status(backend: &dyn GitBackend, member: &Member, branch: &str) -> MemberStatus {
+    let path = &member.path;
+    let local = format!("refs/heads/{}", branch);
+    let checked_out =
+        git::probe(path, &["symbolic-ref", "--short", "-q", "HEAD"]).as_deref() == Some(branch);
+    if !git::ref_exists(path, &local) {
+        return MemberStatus {
+            checked_out,
+            commits: Vec::new(),
+            push: PushState::NotPushed,
+            merge: MergeState::Missing,
+        };
+    }
+
+    let remote = git::default_remote(path);
+    let remote_branch = remote
+        .as_ref()
+        .map(|r| format!("refs/remotes/{}/{}", r, branch))
+        .filter(|r| git::ref_exists(path, r));
+    let push = match &remote_branch {
+        None => PushState::NotPushed,
+        Some(remote_branch) => match backend
+            .left_right(path, &local, remote_branch)
+            .ok()
+            .flatten()
+            .unwrap_or_default()
+        {
+            (0, 0) => PushState::UpToDate,
+            (ahead, 0) => PushState::Ahead(ahead),
+            (0, behind) => PushState::Behind(behind),
+            (ahead, behind) => PushState::Diverged(ahead, behind),
+        },
+    };
+
+    // `None` when the log could not be read, e.g. the base branch is gone.
+    let commits: Option<Vec<String>> = git::probe(
+        path,
+        &[
+            "log",
+            "--format=%h %s",
+            &format!("refs/heads/{}..{}", member.base, local),
+        ],
+    )
+    .map(|out| out.lines().map(String::from).collect());
+
+    let remote_base = remote
+        .map(|r| format!("refs/remotes/{}/{}", r, member.base))
+        .filter(|r| git::ref_exists(path, r));
+    let tip = git::probe(path, &["rev-parse", &local]);
+    let merge = if tip.is_some() && tip == member.start_commit {
+        MergeState::Empty
+    } else if commits.as_ref().is_some_and(|c| c.is_empty()) {
+        MergeState::Merged
+    } else if remote_base
+        .as_ref()
+        .is_some_and(|base| is_ancestor(path, &local, base))
+    {
+        MergeState::MergedRemote
+    } else {
+        MergeState::Unmerged
+    };
+
+    MemberStatus {
+        checked_out,
+        commits: commits.unwrap_or_default(),
+        push,
+        merge,
+    }
+}
+
+fn is_ancestor(path: &Path, ancestor: &str, of: &str) -> bool {
+    git::probe(path, &["merge-base", "--is-ancestor", ancestor, of]).is_some()
+}
+
+/// Ends one repository's part of a change. With `merge`, the branch is first
+/// merged into the base with `--no-ff`. The branch is always deleted with
+/// `branch -d`, so git refuses if its commits are not merged after all.
+pub fn finish(member: &Member, branch: &str, merge: bool) -> Result<Vec<GitOpResult>> {
+    let path = &member.path;
+    let mut results = Vec::new();
+    let on_branch =
+        git::probe(path, &["symbolic-ref", "--short", "-q", "HEAD"]).as_deref() == Some(branch);
+    if merge || on_branch {
+        let res = toad_git::run_git(path, &["checkout", "-q", &member.base], &member.name)?;
+        let ok = res.success;
+        results.push(res);
+        if !ok {
+            return Ok(results);
+        }
+    }
+    if merge {
+        // Exempt from the commit policy like every merge commit (see `policy`).
+        let res = toad_git::run_git(
+            path,
+            &[
+                "merge",
+                "--no-ff",
+                "--no-edit",
+                "-m",
+                &format!("Merge change '{}'", branch),
+                branch,
+            ],
+            &member.name,
+        )?;
+        let ok = res.success;
+        results.push(res);
+        if !ok {
+            return Ok(results);
+        }
+    }
+    results.push(toad_git::run_git(
+        path,
+        &["branch", "-d", branch],
+        &member.name,
+    )?);
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests;
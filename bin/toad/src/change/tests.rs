@@ -0,0 +1,115 @@
+use super::*;
+use crate::backend::CliBackend;
+use crate::test_support::{cloned_remote, commit, git};
+use tempfile::tempdir;
+
+#[test]
+fn test_change_lifecycle() {
+    let dir = tempdir().unwrap();
+    let work = cloned_remote(dir.path());
+
+    assert!(start_issues(&work, "feat-x").is_empty());
+    let (member, res) = start(&work, "work", "feat-x").unwrap();
+    assert!(res.unwrap().success);
+    assert_eq!(member.base, "main");
+    assert_eq!(
+        status(&CliBackend, &member, "feat-x").merge,
+        MergeState::Empty
+    );
+
+    commit(&work, "feature.rs");
+    let st = status(&CliBackend, &member, "feat-x");
+    assert!(st.checked_out);
+    assert_eq!(st.merge, MergeState::Unmerged);
+    assert_eq!(st.push, PushState::NotPushed);
+    assert!(st.commits[0].ends_with("feature.rs"));
+
+    git(&work, &["push", "-q", "-u", "origin", "feat-x"]);
+    assert_eq!(
+        status(&CliBackend, &member, "feat-x").push,
+        PushState::UpToDate
+    );
+
+    let results = finish(&member, "feat-x", true).unwrap();
+    assert!(results.iter().all(|r| r.success));
+    assert!(work.join("feature.rs").exists());
+    assert_eq!(
+        git::probe(&work, &["symbolic-ref", "--short", "HEAD"]).as_deref(),
+        Some("main")
+    );
+    assert_eq!(
+        status(&CliBackend, &member, "feat-x").merge,
+        MergeState::Missing
+    );
+}
+
+#[test]
+fn test_start_adopts_checked_out_branch_and_blocks_existing() {
+    let dir = tempdir().unwrap();
+    let work = cloned_remote(dir.path());
+    git(&work, &["checkout", "-q", "-b", "feat-y"]);
+
+    let (member, res) = start(&work, "work", "feat-y").unwrap();
+    assert!(res.is_none());
+    assert_eq!(member.base, "main");
+
+    git(&work, &["checkout", "-q", "main"]);
+    assert_eq!(
+        start_issues(&work, "feat-y"),
+        vec!["Branch 'feat-y' already exists".to_string()]
+    );
+}
+
+#[test]
+fn test_missing_base_is_never_merged_and_adopting_needs_a_base() {
+    let dir = tempdir().unwrap();
+    let work = cloned_remote(dir.path());
+    let (member, _) = start(&work, "work", "feat-z").unwrap();
+    commit(&work, "feature.rs");
+    git(&work, &["branch", "-q", "-D", "main"]);
+    git(&work, &["update-ref", "-d", "refs/remotes/origin/main"]);
+    assert_eq!(
+        status(&CliBackend, &member, "feat-z").merge,
+        MergeState::Unmerged
+    );
+
+    // Neither a remote HEAD nor a local main/master is left to merge into.
+    git(&work, &["remote", "set-head", "origin", "-d"]);
+    assert_eq!(
+        start_issues(&work, "feat-z"),
+        vec!["No default branch to merge back into".to_string()]
+    );
+    assert!(start(&work, "work", "feat-z").is_err());
+}
+
+#[test]
+fn test_start_all_saves_joined_repositories_before_an_error() {
+    let dir = tempdir().unwrap();
+    let work = cloned_remote(dir.path());
+    let detached = dir.path().join("detached");
+    git(
+        dir.path(),
+        &["clone", "-q", work.to_str().unwrap(), "detached"],
+    );
+    git(&detached, &["checkout", "-q", "--detach"]);
+
+    let registry_path = dir.path().join("changes.json");
+    let mut registry = ChangeRegistry::default();
+    let repos = vec![
+        ("work".to_string(), work.clone()),
+        ("detached".to_string(), detached),
+    ];
+    let err = start_all(&mut registry, &registry_path, "feat", "feat-x", &repos).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("1 of 2 repositories joined 'feat'"));
+
+    let saved = ChangeRegistry::load(&registry_path).unwrap();
+    let members: Vec<_> = saved.changes["feat"]
+        .members
+        .iter()
+        .map(|m| m.name.as_str())
+        .collect();
+    assert_eq!(members, vec!["work"]);
+    assert!(git::ref_exists(&work, "refs/heads/feat-x"));
+}
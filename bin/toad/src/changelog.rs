@@ -0,0 +1,186 @@
+//! Conventional Commit changelogs for `toad changelog`.
+//!
+//! Commits in a range are grouped into Keep a Changelog sections (`feat` is
+//! Added, `fix` is Fixed, `perf`/`refactor` and other breaking changes are
+//! Changed, `revert` is Removed, `fix(security)` is Security). Housekeeping
+//! types (`docs`, `chore`, `ci`, `build`, `test`, `style`) and
+//! non-conventional subjects are left out.
+
+use crate::git;
+use anyhow::Result;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+const RECORD: char = '\x1e';
+const FIELD: char = '\x1f';
+
+const HEADER: &str = "# Changelog
+
+All notable changes to this project are documented in this file.
+
+The format is based on [Keep a Changelog](https://keepachangelog.com/en/1.1.0/),
+and this project adheres to
+[Semantic Versioning](https://semver.org/spec/v2.0.0.html).
+
+---
+";
+
+/// Keep a Changelog sections, in the order they are rendered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Section {
+    Added,
+    Changed,
+    Removed,
+    Fixed,
+    Security,
+}
+
+impl Section {
+    pub fn title(&self) -> &'static str {
+        match self {
+            Section::Added => "Added",
+            Section::Changed => "Changed",
+            Section::Removed => "Removed",
+            Section::Fixed => "Fixed",
+            Section::Security => "Security",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub section: Section,
+    pub scope: Option<String>,
+    pub description: String,
+    pub breaking: bool,
+    pub short: String,
+}
+
+/// Parses `type(scope)!: description`, returning the entry it belongs in or
+/// `None` for housekeeping and non-conventional commits.
+pub fn parse(short: &str, subject: &str, body: &str) -> Option<Entry> {
+    let (header, description) = subject.split_once(": ")?;
+    let description = description.trim();
+    let (header, bang) = match header.strip_suffix('!') {
+        Some(h) => (h, true),
+        None => (header, false),
+    };
+    let (kind, scope) = match header.split_once('(') {
+        Some((kind, scope)) => (kind, Some(scope.strip_suffix(')')?.to_string())),
+        None => (header, None),
+    };
+    if description.is_empty() || kind.is_empty() || !kind.chars().all(|c| c.is_ascii_lowercase()) {
+        return None;
+    }
+    let breaking = bang
+        || body
+            .lines()
+            .any(|l| l.starts_with("BREAKING CHANGE:") || l.starts_with("BREAKING-CHANGE:"));
+
+    let section = match kind {
+        "feat" => Section::Added,
+        "fix" if scope.as_deref() == Some("security") => Section::Security,
+        "fix" => Section::Fixed,
+        "perf" | "refactor" => Section::Changed,
+        "revert" => Section::Removed,
+        _ if breaking => Section::Changed,
+        _ => return None,
+    };
+    let mut chars = description.chars();
+    let description = match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    };
+    Some(Entry {
+        section,
+        scope,
+        description,
+        breaking,
+        short: short.to_string(),
+    })
+}
+
+/// Entries for the non-merge commits in `from..to`, oldest first, plus the
+/// number of commits that were left out.
+pub fn entries(repo: &Path, from: &str, to: &str) -> Result<(Vec<Entry>, usize)> {
+    let log = git::output(
+        repo,
+        &[
+            "log",
+            "--no-merges",
+            "--reverse",
+            &format!("--format={}%h{}%s{}%b", RECORD, FIELD, FIELD),
+            &format!("{}..{}", from, to),
+        ],
+    )?;
+    let mut found = Vec::new();
+    let mut skipped = 0;
+    for record in log.split(RECORD).filter(|r| !r.trim().is_empty()) {
+        let mut parts = record.splitn(3, FIELD);
+        let short = parts.next().unwrap_or_default();
+        let subject = parts.next().unwrap_or_default();
+        match parse(short, subject, parts.next().unwrap_or_default()) {
+            Some(entry) => found.push(entry),
+            None => skipped += 1,
+        }
+    }
+    Ok((found, skipped))
+}
+
+fn line(entry: &Entry, krate: Option<&str>) -> String {
+    let breaking = if entry.breaking { "**BREAKING:** " } else { "" };
+    let label = match (krate, entry.scope.as_deref()) {
+        (Some(k), Some(s)) => format!("**{} ({}):** ", k, s),
+        (Some(k), None) | (None, Some(k)) => format!("**{}:** ", k),
+        (None, None) => String::new(),
+    };
+    format!(
+        "- {}{}{} (`{}`)",
+        breaking, label, entry.description, entry.short
+    )
+}
+
+/// Renders one `## [version] — date` section. Each group is a crate name
+/// (`None` for the repository itself) with its entries; crate names prefix
+/// their entries so several repositories can share one changelog.
+pub fn render(version: &str, date: &str, groups: &[(Option<&str>, &[Entry])]) -> String {
+    let mut md = format!("## [{}] — {}\n", version, date);
+    for section in [
+        Section::Added,
+        Section::Changed,
+        Section::Removed,
+        Section::Fixed,
+        Section::Security,
+    ] {
+        let lines: Vec<String> = groups
+            .iter()
+            .flat_map(|(krate, entries)| {
+                entries
+                    .iter()
+                    .filter(move |e| e.section == section)
+                    .map(move |e| line(e, *krate))
+            })
+            .collect();
+        if lines.is_empty() {
+            continue;
+        }
+        let _ = write!(md, "\n### {}\n\n{}\n", section.title(), lines.join("\n"));
+    }
+    md
+}
+
+/// Inserts a rendered section above the newest release in `CHANGELOG.md`,
+/// creating the file with the standard header if needed.
+pub fn prepend(path: &Path, section: &str) -> Result<()> {
+    let current = fs::read_to_string(path).unwrap_or_else(|_| HEADER.to_string());
+    let updated = match current.find("\n## ") {
+        Some(idx) => format!("{}\n{}\n{}", &current[..idx], section, &current[idx + 1..]),
+        None => format!("{}\n\n{}", current.trim_end_matches('\n'), section),
+    };
+    fs::write(path, updated)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,100 @@
+use super::*;
+use crate::test_support::git;
+use tempfile::tempdir;
+
+#[test]
+fn test_parse_maps_types_to_sections() {
+    let section = |subject: &str, body: &str| parse("abc1234", subject, body).map(|e| e.section);
+    assert_eq!(section("feat(cli): add journal", ""), Some(Section::Added));
+    assert_eq!(
+        section("fix: handle detached HEAD", ""),
+        Some(Section::Fixed)
+    );
+    assert_eq!(
+        section("fix(security): redact tokens", ""),
+        Some(Section::Security)
+    );
+    assert_eq!(section("perf: cache scans", ""), Some(Section::Changed));
+    assert_eq!(
+        section("revert: drop sync flag", ""),
+        Some(Section::Removed)
+    );
+    assert_eq!(section("chore: bump deps", ""), None);
+    assert_eq!(
+        section("chore!: drop MSRV 1.70", ""),
+        Some(Section::Changed)
+    );
+    assert_eq!(section("Update README", ""), None);
+
+    let entry = parse(
+        "abc1234",
+        "feat(core): rename Workspace",
+        "BREAKING CHANGE: renamed",
+    )
+    .unwrap();
+    assert!(entry.breaking);
+    assert_eq!(entry.scope.as_deref(), Some("core"));
+    assert_eq!(entry.description, "Rename Workspace");
+}
+
+#[test]
+fn test_render_groups_sections_and_crates() {
+    let e = |subject: &str| parse("abc1234", subject, "").unwrap();
+    let core = vec![e("fix(scan): skip broken links"), e("feat: add tiers")];
+    let hub = vec![e("feat: add journal")];
+    let md = render(
+        "v1.1.0",
+        "2026-10-19",
+        &[(None, hub.as_slice()), (Some("toad-core"), core.as_slice())],
+    );
+    assert_eq!(
+        md,
+        "## [v1.1.0] — 2026-10-19\n\
+         \n### Added\n\n\
+         - Add journal (`abc1234`)\n\
+         - **toad-core:** Add tiers (`abc1234`)\n\
+         \n### Fixed\n\n\
+         - **toad-core (scan):** Skip broken links (`abc1234`)\n"
+    );
+}
+
+#[test]
+fn test_entries_and_prepend() {
+    let dir = tempdir().unwrap();
+    let repo = dir.path();
+    git(repo, &["init", "-q", "-b", "main"]);
+    git(
+        repo,
+        &["commit", "-q", "--allow-empty", "-m", "chore: init"],
+    );
+    git(repo, &["tag", "v1"]);
+    git(
+        repo,
+        &[
+            "commit",
+            "-q",
+            "--allow-empty",
+            "-m",
+            "feat: add risk report",
+        ],
+    );
+    git(repo, &["commit", "-q", "--allow-empty", "-m", "wip"]);
+
+    let (found, skipped) = entries(repo, "v1", "HEAD").unwrap();
+    assert_eq!(found.len(), 1);
+    assert_eq!(skipped, 1);
+
+    let path = repo.join("CHANGELOG.md");
+    prepend(
+        &path,
+        &render("v2", "2026-10-19", &[(None, found.as_slice())]),
+    )
+    .unwrap();
+    prepend(&path, "## [v3] — 2026-10-20\n").unwrap();
+    let text = fs::read_to_string(&path).unwrap();
+    assert!(text.starts_with("# Changelog\n"));
+    let v3 = text.find("## [v3]").unwrap();
+    let v2 = text.find("## [v2]").unwrap();
+    assert!(v3 < v2);
+    assert!(text.contains("---\n\n## [v3] — 2026-10-20\n\n## [v2]"));
+}
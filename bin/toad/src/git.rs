@@ -0,0 +1,55 @@
+//! Read-only git queries used by the orchestration commands.
+//!
+//! `toad_git` returns consolidated `GitOpResult`s for operations that change a
+//! repository. The helpers here cover the plumbing reads (rev-parse, log,
+//! for-each-ref) whose stdout the CLI needs to reason about.
+
+use anyhow::{bail, Result};
+use std::path::Path;
+use std::process::Command;
+
+/// Runs `git` in `path` and returns its trimmed stdout.
+pub fn output(path: &Path, args: &[&str]) -> Result<String> {
+    let out = Command::new("git").args(args).current_dir(path).output()?;
+    if !out.status.success() {
+        bail!(
+            "git {} failed in {:?}: {}",
+            args.join(" "),
+            path,
+            String::from_utf8_lossy(&out.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&out.stdout).trim_end().to_string())
+}
+
+/// Like [`output`], but for probes where a failing git call just means "no".
+pub fn probe(path: &Path, args: &[&str]) -> Option<String> {
+    output(path, args).ok()
+}
+
+/// Returns true if `reference` resolves to an object in the repository.
+pub fn ref_exists(path: &Path, reference: &str) -> bool {
+    probe(path, &["rev-parse", "--verify", "--quiet", reference]).is_some()
+}
+
+/// Number of modified tracked files; untracked files never block a checkout.
+pub fn tracked_changes(path: &Path) -> usize {
+    probe(path, &["status", "--porcelain", "--untracked-files=no"])
+        .map(|out| out.lines().count())
+        .unwrap_or(0)
+}
+
+/// `origin` if configured, otherwise the first remote.
+pub fn default_remote(path: &Path) -> Option<String> {
+    let remotes = probe(path, &["remote"])?;
+    if remotes.lines().any(|r| r == "origin") {
+        Some("origin".to_string())
+    } else {
+        remotes.lines().next().map(String::from)
+    }
+}
+
+/// Abbreviates a commit id for display.
+pub fn short(sha: &str) -> &str {
+    &sha[..sha.len().min(7)]
+}
@@ -0,0 +1,130 @@
+//! Cross-repository code search for `toad grep`.
+//!
+//! Searches run through `git grep`, so only tracked files are read and
+//! `.gitignore` is respected. Artifact directories detected for the project's
+//! stack are excluded as well, in case build output was ever committed.
+
+use anyhow::{bail, Result};
+use serde::Serialize;
+use std::path::Path;
+use std::process::Command;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Mode {
+    Lines,
+    Count,
+    FilesWithMatches,
+}
+
+#[derive(Debug, Clone)]
+pub struct GrepOptions {
+    pub pattern: String,
+    pub ignore_case: bool,
+    pub fixed_strings: bool,
+    pub mode: Mode,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Hit {
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RepoHits {
+    pub project: String,
+    pub hits: Vec<Hit>,
+}
+
+/// Searches one repository. No matches is an empty list, not an error.
+pub fn search(path: &Path, opts: &GrepOptions, artifact_dirs: &[String]) -> Result<Vec<Hit>> {
+    let mut args = vec![
+        "grep".to_string(),
+        "-z".to_string(),
+        "-I".to_string(),
+        "--no-color".to_string(),
+    ];
+    args.push(
+        match opts.mode {
+            Mode::Lines => "-n",
+            Mode::Count => "-c",
+            Mode::FilesWithMatches => "-l",
+        }
+        .to_string(),
+    );
+    if opts.ignore_case {
+        args.push("-i".to_string());
+    }
+    if opts.fixed_strings {
+        args.push("-F".to_string());
+    }
+    args.push("-e".to_string());
+    args.push(opts.pattern.clone());
+    args.push("--".to_string());
+    args.push(".".to_string());
+    for dir in artifact_dirs {
+        args.push(format!(":(exclude,glob)**/{}/**", dir));
+    }
+
+    let out = Command::new("git").args(&args).current_dir(path).output()?;
+    match out.status.code() {
+        Some(0) => Ok(parse(&String::from_utf8_lossy(&out.stdout), opts.mode)),
+        Some(1) => Ok(Vec::new()),
+        _ => bail!(
+            "git grep failed in {:?}: {}",
+            path,
+            String::from_utf8_lossy(&out.stderr).trim()
+        ),
+    }
+}
+
+/// Parses `git grep -z` output for the given mode.
+pub fn parse(output: &str, mode: Mode) -> Vec<Hit> {
+    match mode {
+        Mode::FilesWithMatches => output
+            .split('\0')
+            .map(|p| p.trim_matches('\n'))
+            .filter(|p| !p.is_empty())
+            .map(|p| Hit {
+                path: p.to_string(),
+                line: None,
+                text: None,
+                count: None,
+            })
+            .collect(),
+        Mode::Count => output
+            .lines()
+            .filter_map(|l| {
+                let (path, count) = l.split_once('\0')?;
+                Some(Hit {
+                    path: path.to_string(),
+                    line: None,
+                    text: None,
+                    count: count.parse().ok(),
+                })
+            })
+            .collect(),
+        Mode::Lines => output
+            .lines()
+            .filter_map(|l| {
+                let mut parts = l.splitn(3, '\0');
+                let path = parts.next()?;
+                let line = parts.next()?.parse().ok()?;
+                Some(Hit {
+                    path: path.to_string(),
+                    line: Some(line),
+                    text: Some(parts.next().unwrap_or_default().to_string()),
+                    count: None,
+                })
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,20 @@
+use super::*;
+
+#[test]
+fn test_parse_grep_modes() {
+    let lines = parse(
+        "src/main.rs\u{0}12\u{0}let toad = 1;\nREADME.md\u{0}3\u{0}a: b\n",
+        Mode::Lines,
+    );
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0].path, "src/main.rs");
+    assert_eq!(lines[0].line, Some(12));
+    assert_eq!(lines[1].text.as_deref(), Some("a: b"));
+
+    let counts = parse("src/main.rs\u{0}4\n", Mode::Count);
+    assert_eq!(counts[0].count, Some(4));
+
+    let files = parse("src/main.rs\u{0}README.md\u{0}", Mode::FilesWithMatches);
+    let paths: Vec<_> = files.iter().map(|h| h.path.as_str()).collect();
+    assert_eq!(paths, vec!["src/main.rs", "README.md"]);
+}
@@ -0,0 +1,163 @@
+//! Disk usage history for `toad stats --trend` and `--diff`.
+//!
+//! Every `toad stats` run appends one snapshot per measured project (total
+//! and artifact bytes, bloat index and activity tier) to
+//! `stats-history.json` in the active context's directory. Snapshots older
+//! than a year are dropped when a new one is recorded.
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use toad_core::{GlobalConfig, Workspace};
+use toad_ops::stats::format_size;
+
+const HISTORY_FILE: &str = "stats-history.json";
+const RETENTION_SECS: u64 = 365 * 24 * 60 * 60;
+const SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProjectSnapshot {
+    pub name: String,
+    pub total_bytes: u64,
+    pub artifact_bytes: u64,
+    pub bloat_index: f64,
+    pub tier: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub taken_at: u64,
+    pub projects: Vec<ProjectSnapshot>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StatsHistory {
+    /// Oldest first.
+    #[serde(default)]
+    pub snapshots: Vec<Snapshot>,
+}
+
+impl StatsHistory {
+    pub fn path(workspace: &Workspace) -> Result<PathBuf> {
+        let dir = match &workspace.active_context {
+            Some(name) => GlobalConfig::context_dir(name, None)?,
+            None => GlobalConfig::config_dir(None)?,
+        };
+        Ok(dir.join(HISTORY_FILE))
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        // Write then rename so a crash never leaves a truncated history.
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_string(self)?)?;
+        fs::rename(tmp, path)?;
+        Ok(())
+    }
+
+    pub fn record(&mut self, snapshot: Snapshot) {
+        let cutoff = snapshot.taken_at.saturating_sub(RETENTION_SECS);
+        self.snapshots.retain(|s| s.taken_at >= cutoff);
+        self.snapshots.push(snapshot);
+    }
+
+    /// Each project's measurements since `since`, oldest first.
+    pub fn series(&self, since: u64) -> BTreeMap<String, Vec<(u64, ProjectSnapshot)>> {
+        let mut series: BTreeMap<String, Vec<(u64, ProjectSnapshot)>> = BTreeMap::new();
+        for s in self.snapshots.iter().filter(|s| s.taken_at >= since) {
+            for p in &s.projects {
+                series
+                    .entry(p.name.clone())
+                    .or_default()
+                    .push((s.taken_at, p.clone()));
+            }
+        }
+        series
+    }
+
+    /// A project's latest measurement taken at or before `at`.
+    pub fn at(&self, name: &str, at: u64) -> Option<&ProjectSnapshot> {
+        self.snapshots
+            .iter()
+            .rev()
+            .filter(|s| s.taken_at <= at)
+            .find_map(|s| s.projects.iter().find(|p| p.name == name))
+    }
+}
+
+/// `12h`, `30d` or `8w` in seconds.
+pub fn parse_window(window: &str) -> Result<u64> {
+    let window = window.trim();
+    let last = window.char_indices().last().map_or(0, |(i, _)| i);
+    let (count, unit) = window.split_at(last);
+    let secs = match unit {
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
+        _ => bail!("invalid window '{}' (use e.g. 12h, 30d or 8w)", window),
+    };
+    match count.parse::<u64>() {
+        Ok(n) if n > 0 => Ok(n * secs),
+        _ => bail!("invalid window '{}' (use e.g. 12h, 30d or 8w)", window),
+    }
+}
+
+/// A `YYYY-MM-DD` date (local midnight) or a window back from `now`, as a
+/// Unix timestamp.
+pub fn parse_point(point: &str, now: u64) -> Result<u64> {
+    if let Ok(window) = parse_window(point) {
+        return Ok(now.saturating_sub(window));
+    }
+    let Ok(date) = chrono::NaiveDate::parse_from_str(point.trim(), "%Y-%m-%d") else {
+        bail!("invalid date '{}' (use YYYY-MM-DD or e.g. 7d)", point);
+    };
+    let midnight = date
+        .and_hms_opt(0, 0, 0)
+        .and_then(|t| t.and_local_timezone(chrono::Local).earliest());
+    match midnight {
+        Some(t) => Ok(t.timestamp().max(0) as u64),
+        None => bail!("invalid date '{}'", point),
+    }
+}
+
+/// Unicode sparkline scaled between the series' minimum and maximum.
+pub fn sparkline(values: &[u64]) -> String {
+    let (Some(min), Some(max)) = (values.iter().min(), values.iter().max()) else {
+        return String::new();
+    };
+    let span = (max - min) as f64;
+    values
+        .iter()
+        .map(|v| {
+            if span == 0.0 {
+                SPARKS[0]
+            } else {
+                let level = ((v - min) as f64 / span * (SPARKS.len() - 1) as f64).round();
+                SPARKS[level as usize]
+            }
+        })
+        .collect()
+}
+
+/// `+1.2 GB`, `-300 MB` or `±0 B`.
+pub fn signed_size(delta: i64) -> String {
+    match delta {
+        0 => format!("±{}", format_size(0)),
+        d if d > 0 => format!("+{}", format_size(d as u64)),
+        d => format!("-{}", format_size(d.unsigned_abs())),
+    }
+}
+
+#[cfg(test)]
+mod tests;
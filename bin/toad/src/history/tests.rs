@@ -0,0 +1,69 @@
+use super::*;
+use tempfile::tempdir;
+
+const DAY: u64 = 24 * 60 * 60;
+
+fn project(name: &str, total_bytes: u64, tier: &str) -> ProjectSnapshot {
+    ProjectSnapshot {
+        name: name.to_string(),
+        total_bytes,
+        artifact_bytes: total_bytes / 2,
+        bloat_index: 50.0,
+        tier: tier.to_string(),
+    }
+}
+
+#[test]
+fn test_record_series_and_lookup() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("ctx").join(HISTORY_FILE);
+    let now = 400 * DAY;
+
+    let mut history = StatsHistory::load(&path).unwrap();
+    // Older than the retention period: dropped on the next record.
+    history.record(Snapshot {
+        taken_at: 10 * DAY,
+        projects: vec![project("lily", 1, "Active")],
+    });
+    for (day, lily, newt) in [(360, 100, 50), (390, 300, 40), (400, 500, 40)] {
+        history.record(Snapshot {
+            taken_at: day * DAY,
+            projects: vec![
+                project("lily", lily, "Active"),
+                project("newt", newt, "Cold"),
+            ],
+        });
+    }
+    history.save(&path).unwrap();
+    assert!(!path.with_extension("json.tmp").exists());
+
+    let history = StatsHistory::load(&path).unwrap();
+    assert_eq!(history.snapshots.len(), 3);
+    let series = history.series(now - 30 * DAY);
+    let lily: Vec<u64> = series["lily"].iter().map(|(_, p)| p.total_bytes).collect();
+    assert_eq!(lily, vec![300, 500]);
+    assert_eq!(history.at("newt", 395 * DAY).unwrap().total_bytes, 40);
+    assert_eq!(history.at("lily", 365 * DAY).unwrap().total_bytes, 100);
+    assert!(history.at("lily", 300 * DAY).is_none());
+}
+
+#[test]
+fn test_parsing_and_rendering_helpers() {
+    assert_eq!(parse_window("30d").unwrap(), 30 * DAY);
+    assert_eq!(parse_window("12h").unwrap(), 12 * 60 * 60);
+    assert_eq!(parse_window("2w").unwrap(), 14 * DAY);
+    assert!(parse_window("30").is_err());
+    assert!(parse_window("0d").is_err());
+    assert!(parse_window("30é").is_err());
+    assert!(parse_window("").is_err());
+    assert_eq!(parse_point("7d", 10 * DAY).unwrap(), 3 * DAY);
+    assert!(parse_point("2026-10-01", 0).unwrap() > 0);
+    assert!(parse_point("October", 0).is_err());
+
+    assert_eq!(sparkline(&[1, 5, 8]), "▁▅█");
+    assert_eq!(sparkline(&[7, 7]), "▁▁");
+    assert_eq!(sparkline(&[]), "");
+    assert!(signed_size(2048).starts_with('+'));
+    assert!(signed_size(-2048).starts_with('-'));
+    assert!(signed_size(0).starts_with('±'));
+}
@@ -0,0 +1,391 @@
+//! Git hook distribution for `toad hooks`.
+//!
+//! A hook set is a directory of executable hook scripts, by default `hooks/`
+//! in the active context's directory. It is either copied into each
+//! repository's hooks directory, with any different hook already there kept
+//! as `<hook>.toad-backup-<timestamp>`, or linked by pointing
+//! `core.hooksPath` at the template directory itself. Next to each copied
+//! hook, `<hook>.toad-installed` records what Toad wrote, so hooks from an
+//! older template are replaced without a backup and never restored as the
+//! user's own.
+
+use crate::git;
+use anyhow::{bail, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use toad_core::{GitOpResult, GlobalConfig, Workspace};
+
+const HOOKS_DIR: &str = "hooks";
+const BACKUP_MARKER: &str = ".toad-backup-";
+const INSTALLED_MARKER: &str = ".toad-installed";
+
+/// The context-level template directory.
+pub fn default_template_dir(workspace: &Workspace) -> Result<PathBuf> {
+    let dir = match &workspace.active_context {
+        Some(name) => GlobalConfig::context_dir(name, None)?,
+        None => GlobalConfig::config_dir(None)?,
+    };
+    Ok(dir.join(HOOKS_DIR))
+}
+
+#[derive(Debug, Clone)]
+pub struct Template {
+    pub name: String,
+    pub content: Vec<u8>,
+}
+
+/// Hook scripts in a template directory; git's `.sample` files, hidden files
+/// and backups are ignored.
+pub fn templates(dir: &Path) -> Result<Vec<Template>> {
+    if !dir.is_dir() {
+        bail!("hook template directory {:?} does not exist", dir);
+    }
+    let mut hooks = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        if !path.is_file()
+            || name.starts_with('.')
+            || name.ends_with(".sample")
+            || name.contains(BACKUP_MARKER)
+        {
+            continue;
+        }
+        hooks.push(Template {
+            content: fs::read(&path)?,
+            name,
+        });
+    }
+    if hooks.is_empty() {
+        bail!("no hooks found in {:?}", dir);
+    }
+    hooks.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(hooks)
+}
+
+/// The repository's own hooks directory (shared by its linked worktrees),
+/// regardless of `core.hooksPath`.
+pub fn hooks_dir(repo: &Path) -> Option<PathBuf> {
+    git::probe(
+        repo,
+        &["rev-parse", "--path-format=absolute", "--git-common-dir"],
+    )
+    .map(|dir| PathBuf::from(dir).join(HOOKS_DIR))
+}
+
+/// The repository-local `core.hooksPath`, if set.
+pub fn hooks_path(repo: &Path) -> Option<String> {
+    git::probe(repo, &["config", "--local", "core.hooksPath"]).filter(|p| !p.is_empty())
+}
+
+/// Resolves a `core.hooksPath` value the way git does: `~` is the home
+/// directory and a relative path is taken from the top of the working tree.
+fn resolve_hooks_path(repo: &Path, path: &str) -> PathBuf {
+    let home = std::env::var_os("HOME").map(PathBuf::from);
+    let expanded = match (path.strip_prefix('~'), home) {
+        (Some(""), Some(home)) => home,
+        (Some(rest), Some(home)) if rest.starts_with('/') => home.join(&rest[1..]),
+        _ => PathBuf::from(path),
+    };
+    if expanded.is_absolute() {
+        return expanded;
+    }
+    git::probe(repo, &["rev-parse", "--show-toplevel"])
+        .map(PathBuf::from)
+        .unwrap_or_else(|| repo.to_path_buf())
+        .join(expanded)
+}
+
+fn marker(dir: &Path, hook: &str) -> PathBuf {
+    dir.join(format!("{}{}", hook, INSTALLED_MARKER))
+}
+
+/// True when `content` is what Toad last installed as `hook`.
+fn is_own(dir: &Path, hook: &str, content: &[u8]) -> bool {
+    fs::read(marker(dir, hook)).is_ok_and(|m| m == content)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum HookState {
+    Installed,
+    Missing,
+    /// Installed by Toad from an older version of the template.
+    Outdated,
+    /// Present but different from the template.
+    Drifted,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Mode {
+    /// Hooks are copied into the hooks directory.
+    Copied(Vec<(String, HookState)>),
+    /// `core.hooksPath` points at the template directory.
+    Linked,
+    /// `core.hooksPath` points elsewhere, so copied hooks would never run.
+    Overridden(String),
+}
+
+impl Mode {
+    pub fn is_current(&self) -> bool {
+        match self {
+            Mode::Linked => true,
+            Mode::Overridden(_) => false,
+            Mode::Copied(hooks) => hooks.iter().all(|(_, s)| *s == HookState::Installed),
+        }
+    }
+
+    /// Copied hooks in `state`; none when the hooks are not copied.
+    pub fn hooks_in(&self, state: &HookState) -> Vec<&str> {
+        match self {
+            Mode::Copied(hooks) => hooks
+                .iter()
+                .filter(|(_, s)| s == state)
+                .map(|(h, _)| h.as_str())
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+fn same_dir(a: &Path, b: &Path) -> bool {
+    match (fs::canonicalize(a), fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+pub fn status(repo: &Path, template_dir: &Path, templates: &[Template]) -> Result<Mode> {
+    if let Some(path) = hooks_path(repo) {
+        return Ok(
+            if same_dir(&resolve_hooks_path(repo, &path), template_dir) {
+                Mode::Linked
+            } else {
+                Mode::Overridden(path)
+            },
+        );
+    }
+    let Some(dir) = hooks_dir(repo) else {
+        bail!("{:?} is not a git repository", repo);
+    };
+    Ok(Mode::Copied(
+        templates
+            .iter()
+            .map(|t| {
+                let state = match fs::read(dir.join(&t.name)) {
+                    Ok(content) if content == t.content => HookState::Installed,
+                    Ok(content) if is_own(&dir, &t.name, &content) => HookState::Outdated,
+                    Ok(_) => HookState::Drifted,
+                    Err(_) => HookState::Missing,
+                };
+                (t.name.clone(), state)
+            })
+            .collect(),
+    ))
+}
+
+/// What `toad hooks install` would do to a repository.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InstallPlan {
+    /// `core.hooksPath` already points at the template directory.
+    Linked,
+    /// Point `core.hooksPath` at the template directory.
+    Link,
+    /// Copy these hooks; `true` marks a different hook that is backed up first.
+    Copy(Vec<(String, bool)>),
+    /// Copy the hooks, which stay inactive while `core.hooksPath` is set.
+    CopyInactive(String),
+}
+
+pub fn plan_install(
+    repo: &Path,
+    template_dir: &Path,
+    templates: &[Template],
+    link: bool,
+) -> Result<InstallPlan> {
+    Ok(match status(repo, template_dir, templates)? {
+        Mode::Linked => InstallPlan::Linked,
+        _ if link => InstallPlan::Link,
+        Mode::Overridden(path) => InstallPlan::CopyInactive(path),
+        Mode::Copied(states) => InstallPlan::Copy(
+            states
+                .into_iter()
+                .filter_map(|(hook, state)| match state {
+                    HookState::Installed => None,
+                    HookState::Missing | HookState::Outdated => Some((hook, false)),
+                    HookState::Drifted => Some((hook, true)),
+                })
+                .collect(),
+        ),
+    })
+}
+
+/// Points `core.hooksPath` at the template directory.
+pub fn link(repo: &Path, name: &str, template_dir: &Path) -> Result<GitOpResult> {
+    toad_git::run_git(
+        repo,
+        &["config", "core.hooksPath", &template_dir.to_string_lossy()],
+        name,
+    )
+}
+
+/// Unsets the repository-local `core.hooksPath`.
+pub fn unlink(repo: &Path, name: &str) -> Result<GitOpResult> {
+    toad_git::run_git(
+        repo,
+        &["config", "--local", "--unset", "core.hooksPath"],
+        name,
+    )
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Installed {
+    pub hook: String,
+    /// Where a different existing hook was moved to.
+    pub backup: Option<PathBuf>,
+}
+
+/// Copies every template into the repository's hooks directory, backing up
+/// hooks that differ unless Toad installed them. Hooks that already match are
+/// left untouched.
+pub fn install(repo: &Path, templates: &[Template], ts: u64) -> Result<Vec<Installed>> {
+    let Some(dir) = hooks_dir(repo) else {
+        bail!("{:?} is not a git repository", repo);
+    };
+    fs::create_dir_all(&dir)?;
+    let mut installed = Vec::new();
+    for t in templates {
+        let target = dir.join(&t.name);
+        let mut backup = None;
+        if target.symlink_metadata().is_ok() {
+            let current = fs::read(&target).ok();
+            if current.as_deref() == Some(t.content.as_slice()) {
+                continue;
+            }
+            if !current.is_some_and(|c| is_own(&dir, &t.name, &c)) {
+                let to = dir.join(format!("{}{}{}", t.name, BACKUP_MARKER, ts));
+                fs::rename(&target, &to)?;
+                backup = Some(to);
+            }
+        }
+        fs::write(&target, &t.content)?;
+        make_executable(&target)?;
+        fs::write(marker(&dir, &t.name), &t.content)?;
+        installed.push(Installed {
+            hook: t.name.clone(),
+            backup,
+        });
+    }
+    Ok(installed)
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(0o755))?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+/// Backups of one hook, oldest first.
+fn backups(dir: &Path, hook: &str) -> Vec<PathBuf> {
+    let prefix = format!("{}{}", hook, BACKUP_MARKER);
+    let mut found: Vec<(u64, PathBuf)> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            let name = e.file_name().to_string_lossy().to_string();
+            let ts = name.strip_prefix(&prefix)?.parse().ok()?;
+            Some((ts, e.path()))
+        })
+        .collect();
+    found.sort();
+    found.into_iter().map(|(_, p)| p).collect()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Removed {
+    /// The hook was removed and the oldest backup of a hook Toad did not
+    /// install (the pre-Toad hook) put back.
+    Restored(String),
+    Deleted(String),
+    /// Left in place because it no longer matches the template.
+    KeptDrifted(String),
+}
+
+/// What `toad hooks remove` would do to a repository.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RemovePlan {
+    /// Unset `core.hooksPath`.
+    Unlink,
+    /// Remove the copied hooks; `false` marks a modified hook that is kept.
+    Copied(Vec<(String, bool)>),
+    /// Remove copied hooks only; `core.hooksPath` points elsewhere.
+    Overridden(String),
+}
+
+pub fn plan_remove(
+    repo: &Path,
+    template_dir: &Path,
+    templates: &[Template],
+    force: bool,
+) -> Result<RemovePlan> {
+    Ok(match status(repo, template_dir, templates)? {
+        Mode::Linked => RemovePlan::Unlink,
+        Mode::Overridden(path) => RemovePlan::Overridden(path),
+        Mode::Copied(states) => RemovePlan::Copied(
+            states
+                .into_iter()
+                .filter(|(_, state)| *state != HookState::Missing)
+                .map(|(hook, state)| (hook, force || state != HookState::Drifted))
+                .collect(),
+        ),
+    })
+}
+
+/// Removes hooks that still match their template, or an older one Toad
+/// installed, and restores the hook each one originally replaced. Drifted
+/// hooks are kept unless `force` is set.
+pub fn remove(repo: &Path, templates: &[Template], force: bool) -> Result<Vec<Removed>> {
+    let Some(dir) = hooks_dir(repo) else {
+        bail!("{:?} is not a git repository", repo);
+    };
+    let mut removed = Vec::new();
+    for t in templates {
+        let target = dir.join(&t.name);
+        let Ok(content) = fs::read(&target) else {
+            continue;
+        };
+        let installed = fs::read(marker(&dir, &t.name)).ok();
+        let own = |c: &[u8]| c == t.content.as_slice() || installed.as_deref() == Some(c);
+        if !own(&content) && !force {
+            removed.push(Removed::KeptDrifted(t.name.clone()));
+            continue;
+        }
+        fs::remove_file(&target)?;
+        if installed.is_some() {
+            fs::remove_file(marker(&dir, &t.name))?;
+        }
+        let original = backups(&dir, &t.name)
+            .into_iter()
+            .find(|b| !fs::read(b).is_ok_and(|c| own(&c)));
+        match original {
+            Some(original) => {
+                fs::rename(original, &target)?;
+                removed.push(Removed::Restored(t.name.clone()));
+            }
+            None => removed.push(Removed::Deleted(t.name.clone())),
+        }
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,179 @@
+use super::*;
+use crate::test_support::git;
+use tempfile::tempdir;
+
+fn fixture(root: &Path) -> (PathBuf, PathBuf) {
+    let templates = root.join("templates");
+    fs::create_dir_all(&templates).unwrap();
+    fs::write(templates.join("pre-commit"), "#!/bin/sh\nexit 0\n").unwrap();
+    fs::write(templates.join("pre-push"), "#!/bin/sh\nexit 0\n").unwrap();
+    fs::write(templates.join("commit-msg.sample"), "ignored").unwrap();
+    let repo = root.join("repo");
+    fs::create_dir_all(&repo).unwrap();
+    git(&repo, &["init", "-q"]);
+    (templates, repo)
+}
+
+#[test]
+fn test_install_backs_up_and_remove_restores() {
+    let dir = tempdir().unwrap();
+    let (template_dir, repo) = fixture(dir.path());
+    let set = templates(&template_dir).unwrap();
+    assert_eq!(
+        set.iter().map(|t| t.name.as_str()).collect::<Vec<_>>(),
+        vec!["pre-commit", "pre-push"]
+    );
+    let hooks = repo.join(".git/hooks");
+    fs::write(hooks.join("pre-commit"), "#!/bin/sh\necho mine\n").unwrap();
+
+    let installed = install(&repo, &set, 100).unwrap();
+    assert_eq!(installed.len(), 2);
+    assert_eq!(
+        installed[0].backup,
+        Some(hooks.join("pre-commit.toad-backup-100"))
+    );
+    assert!(status(&repo, &template_dir, &set).unwrap().is_current());
+    // A second run has nothing to do.
+    assert!(install(&repo, &set, 200).unwrap().is_empty());
+
+    let removed = remove(&repo, &set, false).unwrap();
+    assert_eq!(
+        removed,
+        vec![
+            Removed::Restored("pre-commit".to_string()),
+            Removed::Deleted("pre-push".to_string()),
+        ]
+    );
+    assert_eq!(
+        fs::read_to_string(hooks.join("pre-commit")).unwrap(),
+        "#!/bin/sh\necho mine\n"
+    );
+    assert!(!hooks.join("pre-push").exists());
+}
+
+#[test]
+fn test_status_reports_drift_and_hooks_path() {
+    let dir = tempdir().unwrap();
+    let (template_dir, repo) = fixture(dir.path());
+    let set = templates(&template_dir).unwrap();
+    install(&repo, &set, 1).unwrap();
+    fs::write(repo.join(".git/hooks/pre-push"), "#!/bin/sh\nexit 1\n").unwrap();
+
+    assert_eq!(
+        status(&repo, &template_dir, &set).unwrap(),
+        Mode::Copied(vec![
+            ("pre-commit".to_string(), HookState::Installed),
+            ("pre-push".to_string(), HookState::Drifted),
+        ])
+    );
+    assert_eq!(
+        remove(&repo, &set, false).unwrap(),
+        vec![
+            Removed::Deleted("pre-commit".to_string()),
+            Removed::KeptDrifted("pre-push".to_string()),
+        ]
+    );
+
+    git(
+        &repo,
+        &["config", "core.hooksPath", template_dir.to_str().unwrap()],
+    );
+    assert_eq!(status(&repo, &template_dir, &set).unwrap(), Mode::Linked);
+    git(&repo, &["config", "core.hooksPath", ".githooks"]);
+    assert_eq!(
+        status(&repo, &template_dir, &set).unwrap(),
+        Mode::Overridden(".githooks".to_string())
+    );
+}
+
+#[test]
+fn test_reinstall_replaces_own_hook_and_remove_skips_own_backups() {
+    let dir = tempdir().unwrap();
+    let (template_dir, repo) = fixture(dir.path());
+    let hooks = repo.join(".git/hooks");
+    fs::write(hooks.join("pre-commit"), "#!/bin/sh\necho mine\n").unwrap();
+    install(&repo, &templates(&template_dir).unwrap(), 100).unwrap();
+    // A legacy backup of an older Toad hook, made before markers existed.
+    fs::write(hooks.join("pre-push.toad-backup-50"), "#!/bin/sh\nexit 0\n").unwrap();
+
+    fs::write(template_dir.join("pre-commit"), "#!/bin/sh\nexit 2\n").unwrap();
+    let set = templates(&template_dir).unwrap();
+    assert_eq!(
+        status(&repo, &template_dir, &set).unwrap(),
+        Mode::Copied(vec![
+            ("pre-commit".to_string(), HookState::Outdated),
+            ("pre-push".to_string(), HookState::Installed),
+        ])
+    );
+    let installed = install(&repo, &set, 200).unwrap();
+    assert_eq!(installed[0].backup, None);
+    assert!(!hooks.join("pre-commit.toad-backup-200").exists());
+
+    assert_eq!(
+        remove(&repo, &set, false).unwrap(),
+        vec![
+            Removed::Restored("pre-commit".to_string()),
+            Removed::Deleted("pre-push".to_string()),
+        ]
+    );
+    assert_eq!(
+        fs::read_to_string(hooks.join("pre-commit")).unwrap(),
+        "#!/bin/sh\necho mine\n"
+    );
+    assert!(!hooks.join("pre-push").exists());
+    assert!(!hooks.join("pre-commit.toad-installed").exists());
+}
+
+#[test]
+fn test_relative_hooks_path_resolves_from_the_top_level() {
+    let dir = tempdir().unwrap();
+    let (_, repo) = fixture(dir.path());
+    let template_dir = repo.join(".githooks");
+    fs::create_dir_all(&template_dir).unwrap();
+    fs::write(template_dir.join("pre-commit"), "#!/bin/sh\n").unwrap();
+    let set = templates(&template_dir).unwrap();
+    git(&repo, &["config", "core.hooksPath", ".githooks"]);
+    assert_eq!(status(&repo, &template_dir, &set).unwrap(), Mode::Linked);
+    assert_eq!(
+        resolve_hooks_path(&repo, "~/hooks"),
+        PathBuf::from(std::env::var("HOME").unwrap()).join("hooks")
+    );
+}
+
+#[test]
+fn test_install_and_remove_plans() {
+    let dir = tempdir().unwrap();
+    let (template_dir, repo) = fixture(dir.path());
+    let set = templates(&template_dir).unwrap();
+    fs::write(repo.join(".git/hooks/pre-push"), "#!/bin/sh\necho mine\n").unwrap();
+
+    assert_eq!(
+        plan_install(&repo, &template_dir, &set, false).unwrap(),
+        InstallPlan::Copy(vec![
+            ("pre-commit".to_string(), false),
+            ("pre-push".to_string(), true),
+        ])
+    );
+    assert_eq!(
+        plan_install(&repo, &template_dir, &set, true).unwrap(),
+        InstallPlan::Link
+    );
+    let mode = status(&repo, &template_dir, &set).unwrap();
+    assert_eq!(mode.hooks_in(&HookState::Missing), vec!["pre-commit"]);
+    assert_eq!(
+        plan_remove(&repo, &template_dir, &set, false).unwrap(),
+        RemovePlan::Copied(vec![("pre-push".to_string(), false)])
+    );
+
+    assert!(link(&repo, "repo", &template_dir).unwrap().success);
+    assert_eq!(
+        plan_install(&repo, &template_dir, &set, false).unwrap(),
+        InstallPlan::Linked
+    );
+    assert_eq!(
+        plan_remove(&repo, &template_dir, &set, false).unwrap(),
+        RemovePlan::Unlink
+    );
+    assert!(unlink(&repo, "repo").unwrap().success);
+    assert_eq!(hooks_path(&repo), None);
+}
@@ -0,0 +1,184 @@
+//! Group rebase and merge for `toad ggit rebase` / `toad ggit merge`.
+//!
+//! Each repository either completes, is left stopped on its conflicts for the
+//! user to resolve, or is skipped. `--continue` and `--abort` then act on every
+//! repository that is still mid-operation.
+
+use crate::git;
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Strategy {
+    Rebase,
+    Merge,
+}
+
+impl Strategy {
+    pub fn verb(self) -> &'static str {
+        match self {
+            Strategy::Rebase => "rebase",
+            Strategy::Merge => "merge",
+        }
+    }
+}
+
+/// What a group run does in each repository.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Mode<'a> {
+    /// Start rebasing onto, or merging, the given target.
+    Start(&'a str),
+    Continue,
+    Abort,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    Applied,
+    UpToDate,
+    Conflicted(Vec<String>),
+    Skipped(String),
+    Failed(String),
+}
+
+/// The operation a repository is currently stopped in, if any.
+pub fn in_progress(path: &Path) -> Option<Strategy> {
+    let exists = |name: &str| {
+        git::probe(path, &["rev-parse", "--git-path", name])
+            .map(|p| path.join(p).exists())
+            .unwrap_or(false)
+    };
+    if exists("rebase-merge") || exists("rebase-apply") {
+        Some(Strategy::Rebase)
+    } else if exists("MERGE_HEAD") {
+        Some(Strategy::Merge)
+    } else {
+        None
+    }
+}
+
+/// Files with unresolved conflicts.
+pub fn conflicted_files(path: &Path) -> Vec<String> {
+    git::probe(path, &["diff", "--name-only", "--diff-filter=U"])
+        .map(|out| out.lines().map(String::from).collect())
+        .unwrap_or_default()
+}
+
+/// Blocking issues for starting `strategy` in a repository.
+pub fn preflight(path: &Path, strategy: Strategy) -> Vec<String> {
+    let mut issues = Vec::new();
+    if let Some(op) = in_progress(path) {
+        issues.push(format!(
+            "A {} is already in progress. Run --continue or --abort first.",
+            op.verb()
+        ));
+    }
+    let tracked_changes = git::tracked_changes(path);
+    if tracked_changes > 0 {
+        issues.push(format!("Dirty — {} uncommitted files", tracked_changes));
+    }
+    if strategy == Strategy::Rebase && git::probe(path, &["symbolic-ref", "-q", "HEAD"]).is_none() {
+        issues.push("Detached HEAD — check out a branch before rebasing".to_string());
+    }
+    issues
+}
+
+/// Starts the rebase or merge, leaving the repository stopped on conflict.
+pub fn start(path: &Path, name: &str, strategy: Strategy, target: &str) -> Result<Outcome> {
+    let target_ref = format!("{}^{{commit}}", target);
+    if !git::ref_exists(path, &target_ref) {
+        return Ok(Outcome::Skipped(format!("'{}' not found", target)));
+    }
+
+    let before = git::probe(path, &["rev-parse", "HEAD"]);
+    let args: &[&str] = match strategy {
+        Strategy::Rebase => &["rebase", target],
+        // Merge commits are exempt from the commit policy (see `policy`).
+        Strategy::Merge => &["merge", "--no-edit", target],
+    };
+    let res = toad_git::run_git(path, args, name)?;
+    if !res.success {
+        return Ok(stopped_outcome(path, res.stderr));
+    }
+
+    if git::probe(path, &["rev-parse", "HEAD"]) == before {
+        Ok(Outcome::UpToDate)
+    } else {
+        Ok(Outcome::Applied)
+    }
+}
+
+/// Runs `--continue` for the repository's in-progress operation.
+pub fn resume(path: &Path, name: &str, strategy: Strategy) -> Result<Outcome> {
+    let remaining = conflicted_files(path);
+    if !remaining.is_empty() {
+        return Ok(Outcome::Conflicted(remaining));
+    }
+    // `core.editor=true` accepts the prepared message without opening an editor.
+    let res = toad_git::run_git(
+        path,
+        &["-c", "core.editor=true", strategy.verb(), "--continue"],
+        name,
+    )?;
+    if res.success {
+        Ok(Outcome::Applied)
+    } else {
+        Ok(stopped_outcome(path, res.stderr))
+    }
+}
+
+/// Runs `--abort`, restoring the repository to its pre-operation state.
+pub fn abort(path: &Path, name: &str, strategy: Strategy) -> Result<Outcome> {
+    let res = toad_git::run_git(path, &[strategy.verb(), "--abort"], name)?;
+    if res.success {
+        Ok(Outcome::Applied)
+    } else {
+        Ok(Outcome::Failed(res.stderr.trim().to_string()))
+    }
+}
+
+impl Outcome {
+    /// True if the repository was left needing attention.
+    pub fn is_stopped(&self) -> bool {
+        matches!(self, Outcome::Conflicted(_) | Outcome::Failed(_))
+    }
+}
+
+/// Applies `mode` to each repository in order. With `fail_fast`, the run stops
+/// after the first repository that conflicts or fails. `announce` is called
+/// before each repository is touched.
+pub fn run(
+    repos: &[(String, PathBuf)],
+    strategy: Strategy,
+    mode: Mode,
+    fail_fast: bool,
+    mut announce: impl FnMut(&str),
+) -> Result<Vec<(String, Outcome)>> {
+    let mut outcomes = Vec::new();
+    for (name, path) in repos {
+        announce(name);
+        let outcome = match mode {
+            Mode::Start(target) => start(path, name, strategy, target)?,
+            Mode::Continue => resume(path, name, strategy)?,
+            Mode::Abort => abort(path, name, strategy)?,
+        };
+        let stop = outcome.is_stopped();
+        outcomes.push((name.clone(), outcome));
+        if fail_fast && stop {
+            break;
+        }
+    }
+    Ok(outcomes)
+}
+
+fn stopped_outcome(path: &Path, stderr: String) -> Outcome {
+    let files = conflicted_files(path);
+    if files.is_empty() {
+        Outcome::Failed(stderr.trim().to_string())
+    } else {
+        Outcome::Conflicted(files)
+    }
+}
+
+#[cfg(test)]
+mod tests;
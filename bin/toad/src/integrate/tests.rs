@@ -0,0 +1,124 @@
+use super::*;
+use crate::test_support::{commit, commit_with, git, repo};
+use std::fs;
+use tempfile::tempdir;
+
+/// A repository on `main` with a `feature` branch one commit ahead.
+fn with_feature(root: &Path, name: &str) -> PathBuf {
+    let path = repo(root, name);
+    git(&path, &["checkout", "-q", "-b", "feature"]);
+    commit(&path, "feature.rs");
+    git(&path, &["checkout", "-q", "main"]);
+    path
+}
+
+/// Makes `main` and `feature` both change README.md.
+fn conflicting(root: &Path, name: &str) -> PathBuf {
+    let path = repo(root, name);
+    git(&path, &["checkout", "-q", "-b", "feature"]);
+    commit_with(&path, "README.md", "feature side", "feature edit");
+    git(&path, &["checkout", "-q", "main"]);
+    commit_with(&path, "README.md", "main side", "main edit");
+    path
+}
+
+fn head(path: &Path) -> Option<String> {
+    git::probe(path, &["rev-parse", "HEAD"])
+}
+
+#[test]
+fn test_run_applies_in_order_and_skips_missing_targets() {
+    let dir = tempdir().unwrap();
+    let repos = vec![
+        ("lily".to_string(), with_feature(dir.path(), "lily")),
+        ("newt".to_string(), repo(dir.path(), "newt")),
+        ("pond".to_string(), with_feature(dir.path(), "pond")),
+    ];
+    git(&repos[2].1, &["merge", "-q", "feature"]);
+
+    let mut announced = Vec::new();
+    let outcomes = run(&repos, Strategy::Merge, Mode::Start("feature"), true, |n| {
+        announced.push(n.to_string())
+    })
+    .unwrap();
+
+    let names: Vec<_> = outcomes.iter().map(|(n, _)| n.as_str()).collect();
+    assert_eq!(names, vec!["lily", "newt", "pond"]);
+    assert_eq!(announced, names);
+    assert_eq!(outcomes[0].1, Outcome::Applied);
+    assert!(matches!(outcomes[1].1, Outcome::Skipped(_)));
+    assert_eq!(outcomes[2].1, Outcome::UpToDate);
+    assert!(repos[0].1.join("feature.rs").exists());
+}
+
+#[test]
+fn test_conflict_stops_fail_fast_and_abort_restores() {
+    let dir = tempdir().unwrap();
+    let lily = conflicting(dir.path(), "lily");
+    let before = head(&lily);
+    let repos = vec![
+        ("lily".to_string(), lily.clone()),
+        ("newt".to_string(), with_feature(dir.path(), "newt")),
+    ];
+
+    let outcomes = run(
+        &repos,
+        Strategy::Merge,
+        Mode::Start("feature"),
+        true,
+        |_| {},
+    )
+    .unwrap();
+    assert_eq!(outcomes.len(), 1);
+    assert_eq!(
+        outcomes[0].1,
+        Outcome::Conflicted(vec!["README.md".to_string()])
+    );
+    assert_eq!(in_progress(&lily), Some(Strategy::Merge));
+    assert!(preflight(&lily, Strategy::Merge)[0].contains("already in progress"));
+
+    let outcomes = run(&repos[..1], Strategy::Merge, Mode::Abort, false, |_| {}).unwrap();
+    assert_eq!(outcomes[0].1, Outcome::Applied);
+    assert_eq!(in_progress(&lily), None);
+    assert_eq!(head(&lily), before);
+    assert!(preflight(&lily, Strategy::Merge).is_empty());
+}
+
+#[test]
+fn test_rebase_continue_waits_for_resolution() {
+    let dir = tempdir().unwrap();
+    let lily = conflicting(dir.path(), "lily");
+    git(&lily, &["checkout", "-q", "feature"]);
+    let repos = vec![("lily".to_string(), lily.clone())];
+
+    let outcomes = run(&repos, Strategy::Rebase, Mode::Start("main"), false, |_| {}).unwrap();
+    assert!(matches!(outcomes[0].1, Outcome::Conflicted(_)));
+    assert_eq!(in_progress(&lily), Some(Strategy::Rebase));
+
+    // Unresolved files keep the repository stopped.
+    let outcomes = run(&repos, Strategy::Rebase, Mode::Continue, false, |_| {}).unwrap();
+    assert!(matches!(outcomes[0].1, Outcome::Conflicted(_)));
+
+    fs::write(lily.join("README.md"), "both sides").unwrap();
+    git(&lily, &["add", "README.md"]);
+    let outcomes = run(&repos, Strategy::Rebase, Mode::Continue, false, |_| {}).unwrap();
+    assert_eq!(outcomes[0].1, Outcome::Applied);
+    assert_eq!(in_progress(&lily), None);
+    assert_eq!(
+        fs::read_to_string(lily.join("README.md")).unwrap(),
+        "both sides"
+    );
+}
+
+#[test]
+fn test_preflight_blocks_dirty_and_detached_rebase() {
+    let dir = tempdir().unwrap();
+    let lily = repo(dir.path(), "lily");
+    fs::write(lily.join("README.md"), "edited").unwrap();
+    assert!(preflight(&lily, Strategy::Merge)[0].starts_with("Dirty"));
+
+    git(&lily, &["checkout", "-q", "--", "README.md"]);
+    git(&lily, &["checkout", "-q", "--detach"]);
+    assert!(preflight(&lily, Strategy::Merge).is_empty());
+    assert!(preflight(&lily, Strategy::Rebase)[0].starts_with("Detached HEAD"));
+}
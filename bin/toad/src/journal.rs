@@ -0,0 +1,188 @@
+//! Standup notes for `toad journal`.
+//!
+//! Everything comes from local history, so it works offline: the commits the
+//! repository's configured `user.email` authored on local branches, the
+//! branches whose reflogs moved (commits, resets, checkouts) in the window,
+//! and whatever is still uncommitted.
+
+use crate::git;
+use anyhow::{bail, Result};
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use std::path::Path;
+
+const FIELD: char = '\x1f';
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Commit {
+    pub short: String,
+    pub branch: Option<String>,
+    pub subject: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Entry {
+    /// Oldest first.
+    pub commits: Vec<Commit>,
+    pub branches: BTreeSet<String>,
+    /// `status --porcelain` lines.
+    pub wip: Vec<String>,
+}
+
+impl Entry {
+    pub fn is_empty(&self) -> bool {
+        self.commits.is_empty() && self.branches.is_empty() && self.wip.is_empty()
+    }
+}
+
+/// Resolves a git date (`yesterday`, `3 days ago`, `2024-05-01`) to a Unix timestamp.
+pub fn resolve_since(repo: &Path, since: &str) -> Result<u64> {
+    let out = git::output(repo, &["rev-parse", &format!("--since={}", since)])?;
+    match out
+        .strip_prefix("--max-age=")
+        .and_then(|ts| ts.parse().ok())
+    {
+        Some(ts) => Ok(ts),
+        None => bail!("cannot understand date '{}'", since),
+    }
+}
+
+/// Reflog entries of `reference` as (timestamp, message), newest first.
+fn reflog(repo: &Path, reference: &str) -> Vec<(u64, String)> {
+    git::probe(
+        repo,
+        &[
+            "reflog",
+            "show",
+            "--date=unix",
+            &format!("--format=%gd{}%gs", FIELD),
+            reference,
+        ],
+    )
+    .unwrap_or_default()
+    .lines()
+    .filter_map(|line| {
+        let (selector, message) = line.split_once(FIELD)?;
+        let ts = selector
+            .rsplit_once("@{")?
+            .1
+            .strip_suffix('}')?
+            .parse()
+            .ok()?;
+        Some((ts, message.to_string()))
+    })
+    .collect()
+}
+
+pub fn collect(repo: &Path, since: &str, since_ts: u64) -> Result<Entry> {
+    let mut entry = Entry::default();
+
+    let author = git::probe(repo, &["config", "user.email"])
+        .or_else(|| git::probe(repo, &["config", "user.name"]));
+    if let Some(author) = author {
+        let author = format!("--author={}", author);
+        let since = format!("--since={}", since);
+        let format = format!("--format=%h{}%S{}%s", FIELD, FIELD);
+        let mut args = vec![
+            "log",
+            "--branches",
+            "--source",
+            "--no-merges",
+            "--topo-order",
+            "--reverse",
+            &author,
+            &since,
+            &format,
+        ];
+        // Listing HEAD alongside branches would credit commits to it, so it
+        // is only added when detached.
+        if git::probe(repo, &["symbolic-ref", "-q", "HEAD"]).is_none() {
+            args.push("HEAD");
+        }
+        let log = git::output(repo, &args).unwrap_or_default();
+        for line in log.lines() {
+            let mut parts = line.splitn(3, FIELD);
+            let short = parts.next().unwrap_or_default().to_string();
+            let source = parts.next().unwrap_or_default();
+            // `%S` is the short name of the ref the commit was reached from.
+            let branch = Some(source.trim_start_matches("refs/heads/"))
+                .filter(|b| !b.is_empty() && *b != "HEAD")
+                .map(String::from);
+            entry.commits.push(Commit {
+                short,
+                branch,
+                subject: parts.next().unwrap_or_default().to_string(),
+            });
+        }
+    }
+
+    let locals = git::probe(
+        repo,
+        &["for-each-ref", "--format=%(refname:short)", "refs/heads"],
+    )
+    .unwrap_or_default();
+    for branch in locals.lines() {
+        if reflog(repo, &format!("refs/heads/{}", branch))
+            .first()
+            .is_some_and(|(ts, _)| *ts >= since_ts)
+        {
+            entry.branches.insert(branch.to_string());
+        }
+    }
+    for (_, message) in reflog(repo, "HEAD")
+        .into_iter()
+        .take_while(|(ts, _)| *ts >= since_ts)
+    {
+        if let Some((_, to)) = message
+            .strip_prefix("checkout: moving from ")
+            .and_then(|m| m.rsplit_once(" to "))
+        {
+            if locals.lines().any(|b| b == to) {
+                entry.branches.insert(to.to_string());
+            }
+        }
+    }
+    entry
+        .branches
+        .extend(entry.commits.iter().filter_map(|c| c.branch.clone()));
+
+    entry.wip = git::probe(repo, &["status", "--porcelain"])
+        .map(|out| out.lines().map(String::from).collect())
+        .unwrap_or_default();
+    Ok(entry)
+}
+
+/// Renders one repository's section of the journal.
+pub fn render(name: &str, entry: &Entry) -> String {
+    let mut md = String::new();
+    let _ = writeln!(md, "## {}\n", name);
+    if !entry.branches.is_empty() {
+        let branches: Vec<String> = entry.branches.iter().map(|b| format!("`{}`", b)).collect();
+        let _ = writeln!(md, "**Branches:** {}\n", branches.join(", "));
+    }
+    if !entry.commits.is_empty() {
+        let _ = writeln!(md, "**Commits:**\n");
+        for c in &entry.commits {
+            match &c.branch {
+                Some(b) => {
+                    let _ = writeln!(md, "- {} `{}` ({})", c.subject, c.short, b);
+                }
+                None => {
+                    let _ = writeln!(md, "- {} `{}`", c.subject, c.short);
+                }
+            }
+        }
+        md.push('\n');
+    }
+    if !entry.wip.is_empty() {
+        let _ = writeln!(md, "**In progress:**\n");
+        for line in &entry.wip {
+            let _ = writeln!(md, "- `{}`", line.trim());
+        }
+        md.push('\n');
+    }
+    md
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,75 @@
+use super::*;
+use crate::test_support::{git, git_at};
+use std::fs;
+use tempfile::tempdir;
+
+fn commit(dir: &Path, file: &str, email: &str) {
+    fs::write(dir.join(file), file).unwrap();
+    git(dir, &["add", "-A"]);
+    git(
+        dir,
+        &[
+            "-c",
+            &format!("user.email={}", email),
+            "commit",
+            "-q",
+            "-m",
+            &format!("Add {}", file),
+        ],
+    );
+}
+
+#[test]
+fn test_collect_lists_own_commits_branches_and_wip() {
+    let dir = tempdir().unwrap();
+    let repo = dir.path();
+    git(repo, &["init", "-q", "-b", "main"]);
+    git(repo, &["config", "user.name", "Toad"]);
+    git(repo, &["config", "user.email", "toad@example.com"]);
+    commit(repo, "base.rs", "toad@example.com");
+    git(repo, &["checkout", "-q", "-b", "feat-pond"]);
+    commit(repo, "pond.rs", "toad@example.com");
+    commit(repo, "other.rs", "newt@example.com");
+    fs::write(repo.join("draft.md"), "wip").unwrap();
+
+    let since_ts = resolve_since(repo, "1 hour ago").unwrap();
+    let entry = collect(repo, "1 hour ago", since_ts).unwrap();
+    let subjects: Vec<&str> = entry.commits.iter().map(|c| c.subject.as_str()).collect();
+    assert_eq!(subjects, vec!["Add base.rs", "Add pond.rs"]);
+    assert_eq!(entry.commits[1].branch.as_deref(), Some("feat-pond"));
+    assert_eq!(
+        entry.branches.iter().collect::<Vec<_>>(),
+        vec!["feat-pond", "main"]
+    );
+    assert_eq!(entry.wip, vec!["?? draft.md".to_string()]);
+
+    let md = render("pond", &entry);
+    assert!(md.starts_with("## pond\n"));
+    assert!(md.contains("**Branches:** `feat-pond`, `main`"));
+    assert!(md.contains("- Add pond.rs `"));
+    assert!(md.contains("- `?? draft.md`"));
+}
+
+#[test]
+fn test_window_excludes_older_activity() {
+    let dir = tempdir().unwrap();
+    let repo = dir.path();
+    git(repo, &["init", "-q", "-b", "main"]);
+    git(repo, &["config", "user.name", "Toad"]);
+    git(repo, &["config", "user.email", "toad@example.com"]);
+    fs::write(repo.join("old.rs"), "old").unwrap();
+    git(repo, &["add", "-A"]);
+    // Reflog entries carry the committer date too, so the whole setup is old.
+    let old = "2020-01-01T00:00:00Z";
+    git_at(repo, &["commit", "-q", "-m", "Old work"], old);
+    git_at(repo, &["branch", "review"], old);
+
+    let since_ts = resolve_since(repo, "1 day ago").unwrap();
+    assert!(collect(repo, "1 day ago", since_ts).unwrap().is_empty());
+
+    // Checking a branch out today counts as touching it.
+    git(repo, &["checkout", "-q", "review"]);
+    let entry = collect(repo, "1 day ago", since_ts).unwrap();
+    assert!(entry.commits.is_empty());
+    assert_eq!(entry.branches.iter().collect::<Vec<_>>(), vec!["review"]);
+}
@@ -0,0 +1,251 @@
+//! Ecosystem lockfiles for `toad lock`.
+//!
+//! A `toad.lock` pins every project and submodule to an exact commit so an
+//! ecosystem state can be shared in a bug report and restored later.
+//!
+//! ```toml
+//! version = 1
+//!
+//! [[repo]]
+//! name = "pond"
+//! path = "projects/pond"
+//! remote = "git@github.com:org/pond.git"
+//! branch = "main"
+//! commit = "4f3c2a1..."
+//! ```
+
+use crate::git;
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use toad_core::GitOpResult;
+
+pub const LOCK_FILE: &str = "toad.lock";
+const LOCK_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Lockfile {
+    pub version: u32,
+    #[serde(default, rename = "repo")]
+    pub repos: Vec<LockedRepo>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LockedRepo {
+    pub name: String,
+    /// Path relative to the workspace root (`.` for the Hub root).
+    pub path: String,
+    pub remote: Option<String>,
+    /// `None` when the repository was on a detached HEAD.
+    pub branch: Option<String>,
+    pub commit: String,
+}
+
+/// A repository whose pinned state differs between two lockfiles.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LockChange {
+    pub path: String,
+    pub old: Option<LockedRepo>,
+    pub new: Option<LockedRepo>,
+}
+
+impl Lockfile {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content =
+            fs::read_to_string(path).with_context(|| format!("Could not read {:?}", path))?;
+        let lock: Self =
+            toml::from_str(&content).with_context(|| format!("Invalid lockfile {:?}", path))?;
+        if lock.version > LOCK_VERSION {
+            bail!(
+                "{:?} uses lockfile version {}, this toad supports up to {}.",
+                path,
+                lock.version,
+                LOCK_VERSION
+            );
+        }
+        Ok(lock)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, toml::to_string(self)?)?;
+        Ok(())
+    }
+}
+
+impl LockedRepo {
+    pub fn abs_path(&self, root: &Path) -> PathBuf {
+        root.join(&self.path)
+    }
+}
+
+impl LockChange {
+    /// `%h %s` lines for the commits from the old pin to the new one, read in
+    /// the new checkout. Empty when the pin moved back; `None` when either
+    /// side is missing or the history is not available locally.
+    pub fn log(&self, root: &Path) -> Option<Vec<String>> {
+        let (old, new) = (self.old.as_ref()?, self.new.as_ref()?);
+        let range = format!("{}..{}", old.commit, new.commit);
+        git::probe(&new.abs_path(root), &["log", "--format=%h %s", &range])
+            .map(|log| log.lines().map(String::from).collect())
+    }
+}
+
+/// Where a locked repository stands before a restore.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RestoreState {
+    /// Already at the pinned commit and branch.
+    Current,
+    Ready,
+    /// Blocking issues from [`preflight`].
+    Blocked(Vec<String>),
+}
+
+/// Records the current remote, branch and commit of each repository.
+/// Repositories without a commit yet are left out.
+pub fn snapshot(root: &Path, repos: &[(String, PathBuf)]) -> Lockfile {
+    let repos = repos
+        .iter()
+        .filter_map(|(name, path)| capture(root, name, path))
+        .collect();
+    Lockfile {
+        version: LOCK_VERSION,
+        repos,
+    }
+}
+
+fn capture(root: &Path, name: &str, path: &Path) -> Option<LockedRepo> {
+    let commit = git::probe(path, &["rev-parse", "HEAD"])?;
+    let rel = path.strip_prefix(root).unwrap_or(path);
+    let rel = if rel.as_os_str().is_empty() {
+        ".".to_string()
+    } else {
+        rel.to_string_lossy().to_string()
+    };
+    Some(LockedRepo {
+        name: name.to_string(),
+        path: rel,
+        remote: remote_url(path),
+        branch: git::probe(path, &["symbolic-ref", "--short", "-q", "HEAD"]),
+        commit,
+    })
+}
+
+/// Ways a snapshot entry may fail to reproduce the checkout elsewhere.
+pub fn warnings(root: &Path, entry: &LockedRepo) -> Vec<String> {
+    let mut warnings = Vec::new();
+    if git::tracked_changes(&entry.abs_path(root)) > 0 {
+        warnings.push("Uncommitted changes are not captured by the lock.".to_string());
+    }
+    if entry.remote.is_none() {
+        warnings.push("No remote configured; others cannot fetch this commit.".to_string());
+    }
+    warnings
+}
+
+/// URL of `origin`, falling back to the first configured remote.
+pub fn remote_url(path: &Path) -> Option<String> {
+    let remote = git::default_remote(path)?;
+    git::probe(path, &["remote", "get-url", &remote])
+}
+
+/// Blocking issues for restoring `entry`. Fetches from the recorded remote
+/// when the pinned commit is not available locally.
+pub fn preflight(root: &Path, entry: &LockedRepo) -> Vec<String> {
+    let path = entry.abs_path(root);
+    if git::probe(&path, &["rev-parse", "--git-dir"]).is_none() {
+        return vec!["Missing — clone it first (see 'toad clone')".to_string()];
+    }
+
+    let mut issues = Vec::new();
+    let dirty = git::tracked_changes(&path);
+    if dirty > 0 {
+        issues.push(format!("Dirty — {} uncommitted files", dirty));
+    }
+
+    let target = format!("{}^{{commit}}", entry.commit);
+    if !git::ref_exists(&path, &target) {
+        if let Some(remote) = &entry.remote {
+            let _ = git::probe(&path, &["fetch", "--quiet", remote]);
+        }
+        if !git::ref_exists(&path, &target) {
+            issues.push(format!(
+                "Commit {} is not available locally or on the recorded remote",
+                git::short(&entry.commit)
+            ));
+        }
+    }
+    issues
+}
+
+/// True when the repository already matches the pinned state.
+pub fn is_current(root: &Path, entry: &LockedRepo) -> bool {
+    let path = entry.abs_path(root);
+    git::probe(&path, &["rev-parse", "HEAD"]).as_deref() == Some(entry.commit.as_str())
+        && git::probe(&path, &["symbolic-ref", "--short", "-q", "HEAD"]) == entry.branch
+}
+
+/// Classifies every repository in a lockfile ahead of [`restore`].
+pub fn plan_restore<'a>(root: &Path, lock: &'a Lockfile) -> Vec<(&'a LockedRepo, RestoreState)> {
+    lock.repos
+        .iter()
+        .map(|entry| {
+            let state = if is_current(root, entry) {
+                RestoreState::Current
+            } else {
+                match preflight(root, entry) {
+                    issues if issues.is_empty() => RestoreState::Ready,
+                    issues => RestoreState::Blocked(issues),
+                }
+            };
+            (entry, state)
+        })
+        .collect()
+}
+
+/// Checks the repository out to the pinned commit. The recorded branch is
+/// used when its tip still matches; otherwise HEAD is detached so no local
+/// branch is rewritten.
+pub fn restore(root: &Path, entry: &LockedRepo) -> Result<GitOpResult> {
+    let path = entry.abs_path(root);
+    let on_branch = entry.branch.as_ref().filter(|branch| {
+        git::probe(&path, &["rev-parse", &format!("refs/heads/{}", branch)]).as_deref()
+            == Some(entry.commit.as_str())
+    });
+    match on_branch {
+        Some(branch) => toad_git::run_git(&path, &["checkout", "--quiet", branch], &entry.name),
+        None => toad_git::run_git(
+            &path,
+            &["checkout", "--quiet", "--detach", &entry.commit],
+            &entry.name,
+        ),
+    }
+}
+
+/// Repositories added, removed or moved between two lockfiles, keyed by path.
+pub fn diff(old: &Lockfile, new: &Lockfile) -> Vec<LockChange> {
+    let mut changes = Vec::new();
+    for o in &old.repos {
+        let n = new.repos.iter().find(|n| n.path == o.path);
+        if n != Some(o) {
+            changes.push(LockChange {
+                path: o.path.clone(),
+                old: Some(o.clone()),
+                new: n.cloned(),
+            });
+        }
+    }
+    for n in &new.repos {
+        if !old.repos.iter().any(|o| o.path == n.path) {
+            changes.push(LockChange {
+                path: n.path.clone(),
+                old: None,
+                new: Some(n.clone()),
+            });
+        }
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,85 @@
+use super::*;
+use crate::test_support;
+use std::fs;
+use tempfile::tempdir;
+
+fn repo(path: &str, commit: &str) -> LockedRepo {
+    LockedRepo {
+        name: path.to_string(),
+        path: path.to_string(),
+        remote: Some(format!("https://example.com/{}.git", path)),
+        branch: Some("main".to_string()),
+        commit: commit.to_string(),
+    }
+}
+
+#[test]
+fn test_lockfile_round_trip() {
+    let lock = Lockfile {
+        version: LOCK_VERSION,
+        repos: vec![repo("projects/pond", "abc123"), repo(".", "def456")],
+    };
+    let text = toml::to_string(&lock).unwrap();
+    assert!(text.contains("[[repo]]"));
+    assert_eq!(toml::from_str::<Lockfile>(&text).unwrap(), lock);
+}
+
+#[test]
+fn test_lock_diff_reports_moves_additions_and_removals() {
+    let old = Lockfile {
+        version: LOCK_VERSION,
+        repos: vec![
+            repo("projects/a", "111"),
+            repo("projects/b", "222"),
+            repo("projects/c", "333"),
+        ],
+    };
+    let new = Lockfile {
+        version: LOCK_VERSION,
+        repos: vec![
+            repo("projects/a", "111"),
+            repo("projects/b", "999"),
+            repo("projects/d", "444"),
+        ],
+    };
+
+    let changes = diff(&old, &new);
+    let paths: Vec<_> = changes.iter().map(|c| c.path.as_str()).collect();
+    assert_eq!(paths, vec!["projects/b", "projects/c", "projects/d"]);
+    assert_eq!(changes[0].new.as_ref().unwrap().commit, "999");
+    assert!(changes[1].new.is_none());
+    assert!(changes[2].old.is_none());
+}
+
+#[test]
+fn test_plan_restore_warnings_and_change_log() {
+    let dir = tempdir().unwrap();
+    let root = dir.path();
+    let pond = test_support::repo(root, "pond");
+    let first = git::probe(&pond, &["rev-parse", "HEAD"]).unwrap();
+    test_support::commit(&pond, "lily.rs");
+
+    let old = snapshot(root, &[("pond".to_string(), pond.clone())]);
+    assert_eq!(old.repos[0].path, "pond");
+    assert_eq!(
+        warnings(root, &old.repos[0]),
+        vec!["No remote configured; others cannot fetch this commit.".to_string()]
+    );
+    assert_eq!(
+        plan_restore(root, &old),
+        vec![(&old.repos[0], RestoreState::Current)]
+    );
+
+    let mut back = old.clone();
+    back.repos[0].commit = first;
+    assert_eq!(plan_restore(root, &back)[0].1, RestoreState::Ready);
+    let changes = diff(&back, &old);
+    assert_eq!(changes[0].log(root).unwrap().len(), 1);
+    assert!(diff(&old, &back)[0].log(root).unwrap().is_empty());
+
+    fs::write(pond.join("lily.rs"), "changed").unwrap();
+    assert!(matches!(
+        &plan_restore(root, &back)[0].1,
+        RestoreState::Blocked(issues) if issues[0].starts_with("Dirty")
+    ));
+}
@@ -8,13 +8,47 @@ use std::io::{self, Write};
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
-use toad_core::{TagRegistry, VcsStatus, Workspace};
+use toad_core::{ProjectDetail, SubmoduleDetail, TagRegistry, VcsStatus, Workspace};
 use toad_discovery::scan_all_projects;
 use toad_ops::stats::{calculate_project_stats, format_size};
 use toad_scaffold::{create_project, open_in_editor, ProjectConfig};
 
+mod align;
+mod audit;
+mod authors;
+mod backend;
+mod bisect;
+mod bootstrap;
+mod cascade;
+mod change;
+mod changelog;
+mod git;
+mod grep;
+mod history;
+mod hooks;
+mod integrate;
+mod journal;
+mod lock;
+mod maintain;
+mod mirror;
+mod policy;
+mod prune;
+mod push;
+mod release;
+mod remotes;
+mod risk;
+mod submodules;
+#[cfg(test)]
+mod test_support;
+mod txn;
+mod worktree;
+
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// Marker file that identifies a directory as a Toad home.
+const ROOT_MARKER: &str = ".toad-root";
+const ROOT_MARKER_CONTENT: &str = "# Primatif Toad Workspace Root\n# This file identifies this directory as a Toad Control Plane home.\n# Do not delete this file if you want the 'toad' CLI to recognize this workspace.\n";
+
 #[derive(Parser)]
 #[command(name = "toad")]
 #[command(about = "Primatif_Toad: Toad Control CLI", version = VERSION)]
@@ -64,6 +98,26 @@ enum Commands {
         /// Show details for all matching projects
         #[arg(long, short = 'a')]
         all: bool,
+
+        /// Report commits, lines changed and bus factor per author instead of disk usage
+        #[arg(long)]
+        authors: bool,
+
+        /// History window for --authors, as any date git understands (default: "90 days ago")
+        #[arg(long, requires = "authors")]
+        since: Option<String>,
+
+        /// Show growth and shrink over a window of recorded runs (e.g. 30d) with sparklines
+        #[arg(long, value_name = "WINDOW", conflicts_with_all = ["authors", "diff"])]
+        trend: Option<String>,
+
+        /// Show what grew the most since a date (YYYY-MM-DD, or a window such as 7d)
+        #[arg(long, value_name = "DATE", conflicts_with = "authors")]
+        diff: Option<String>,
+
+        /// List the largest blobs in each repository's history (walks every object)
+        #[arg(long, conflicts_with_all = ["authors", "trend", "diff"])]
+        blobs: bool,
     },
     /// Manage the global Toad workspace anchor
     Home {
@@ -182,6 +236,162 @@ enum Commands {
         #[command(subcommand)]
         subcommand: GgitCommand,
     },
+    /// Search tracked files across projects and submodules
+    Grep {
+        /// Pattern to search for (git grep basic regex)
+        pattern: String,
+        /// Optional query to filter projects
+        #[arg(long, short = 'q')]
+        query: Option<String>,
+        /// Filter by tag
+        #[arg(long, short = 't')]
+        tag: Option<String>,
+        /// Case-insensitive match
+        #[arg(long, short = 'i')]
+        ignore_case: bool,
+        /// Treat the pattern as a literal string
+        #[arg(long, short = 'F')]
+        fixed_strings: bool,
+        /// Show match counts per file
+        #[arg(long, short = 'c', conflicts_with = "files_with_matches")]
+        count: bool,
+        /// Only list files that match
+        #[arg(long, short = 'l')]
+        files_with_matches: bool,
+        /// Print results as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Report work that exists only on this machine (unpushed, stashed, uncommitted)
+    Risk {
+        /// Optional query to filter projects
+        #[arg(long, short = 'q')]
+        query: Option<String>,
+        /// Filter by tag
+        #[arg(long, short = 't')]
+        tag: Option<String>,
+        /// Print results as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Markdown standup notes from local git history (commits, branches, work in progress)
+    Journal {
+        /// Start of the window, as any date git understands
+        #[arg(long, short = 's', default_value = "yesterday")]
+        since: String,
+        /// Optional query to filter projects
+        #[arg(long, short = 'q')]
+        query: Option<String>,
+        /// Filter by tag
+        #[arg(long, short = 't')]
+        tag: Option<String>,
+        /// Write the Markdown to a file instead of stdout
+        #[arg(long, short = 'o')]
+        output: Option<PathBuf>,
+    },
+    /// Snapshot exact commits across the ecosystem into a toad.lock
+    #[command(args_conflicts_with_subcommands = true)]
+    Lock {
+        #[command(subcommand)]
+        subcommand: Option<LockCommand>,
+        /// Lockfile to write (defaults to toad.lock in the workspace root)
+        #[arg(long, short = 'o')]
+        output: Option<PathBuf>,
+        /// Optional query to filter projects
+        #[arg(long, short = 'q')]
+        query: Option<String>,
+        /// Filter by tag
+        #[arg(long, short = 't')]
+        tag: Option<String>,
+    },
+    /// Clone an ecosystem from a manifest of remotes (or a toad.lock)
+    Clone {
+        /// Manifest TOML or toad.lock listing the projects to clone
+        manifest: PathBuf,
+        /// Workspace root to clone into (defaults to the current workspace)
+        #[arg(long)]
+        dest: Option<PathBuf>,
+        /// Name to register the context under
+        #[arg(long, short = 'n')]
+        name: Option<String>,
+        /// Preview the clones without running them
+        #[arg(long, short = 'd')]
+        dry_run: bool,
+    },
+    /// Find the first bad combined state between two Hub refs or lockfiles
+    Bisect {
+        /// Known-good Hub ref or toad.lock
+        #[arg(long)]
+        good: String,
+        /// Known-bad Hub ref or toad.lock
+        #[arg(long)]
+        bad: String,
+        /// Project (or repository path) to run the test in (defaults to the workspace root)
+        #[arg(long, short = 'p')]
+        project: Option<String>,
+        /// Test command; exit 0 = good, 125 = skip, anything else = bad
+        #[arg(last = true, required = true)]
+        command: Vec<String>,
+    },
+    /// Generate Keep a Changelog sections from Conventional Commits between two refs or lockfiles
+    Changelog {
+        /// Range as <from>..<to>: two tags/refs, or two toad.lock files
+        range: String,
+        /// Optional query to filter projects
+        #[arg(long, short = 'q')]
+        query: Option<String>,
+        /// Filter by tag
+        #[arg(long, short = 't')]
+        tag: Option<String>,
+        /// Version heading for the new section (defaults to <to>)
+        #[arg(long)]
+        version: Option<String>,
+        /// Prepend the sections to each repository's CHANGELOG.md and the Hub's
+        #[arg(long, short = 'w')]
+        write: bool,
+    },
+    /// Group one feature branch across several repositories (start, status, finish)
+    Change {
+        #[command(subcommand)]
+        subcommand: ChangeCommand,
+    },
+    /// Distribute a context-level git hook set (install, status, remove)
+    Hooks {
+        #[command(subcommand)]
+        subcommand: HooksCommand,
+        /// Optional query to filter projects
+        #[arg(long, short = 'q', global = true)]
+        query: Option<String>,
+        /// Filter by tag
+        #[arg(long, short = 't', global = true)]
+        tag: Option<String>,
+        /// Hook template directory (defaults to hooks/ in the context directory)
+        #[arg(long, global = true)]
+        from: Option<PathBuf>,
+    },
+    /// Find build artifacts that are tracked in git or missing from .gitignore
+    AuditIgnore {
+        /// Optional query to filter projects
+        #[arg(long, short = 'q')]
+        query: Option<String>,
+        /// Filter by tag
+        #[arg(long, short = 't')]
+        tag: Option<String>,
+        /// Append missing entries to .gitignore and untrack committed artifacts
+        #[arg(long)]
+        fix: bool,
+        /// Skip confirmation prompts
+        #[arg(long, short = 'y', requires = "fix")]
+        yes: bool,
+        /// Preview the fixes without applying them
+        #[arg(long, short = 'd', requires = "fix")]
+        dry_run: bool,
+    },
+    /// Maintain local bare mirrors of every remote for offline work and fast clones
+    Mirror {
+        #[command(subcommand)]
+        subcommand: MirrorCommand,
+    },
     /// Custom workflows and script orchestration
     Cw {
         #[command(subcommand)]
@@ -201,6 +411,71 @@ enum SkillCommand {
     List,
 }
 
+#[derive(Subcommand)]
+enum LockCommand {
+    /// Check every repository out to the commits recorded in a lockfile
+    Restore {
+        /// Lockfile to restore (defaults to toad.lock in the workspace root)
+        file: Option<PathBuf>,
+        /// Skip confirmation prompt
+        #[arg(long, short = 'y')]
+        yes: bool,
+        /// Preview the checkouts without running them
+        #[arg(long, short = 'd')]
+        dry_run: bool,
+    },
+    /// Compare two lockfiles, or one lockfile against the current checkouts
+    Diff {
+        /// The older lockfile
+        old: PathBuf,
+        /// The newer lockfile (defaults to the current state of the repositories)
+        new: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ChangeCommand {
+    /// Create the change branch in the selected repositories
+    Start {
+        /// Name of the change set
+        name: String,
+        /// Select repositories (and submodules) whose name contains this; repeatable
+        #[arg(long, short = 'q')]
+        query: Vec<String>,
+        /// Filter by tag
+        #[arg(long, short = 't')]
+        tag: Option<String>,
+        /// Branch to create (defaults to the change name)
+        #[arg(long, short = 'b')]
+        branch: Option<String>,
+        /// Skip confirmation prompt
+        #[arg(long, short = 'y')]
+        yes: bool,
+        /// Preview the repositories without creating branches
+        #[arg(long, short = 'd')]
+        dry_run: bool,
+    },
+    /// Show commits, push and merge state for each repository in a change
+    Status {
+        /// Change set to show (defaults to all)
+        name: Option<String>,
+    },
+    /// Merge or clean up a change and forget it
+    Finish {
+        /// Name of the change set
+        name: String,
+        /// Merge unmerged branches into their base (--no-ff) before deleting them
+        #[arg(long)]
+        merge: bool,
+        /// Skip confirmation prompt
+        #[arg(long, short = 'y')]
+        yes: bool,
+        /// Preview the merges and deletions without running them
+        #[arg(long, short = 'd')]
+        dry_run: bool,
+    },
+}
+
 #[derive(Subcommand)]
 enum CwCommand {
     /// Execute a custom workflow script
@@ -275,6 +550,9 @@ enum GgitCommand {
         /// Halt the entire batch if a single repo fails
         #[arg(long, short = 'f')]
         fail_fast: bool,
+        /// Run the push preflight and list outgoing commits without pushing
+        #[arg(long, short = 'd')]
+        dry_run: bool,
     },
     /// Pull changes across repositories
     Pull {
@@ -317,6 +595,17 @@ enum GgitCommand {
         #[arg(long, short = 'f')]
         force: bool,
     },
+    /// Continue an interrupted `ggit commit` or `ggit sync`
+    Resume,
+    /// Reset repositories to the heads recorded by an interrupted operation
+    Rollback {
+        /// Skip confirmation prompt
+        #[arg(long, short = 'y')]
+        yes: bool,
+        /// Preview the resets without changing anything
+        #[arg(long, short = 'd')]
+        dry_run: bool,
+    },
     /// List all branches across repositories
     Branches {
         /// Optional query to filter projects
@@ -329,6 +618,55 @@ enum GgitCommand {
         #[arg(long, short = 'r')]
         all: bool,
     },
+    /// Delete merged or stale branches across repositories
+    PruneBranches {
+        /// Optional query to filter projects
+        #[arg(long, short = 'q')]
+        query: Option<String>,
+        /// Filter by tag
+        #[arg(long, short = 't')]
+        tag: Option<String>,
+        /// Also prune unmerged branches with no commits for this many days
+        #[arg(long, value_name = "DAYS")]
+        stale: Option<u64>,
+        /// Include remote branches (deleted on the remote itself)
+        #[arg(long, short = 'r')]
+        remote: bool,
+        /// Skip confirmation prompt
+        #[arg(long, short = 'y')]
+        yes: bool,
+        /// Preview the branches without deleting them
+        #[arg(long, short = 'd')]
+        dry_run: bool,
+    },
+    /// Run gc, prune, repack and commit-graph write across repositories
+    Maintain {
+        /// Optional query to filter projects
+        #[arg(long, short = 'q')]
+        query: Option<String>,
+        /// Filter by tag
+        #[arg(long, short = 't')]
+        tag: Option<String>,
+        /// Show current .git sizes and loose objects without running maintenance
+        #[arg(long, short = 'd')]
+        dry_run: bool,
+    },
+    /// Manage remotes across repositories (list, set-url, rename, add)
+    Remote {
+        #[command(subcommand)]
+        subcommand: RemoteCommand,
+        /// Optional query to filter projects
+        #[arg(long, short = 'q', global = true)]
+        query: Option<String>,
+        /// Filter by tag
+        #[arg(long, short = 't', global = true)]
+        tag: Option<String>,
+    },
+    /// Check one branch out across repositories in linked worktrees
+    Worktree {
+        #[command(subcommand)]
+        subcommand: WorktreeCommand,
+    },
     /// Force-align submodules to Hub root expectations
     Align {
         /// Optional query to filter projects
@@ -337,6 +675,193 @@ enum GgitCommand {
         /// Filter by tag
         #[arg(long, short = 't')]
         tag: Option<String>,
+        /// Preview the checkouts and any work they would lose
+        #[arg(long, short = 'd')]
+        dry_run: bool,
+        /// Align even if commits or changes would be lost (a backup ref is kept)
+        #[arg(long)]
+        force: bool,
+    },
+    /// Rebase the current branch onto a ref across repositories
+    Rebase {
+        /// Ref to rebase onto (e.g., origin/main)
+        #[arg(required_unless_present_any = ["abort", "cont"])]
+        onto: Option<String>,
+        /// Optional query to filter projects
+        #[arg(long, short = 'q')]
+        query: Option<String>,
+        /// Filter by tag
+        #[arg(long, short = 't')]
+        tag: Option<String>,
+        /// Abort the in-progress rebase in every repository
+        #[arg(long, conflicts_with_all = ["onto", "cont"])]
+        abort: bool,
+        /// Continue the in-progress rebase in every repository
+        #[arg(long = "continue", conflicts_with = "onto")]
+        cont: bool,
+        /// Stop at the first repository that conflicts or fails
+        #[arg(long, short = 'f')]
+        fail_fast: bool,
+    },
+    /// Merge a branch into the current branch across repositories
+    Merge {
+        /// Branch to merge
+        #[arg(required_unless_present_any = ["abort", "cont"])]
+        branch: Option<String>,
+        /// Optional query to filter projects
+        #[arg(long, short = 'q')]
+        query: Option<String>,
+        /// Filter by tag
+        #[arg(long, short = 't')]
+        tag: Option<String>,
+        /// Abort the in-progress merge in every repository
+        #[arg(long, conflicts_with_all = ["branch", "cont"])]
+        abort: bool,
+        /// Continue the in-progress merge in every repository
+        #[arg(long = "continue", conflicts_with = "branch")]
+        cont: bool,
+        /// Stop at the first repository that conflicts or fails
+        #[arg(long, short = 'f')]
+        fail_fast: bool,
+    },
+    /// Bump versions, commit (cascade) and tag a coordinated release
+    Release {
+        /// Version to release (e.g., 1.2.0)
+        version: String,
+        /// Optional query to filter projects
+        #[arg(long, short = 'q')]
+        query: Option<String>,
+        /// Filter by tag
+        #[arg(long, short = 't')]
+        tag: Option<String>,
+        /// Commit message (defaults to "chore(release): v<version>")
+        #[arg(long, short = 'm')]
+        message: Option<String>,
+        /// Skip confirmation prompt
+        #[arg(long, short = 'y')]
+        yes: bool,
+        /// Show the release preview without changing anything
+        #[arg(long, short = 'd')]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum WorktreeCommand {
+    /// Create worktrees for a branch under .worktrees/<branch> and register them as a context
+    Add {
+        /// Branch to check out
+        branch: String,
+        /// Optional query to filter projects
+        #[arg(long, short = 'q')]
+        query: Option<String>,
+        /// Filter by tag
+        #[arg(long, short = 't')]
+        tag: Option<String>,
+        /// Create the branch from HEAD where it does not exist yet
+        #[arg(long, short = 'c')]
+        create: bool,
+        /// Preview the worktrees without creating them
+        #[arg(long, short = 'd')]
+        dry_run: bool,
+    },
+    /// Remove a branch's worktrees and unregister their context
+    Remove {
+        /// Branch whose worktrees to remove
+        branch: String,
+        /// Remove even if worktrees have uncommitted or untracked changes
+        #[arg(long)]
+        force: bool,
+        /// Also remove worktrees locked with `git worktree lock`
+        #[arg(long)]
+        unlock: bool,
+        /// Skip confirmation prompt
+        #[arg(long, short = 'y')]
+        yes: bool,
+        /// Preview the removal without changing anything
+        #[arg(long, short = 'd')]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum HooksCommand {
+    /// Install the hook set, backing up any different existing hooks
+    Install {
+        /// Point core.hooksPath at the template directory instead of copying
+        #[arg(long)]
+        link: bool,
+        /// Preview the changes without installing anything
+        #[arg(long, short = 'd')]
+        dry_run: bool,
+    },
+    /// Report repositories whose hooks are missing or drifted from the templates
+    Status,
+    /// Remove installed hooks and restore the ones they replaced
+    Remove {
+        /// Also remove hooks that were modified after installation
+        #[arg(long)]
+        force: bool,
+        /// Preview the changes without removing anything
+        #[arg(long, short = 'd')]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum MirrorCommand {
+    /// Create or update a bare mirror of every project and submodule remote
+    Sync {
+        /// Optional query to filter projects
+        #[arg(long, short = 'q')]
+        query: Option<String>,
+        /// Filter by tag
+        #[arg(long, short = 't')]
+        tag: Option<String>,
+    },
+    /// List the mirrors with their remote, size and last sync
+    List,
+}
+
+#[derive(Subcommand)]
+enum RemoteCommand {
+    /// List remotes and their URLs across repositories
+    List,
+    /// Rewrite remote and .gitmodules URLs that start with a prefix
+    SetUrl {
+        /// URL prefix to replace (e.g. git@old-host:org/)
+        from: String,
+        /// Replacement (e.g. git@new-host:neworg/)
+        to: String,
+        /// Only rewrite this remote (defaults to every remote)
+        #[arg(long, short = 'r')]
+        remote: Option<String>,
+        /// Skip confirmation prompt
+        #[arg(long, short = 'y')]
+        yes: bool,
+        /// Show the rewrite table without applying it
+        #[arg(long, short = 'd')]
+        dry_run: bool,
+    },
+    /// Rename a remote in every repository that has it
+    Rename {
+        /// Current remote name
+        old: String,
+        /// New remote name
+        new: String,
+        /// Preview without renaming
+        #[arg(long, short = 'd')]
+        dry_run: bool,
+    },
+    /// Add a remote to every repository ({name} expands to the repository name)
+    Add {
+        /// Remote name
+        name: String,
+        /// URL template, e.g. git@backup:org/{name}.git
+        url: String,
+        /// Preview without adding
+        #[arg(long, short = 'd')]
+        dry_run: bool,
     },
 }
 
@@ -459,7 +984,479 @@ enum StrategyCommands {
     },
 }
 
-fn print_banner() {
+/// Applies the shared `--query` / `--tag` filters.
+fn filter_projects(
+    projects: Vec<ProjectDetail>,
+    query: &Option<String>,
+    tag: &Option<String>,
+) -> Vec<ProjectDetail> {
+    projects
+        .into_iter()
+        .filter(|p| {
+            let name_match = match query {
+                Some(q) => p.name.to_lowercase().contains(&q.to_lowercase()),
+                None => true,
+            };
+            let tag_match = match tag {
+                Some(t) => {
+                    let target = if t.starts_with('#') {
+                        t.clone()
+                    } else {
+                        format!("#{}", t)
+                    };
+                    p.tags.contains(&target)
+                }
+                None => true,
+            };
+            name_match && tag_match
+        })
+        .collect()
+}
+
+/// The Hub root, when the workspace is a Hub that is not itself one of the
+/// targets, then each target followed by its submodules. Every path is
+/// listed once.
+fn hub_and_submodule_repos(
+    workspace: &Workspace,
+    targets: Vec<ProjectDetail>,
+) -> Vec<(String, PathBuf)> {
+    let mut repos = Vec::new();
+    if workspace.root.join(".gitmodules").exists()
+        && !targets.iter().any(|p| p.path == workspace.root)
+    {
+        repos.push(("Hub Root".to_string(), workspace.root.clone()));
+    }
+    for p in targets {
+        repos.push((p.name.clone(), p.path.clone()));
+        for sub in p.submodules {
+            repos.push((
+                format!("{} > {}", p.name, sub.name),
+                workspace.root.join(&sub.path),
+            ));
+        }
+    }
+    let mut seen = std::collections::HashSet::new();
+    repos.retain(|(_, path)| seen.insert(path.clone()));
+    repos
+}
+
+/// Asks a `[y/N]` question. Anything but an answer starting with `y` prints
+/// "Aborted." and returns false.
+fn confirm(prompt: &str) -> Result<bool> {
+    print!("{} [y/N]: ", prompt);
+    io::stdout().flush()?;
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    if input.trim().to_lowercase().starts_with('y') {
+        Ok(true)
+    } else {
+        println!("Aborted.");
+        Ok(false)
+    }
+}
+
+/// Prints a `--- <TITLE> SUMMARY ---` table of OK/FAIL rows with the error
+/// under each failure; returns true if anything failed.
+fn print_summary(title: &str, results: &[toad_core::GitOpResult]) -> bool {
+    println!("\n--- {} SUMMARY ---", title);
+    let mut any_fail = false;
+    for res in results {
+        let status = if res.success {
+            "OK".green()
+        } else {
+            any_fail = true;
+            "FAIL".red()
+        };
+        println!("{:<40} {}", res.project_name.bold(), status);
+        if !res.success {
+            println!("  Error: {}", res.stderr.trim().dimmed());
+        }
+    }
+    any_fail
+}
+
+/// Loads the cached project registry, rescanning when it is stale.
+fn load_projects(workspace: &Workspace) -> Result<Vec<ProjectDetail>> {
+    let registry = toad_core::ProjectRegistry::load(workspace.active_context.as_deref(), None)
+        .unwrap_or_default();
+    let current_fp = workspace.get_fingerprint().unwrap_or(0);
+
+    if registry.fingerprint == current_fp && !registry.projects.is_empty() {
+        Ok(registry.projects)
+    } else {
+        scan_all_projects(workspace)
+    }
+}
+
+/// Writes the `.toad-root` marker unless `root` already has one.
+fn ensure_root_marker(root: &std::path::Path) -> Result<()> {
+    let marker = root.join(ROOT_MARKER);
+    if !marker.exists() {
+        fs::write(marker, ROOT_MARKER_CONTENT)?;
+    }
+    Ok(())
+}
+
+/// Loads the global config, or an empty one if none has been saved yet.
+fn load_global_config() -> Result<toad_core::GlobalConfig> {
+    Ok(
+        toad_core::GlobalConfig::load(None)?.unwrap_or_else(|| toad_core::GlobalConfig {
+            home_pointer: PathBuf::from("."),
+            active_context: None,
+            project_contexts: std::collections::HashMap::new(),
+        }),
+    )
+}
+
+/// Adds context `name` at `path`, creates its per-context storage and saves
+/// the config.
+fn register_context(
+    config: &mut toad_core::GlobalConfig,
+    name: &str,
+    path: &std::path::Path,
+    description: Option<String>,
+    context_type: toad_core::ContextType,
+    ai_vendors: Vec<String>,
+) -> Result<()> {
+    config.project_contexts.insert(
+        name.to_string(),
+        toad_core::ProjectContext {
+            path: path.to_path_buf(),
+            description,
+            context_type,
+            ai_vendors,
+            registered_at: std::time::SystemTime::now(),
+        },
+    );
+    fs::create_dir_all(toad_core::GlobalConfig::context_dir(name, None)?.join("shadows"))?;
+    config.save(None)
+}
+
+/// Removes context `name` and its per-context storage, saving the config.
+/// Returns false when no such context was registered.
+fn unregister_context(config: &mut toad_core::GlobalConfig, name: &str) -> Result<bool> {
+    if config.project_contexts.remove(name).is_none() {
+        return Ok(false);
+    }
+    let ctx_dir = toad_core::GlobalConfig::context_dir(name, None)?;
+    if ctx_dir.exists() {
+        fs::remove_dir_all(&ctx_dir)?;
+    }
+    config.save(None)?;
+    Ok(true)
+}
+
+/// Validates a commit message against the active context's policy before any
+/// repository is touched.
+fn enforce_commit_policy(workspace: &Workspace, message: &str) -> Result<()> {
+    let policy = policy::ContextPolicy::load(workspace)?;
+    let issues = policy.commit.check(message);
+    if issues.is_empty() {
+        return Ok(());
+    }
+
+    println!("{}", "Commit policy violations:".yellow().bold());
+    for issue in &issues {
+        println!("  - {}", issue.yellow());
+    }
+    if let Some(path) = policy::ContextPolicy::path(workspace)? {
+        println!("\nPolicy: {:?}", path);
+    }
+    bail!("Commit message violates the context commit policy.");
+}
+
+/// Shared driver for `ggit rebase` and `ggit merge`, including the group-wide
+/// `--continue` and `--abort` modes.
+fn run_group_integration(
+    workspace: &Workspace,
+    targets: Vec<ProjectDetail>,
+    strategy: integrate::Strategy,
+    target: Option<&str>,
+    abort: bool,
+    cont: bool,
+    fail_fast: bool,
+) -> Result<()> {
+    let verb = strategy.verb();
+    println!(
+        "{}",
+        format!("--- MULTI-REPO GIT {} ---", verb.to_uppercase())
+            .blue()
+            .bold()
+    );
+
+    if targets.is_empty() {
+        println!("No projects found matching filters.");
+        return Ok(());
+    }
+
+    let mut repos = Vec::new();
+    for p in targets {
+        repos.push((p.name.clone(), p.path.clone()));
+        for sub in p.submodules {
+            repos.push((
+                format!("{} > {}", p.name, sub.name),
+                workspace.root.join(&sub.path),
+            ));
+        }
+    }
+
+    let resuming = abort || cont;
+    if resuming {
+        repos.retain(|(_, path)| integrate::in_progress(path) == Some(strategy));
+        if repos.is_empty() {
+            println!("No {} in progress in the targeted repositories.", verb);
+            return Ok(());
+        }
+    } else {
+        let mut any_issues = false;
+        for (name, path) in &repos {
+            let issues = integrate::preflight(path, strategy);
+            if !issues.is_empty() {
+                if !any_issues {
+                    println!("\n{} Safety checks failed:", "ERROR:".red().bold());
+                }
+                any_issues = true;
+                println!("  » {}:", name.cyan());
+                for issue in issues {
+                    println!("    - {}", issue.yellow());
+                }
+            }
+        }
+        if any_issues {
+            std::process::exit(1);
+        }
+    }
+
+    let mode = if abort {
+        integrate::Mode::Abort
+    } else if cont {
+        integrate::Mode::Continue
+    } else {
+        integrate::Mode::Start(target.unwrap_or_default())
+    };
+    let outcomes = integrate::run(&repos, strategy, mode, fail_fast, |name| match mode {
+        integrate::Mode::Abort => println!("Aborting {} in {}...", verb, name.cyan()),
+        integrate::Mode::Continue => println!("Continuing {} in {}...", verb, name.cyan()),
+        integrate::Mode::Start(target) => match strategy {
+            integrate::Strategy::Rebase => {
+                println!("Rebasing {} onto {}...", name.cyan(), target.cyan())
+            }
+            integrate::Strategy::Merge => {
+                println!("Merging {} into {}...", target.cyan(), name.cyan())
+            }
+        },
+    })?;
+
+    println!("\n--- {} SUMMARY ---", verb.to_uppercase());
+    let mut conflicted = Vec::new();
+    let mut any_fail = false;
+    for (name, outcome) in &outcomes {
+        let status = match outcome {
+            integrate::Outcome::Applied => "OK".green(),
+            integrate::Outcome::UpToDate => "UP-TO-DATE".dimmed(),
+            integrate::Outcome::Skipped(reason) => format!("SKIPPED ({})", reason).yellow(),
+            integrate::Outcome::Conflicted(files) => {
+                conflicted.push((name, files));
+                "CONFLICT".red().bold()
+            }
+            integrate::Outcome::Failed(_) => "FAIL".red(),
+        };
+        println!("{:<40} {}", name.bold(), status);
+        if let integrate::Outcome::Failed(err) = outcome {
+            any_fail = true;
+            println!("  Error: {}", err.dimmed());
+        }
+    }
+
+    if !conflicted.is_empty() {
+        println!(
+            "\n{} {} repositories stopped on conflicts:",
+            "⚠️".red(),
+            conflicted.len()
+        );
+        for (name, files) in &conflicted {
+            println!("  {} {}", "»".red(), name.bold());
+            for file in *files {
+                println!("    {} {}", "└─".dimmed(), file);
+            }
+        }
+        println!(
+            "\nResolve and stage the files, then run 'toad ggit {} --continue' (or '--abort').",
+            verb
+        );
+    }
+
+    if any_fail || !conflicted.is_empty() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Prints the repositories that failed their safety checks; returns true if
+/// there were any.
+fn report_preflight_failures(results: &[toad_core::PreflightResult]) -> bool {
+    if results.iter().all(|res| res.issues.is_empty()) {
+        return false;
+    }
+    println!("\n{} Safety checks failed:", "ERROR:".red().bold());
+    for res in results {
+        if !res.issues.is_empty() {
+            println!("  » {}:", res.project_name.cyan());
+            for issue in &res.issues {
+                println!("    - {}", issue.yellow());
+            }
+        }
+    }
+    true
+}
+
+/// Stops a new journaled operation while an interrupted one is still on disk.
+fn refuse_pending_transaction(journal_path: &std::path::Path) -> Result<()> {
+    let Some(pending) = txn::Journal::load(journal_path)? else {
+        return Ok(());
+    };
+    println!(
+        "{} An interrupted '{}' is still journaled at {:?}.",
+        "ERROR:".red().bold(),
+        pending.operation,
+        journal_path
+    );
+    println!(
+        "Run {} to finish it or {} to restore the recorded heads first.",
+        "toad ggit resume".bold(),
+        "toad ggit rollback".bold()
+    );
+    std::process::exit(1);
+}
+
+/// Runs a journaled operation and prints its summary. The journal is kept,
+/// and the process exits non-zero, unless every step completed.
+fn run_transaction(
+    mut journal: txn::Journal,
+    journal_path: &std::path::Path,
+    title: &str,
+) -> Result<()> {
+    let results = txn::run(&mut journal, journal_path, |step| {
+        let verb = match step.action {
+            txn::Action::Commit { .. } => "Committing",
+            txn::Action::CascadeCommit { .. } => "Cascading commit to",
+            txn::Action::Pull => "Updating",
+            txn::Action::SubmoduleUpdate => "Aligning",
+            txn::Action::Release { .. } => "Committing release in",
+            txn::Action::Tag { .. } => "Tagging",
+        };
+        println!("{} {}...", verb, step.name.cyan());
+    })?;
+
+    println!("\n--- {} SUMMARY ---", title);
+    for res in &results {
+        let status = if res.success {
+            "OK".green()
+        } else {
+            "FAIL".red()
+        };
+        println!("{:<40} {}", res.project_name.bold(), status);
+        if !res.success {
+            println!("  Error: {}", res.stderr.dimmed());
+        }
+    }
+    for step in &journal.steps {
+        match &step.state {
+            txn::StepState::Skipped(reason) => {
+                println!("{:<40} {}", step.name.bold(), "SKIPPED".yellow());
+                println!("  {}", reason.dimmed());
+            }
+            txn::StepState::Pending => {
+                println!("{:<40} {}", step.name.bold(), "NOT RUN".yellow())
+            }
+            _ => {}
+        }
+    }
+
+    if !journal.is_complete() {
+        println!(
+            "\n{} Stopped before every step completed; progress is journaled at {:?}.",
+            "WARN:".yellow(),
+            journal_path
+        );
+        println!(
+            "Run {} to continue or {} to restore the recorded heads.",
+            "toad ggit resume".bold(),
+            "toad ggit rollback".bold()
+        );
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Explains a drifted submodule beneath its status line: commits only in the
+/// checkout are ahead of the recorded pointer, commits only in the recorded
+/// pointer are what `ggit align` would bring in.
+fn print_drift(
+    backend: &dyn backend::GitBackend,
+    path: &std::path::Path,
+    expected: Option<&str>,
+    actual: Option<&str>,
+    depth: usize,
+) {
+    let indent = "  ".repeat(depth + 1);
+    let (Some(expected), Some(actual)) = (expected, actual) else {
+        return;
+    };
+    let Some(drift) = submodules::explain(backend, path, expected, actual) else {
+        println!(
+            "{}{}",
+            indent,
+            format!(
+                "recorded {} not available locally (checked out {}); fetch to compare",
+                git::short(expected),
+                git::short(actual)
+            )
+            .dimmed()
+        );
+        return;
+    };
+
+    println!(
+        "{}{} ↑{} not in recorded commit, ↓{} recorded but not checked out",
+        indent,
+        "drift:".yellow(),
+        drift.ahead,
+        drift.behind
+    );
+    for line in &drift.ahead_log {
+        println!("{}  {} {}", indent, "↑".yellow(), line);
+    }
+    if drift.ahead > drift.ahead_log.len() {
+        println!(
+            "{}    ... and {} more",
+            indent,
+            drift.ahead - drift.ahead_log.len()
+        );
+    }
+    for line in &drift.behind_log {
+        println!("{}  {} {}", indent, "↓".cyan(), line.dimmed());
+    }
+    if drift.behind > drift.behind_log.len() {
+        println!(
+            "{}    ... and {} more",
+            indent,
+            drift.behind - drift.behind_log.len()
+        );
+    }
+    // Ahead commits still held by a branch, tag or remote survive an align.
+    let unreachable = align::inspect("", path, Some(expected)).unreachable;
+    if !unreachable.is_empty() {
+        println!(
+            "{}{} `ggit align` would leave {} commit(s) unreachable",
+            indent,
+            "WARN:".yellow(),
+            unreachable.len()
+        );
+    }
+}
+
+fn print_banner() {
     // Force colors to ensure they show up in all environments
     colored::control::set_override(true);
 
@@ -482,7 +1479,11 @@ fn main() -> Result<()> {
     // Commands that don't require a valid workspace
     let is_bootstrap = matches!(
         &cli.command,
-        Commands::Home { .. } | Commands::Version | Commands::List | Commands::Docs
+        Commands::Home { .. }
+            | Commands::Clone { .. }
+            | Commands::Version
+            | Commands::List
+            | Commands::Docs
     );
 
     let workspace = match &discovered {
@@ -606,6 +1607,7 @@ fn main() -> Result<()> {
         Commands::Status { query, tag } => {
             println!("{}", "--- ECOSYSTEM HEALTH SCAN ---".green().bold());
             let projects = scan_all_projects(&workspace)?;
+            let backend = backend::select();
             let mut dirty = Vec::new();
             let mut untracked = Vec::new();
             let mut clean_count = 0;
@@ -665,14 +1667,13 @@ fn main() -> Result<()> {
                         "⭕ Uninit".to_string()
                     };
 
-                    let alignment = if sub.initialized {
-                        if sub.expected_commit == sub.actual_commit {
-                            " (aligned)".dimmed()
-                        } else {
-                            " (drifted)".red().bold()
-                        }
-                    } else {
+                    let drifted = sub.initialized && sub.expected_commit != sub.actual_commit;
+                    let alignment = if !sub.initialized {
                         "".normal()
+                    } else if drifted {
+                        " (drifted)".red().bold()
+                    } else {
+                        " (aligned)".dimmed()
                     };
 
                     println!(
@@ -683,6 +1684,75 @@ fn main() -> Result<()> {
                         status_indicator,
                         alignment
                     );
+
+                    let sub_path = workspace.root.join(&sub.path);
+                    if drifted {
+                        print_drift(
+                            backend.as_ref(),
+                            &sub_path,
+                            sub.expected_commit.as_deref(),
+                            sub.actual_commit.as_deref(),
+                            2,
+                        );
+                    }
+                    if !sub.initialized {
+                        continue;
+                    }
+
+                    // Discovery stops at the first level; walk the rest here.
+                    let mut stack: Vec<(submodules::SubmoduleNode, usize, String)> =
+                        submodules::tree(backend.as_ref(), &sub_path)
+                            .into_iter()
+                            .rev()
+                            .map(|n| (n, 2, format!("{} -> {}", project.name, sub.name)))
+                            .collect();
+                    while let Some((node, depth, parent)) = stack.pop() {
+                        total_matching += 1;
+                        let label = format!("{} -> {}", parent, node.state.name);
+                        let indent = "  ".repeat(depth);
+                        if !node.initialized() {
+                            no_repo_count += 1;
+                            println!(
+                                "{}{} {} ⭕ Uninit",
+                                indent,
+                                "└─".dimmed(),
+                                node.state.name.cyan()
+                            );
+                            continue;
+                        }
+
+                        let status = submodules::vcs_status(backend.as_ref(), &node.path);
+                        match status {
+                            VcsStatus::Dirty => dirty.push(label.clone()),
+                            VcsStatus::Untracked => untracked.push(label.clone()),
+                            VcsStatus::Clean => clean_count += 1,
+                            VcsStatus::None => no_repo_count += 1,
+                        }
+                        println!(
+                            "{}{} {} {} {}",
+                            indent,
+                            "└─".dimmed(),
+                            node.state.name.cyan(),
+                            status,
+                            if node.is_drifted() {
+                                " (drifted)".red().bold()
+                            } else {
+                                " (aligned)".dimmed()
+                            }
+                        );
+                        if node.is_drifted() {
+                            print_drift(
+                                backend.as_ref(),
+                                &node.path,
+                                node.state.expected.as_deref(),
+                                node.state.actual.as_deref(),
+                                depth,
+                            );
+                        }
+                        for child in node.children.into_iter().rev() {
+                            stack.push((child, depth + 1, label.clone()));
+                        }
+                    }
                 }
             }
 
@@ -739,9 +1809,31 @@ fn main() -> Result<()> {
             }
             println!("\n{}", "--- SCAN COMPLETE ---".green());
         }
-        Commands::Stats { query, tag, all } => {
+        Commands::Stats {
+            query,
+            tag,
+            all,
+            authors,
+            since,
+            trend,
+            diff,
+            blobs,
+        } => {
             println!("{}", "--- ECOSYSTEM ANALYTICS ---".green().bold());
 
+            // Validate report arguments before the (slow) scan.
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)?
+                .as_secs();
+            let trend_since = match trend {
+                Some(window) => Some(now.saturating_sub(history::parse_window(window)?)),
+                None => None,
+            };
+            let diff_at = match diff {
+                Some(point) => Some(history::parse_point(point, now)?),
+                None => None,
+            };
+
             let registry =
                 toad_core::ProjectRegistry::load(workspace.active_context.as_deref(), None)
                     .unwrap_or_default();
@@ -787,6 +1879,88 @@ fn main() -> Result<()> {
                 return Ok(());
             }
 
+            if *authors {
+                let since = since.as_deref().unwrap_or("90 days ago");
+                println!(
+                    "Reading local history of {} projects since {}...",
+                    matching.len(),
+                    since
+                );
+                let mut repos: Vec<_> = matching
+                    .par_iter()
+                    .filter(|p| p.path.join(".git").exists())
+                    .filter_map(|p| {
+                        authors::collect(&p.path, since)
+                            .ok()
+                            .map(|a| (p.name.clone(), a))
+                    })
+                    .filter(|(_, a)| a.commits() > 0)
+                    .collect();
+                if repos.is_empty() {
+                    println!("No commits in this window.");
+                    return Ok(());
+                }
+                repos.sort_by(|(an, a), (bn, b)| {
+                    b.commits().cmp(&a.commits()).then_with(|| an.cmp(bn))
+                });
+
+                let merged = authors::merge(repos.iter().map(|(_, a)| a));
+                let limit = if *all { merged.len() } else { 10 };
+                println!("\n{}", "TOP CONTRIBUTORS".yellow().bold());
+                for (a, projects) in merged.iter().take(limit) {
+                    println!(
+                        "{: <30} | {: >6} commits | {} {} | {} projects",
+                        a.name.bold(),
+                        a.commits,
+                        format!("+{}", a.added).green(),
+                        format!("-{}", a.removed).red(),
+                        projects
+                    );
+                }
+                if !*all && merged.len() > limit {
+                    println!(
+                        "... and {} more. Use --all to see full list.",
+                        merged.len() - limit
+                    );
+                }
+
+                println!("\n{}", "PROJECTS".yellow().bold());
+                let mut at_risk = Vec::new();
+                for (name, repo) in &repos {
+                    let bus = repo.bus_factor();
+                    let bus_label = format!("bus factor {}", bus);
+                    let bus_label = if bus == 1 {
+                        at_risk.push(name.as_str());
+                        bus_label.red().bold()
+                    } else {
+                        bus_label.green()
+                    };
+                    let top: Vec<String> = repo
+                        .authors
+                        .iter()
+                        .take(3)
+                        .map(|a| format!("{} {:.0}%", a.name, repo.share(a)))
+                        .collect();
+                    println!(
+                        "{: <20} | {: >6} commits | {} | {}",
+                        name.bold(),
+                        repo.commits(),
+                        bus_label,
+                        top.join(", ").dimmed()
+                    );
+                }
+
+                if !at_risk.is_empty() {
+                    println!(
+                        "\n{} {} projects rely on one person for most recent commits: {}",
+                        "WARN:".yellow(),
+                        at_risk.len(),
+                        at_risk.join(", ")
+                    );
+                }
+                return Ok(());
+            }
+
             println!("Analyzing {} projects...", matching.len());
             let pb = ProgressBar::new(matching.len() as u64);
             pb.set_style(
@@ -803,8 +1977,9 @@ fn main() -> Result<()> {
                     let artifact_set: std::collections::HashSet<&str> =
                         p.artifact_dirs.iter().map(|s| s.as_str()).collect();
                     let stats = calculate_project_stats(&p.path, &artifact_set);
+                    let git_fp = maintain::footprint(&p.path);
                     pb.inc(1);
-                    (p, stats)
+                    (p, stats, git_fp)
                 })
                 .collect();
 
@@ -813,25 +1988,215 @@ fn main() -> Result<()> {
             // Sort by size descending
             results.sort_by(|a, b| b.1.total_bytes.cmp(&a.1.total_bytes));
 
-            let total_ecosystem_bytes: u64 = results.iter().map(|(_, s)| s.total_bytes).sum();
-            let total_artifact_bytes: u64 = results.iter().map(|(_, s)| s.artifact_bytes).sum();
+            let history_path = history::StatsHistory::path(&workspace)?;
+            let snapshot = history::Snapshot {
+                taken_at: now,
+                projects: results
+                    .iter()
+                    .map(|(p, s, _)| history::ProjectSnapshot {
+                        name: p.name.clone(),
+                        total_bytes: s.total_bytes,
+                        artifact_bytes: s.artifact_bytes,
+                        bloat_index: s.bloat_index,
+                        tier: p.activity.to_string(),
+                    })
+                    .collect(),
+            };
+            // An unreadable history is left as it is rather than overwritten.
+            let stats_history = match history::StatsHistory::load(&history_path) {
+                Ok(mut stats_history) => {
+                    stats_history.record(snapshot);
+                    if let Err(e) = stats_history.save(&history_path) {
+                        println!("{} Could not record stats history: {}", "WARN:".yellow(), e);
+                    }
+                    stats_history
+                }
+                Err(e) => {
+                    println!(
+                        "{} Could not read stats history {:?}, this run is not recorded: {}",
+                        "WARN:".yellow(),
+                        history_path,
+                        e
+                    );
+                    history::StatsHistory::default()
+                }
+            };
+
+            let total_ecosystem_bytes: u64 = results.iter().map(|(_, s, _)| s.total_bytes).sum();
+            let total_artifact_bytes: u64 = results.iter().map(|(_, s, _)| s.artifact_bytes).sum();
+            let total_git_bytes: u64 = results
+                .iter()
+                .filter_map(|(_, _, g)| g.as_ref().map(|g| g.git_bytes))
+                .sum();
 
             println!(
-                "{} Total Usage: {} ({} Artifacts)",
+                "{} Total Usage: {} ({} Artifacts, {} .git)",
                 "■".green(),
                 format_size(total_ecosystem_bytes).bold(),
-                format_size(total_artifact_bytes).dimmed()
+                format_size(total_artifact_bytes).dimmed(),
+                format_size(total_git_bytes).dimmed()
             );
 
             let limit = if *all { results.len() } else { 10 };
             let display_count = std::cmp::min(results.len(), limit);
 
+            if let (Some(window), Some(since)) = (trend, trend_since) {
+                let series = stats_history.series(since);
+                let runs = stats_history
+                    .snapshots
+                    .iter()
+                    .filter(|s| s.taken_at >= since)
+                    .count();
+                println!(
+                    "\n{}",
+                    format!("TREND (last {}, {} snapshots)", window, runs)
+                        .yellow()
+                        .bold()
+                );
+
+                let mut rows: Vec<_> = results
+                    .iter()
+                    .filter_map(|(p, _, _)| series.get(&p.name).map(|s| (p, s)))
+                    .map(|(p, s)| {
+                        let values: Vec<u64> = s.iter().map(|(_, v)| v.total_bytes).collect();
+                        let first = values.first().copied().unwrap_or(0);
+                        let last = values.last().copied().unwrap_or(0);
+                        (p, values, first, last as i64 - first as i64)
+                    })
+                    .collect();
+                rows.sort_by_key(|(p, _, _, delta)| {
+                    (std::cmp::Reverse(delta.unsigned_abs()), p.name.clone())
+                });
+
+                for (p, values, first, delta) in rows.iter().take(display_count) {
+                    let change = history::signed_size(*delta);
+                    let change = match delta {
+                        d if *d > 0 => change.red(),
+                        d if *d < 0 => change.green(),
+                        _ => change.dimmed(),
+                    };
+                    let pct = if *first > 0 {
+                        format!("{:+.0}%", *delta as f64 * 100.0 / *first as f64)
+                    } else {
+                        String::new()
+                    };
+                    println!(
+                        "{: <20} | {: >10} | {: >12} {: >6} | {}",
+                        p.name.bold(),
+                        format_size(values.last().copied().unwrap_or(0)),
+                        change,
+                        pct.dimmed(),
+                        history::sparkline(values).cyan()
+                    );
+                }
+                if !*all && rows.len() > display_count {
+                    println!(
+                        "\n... and {} more. Use --all to see full list.",
+                        rows.len() - display_count
+                    );
+                }
+
+                let growth: i64 = rows.iter().map(|r| r.3).filter(|d| *d > 0).sum();
+                let shrink: i64 = rows.iter().map(|r| r.3).filter(|d| *d < 0).sum();
+                println!(
+                    "\nGrowth: {}  Shrink: {}  Net: {}",
+                    history::signed_size(growth).red(),
+                    history::signed_size(shrink).green(),
+                    history::signed_size(growth + shrink).bold()
+                );
+                if runs < 2 {
+                    println!(
+                        "{}",
+                        "Only this run falls in the window; trends appear as 'toad stats' keeps recording."
+                            .dimmed()
+                    );
+                }
+                return Ok(());
+            }
+
+            if let (Some(point), Some(at)) = (diff, diff_at) {
+                println!("\n{}", format!("GROWTH SINCE {}", point).yellow().bold());
+                let mut rows = Vec::new();
+                let mut new_projects = Vec::new();
+                for (p, s, _) in &results {
+                    match stats_history.at(&p.name, at) {
+                        Some(then) => rows.push((p, s, then)),
+                        None => new_projects.push(p.name.as_str()),
+                    }
+                }
+                if rows.is_empty() {
+                    let oldest = stats_history
+                        .snapshots
+                        .first()
+                        .and_then(|s| chrono::DateTime::from_timestamp(s.taken_at as i64, 0))
+                        .map(|t| {
+                            t.with_timezone(&chrono::Local)
+                                .format("%Y-%m-%d")
+                                .to_string()
+                        })
+                        .unwrap_or_else(|| "today".to_string());
+                    println!(
+                        "No stats were recorded at or before {}. The oldest snapshot is from {}.",
+                        point, oldest
+                    );
+                    return Ok(());
+                }
+                let delta = |now: u64, then: u64| now as i64 - then as i64;
+                rows.sort_by_key(|(p, s, then)| {
+                    (
+                        std::cmp::Reverse(delta(s.total_bytes, then.total_bytes)),
+                        p.name.clone(),
+                    )
+                });
+
+                for (p, s, then) in rows.iter().take(display_count) {
+                    let total = delta(s.total_bytes, then.total_bytes);
+                    let change = history::signed_size(total);
+                    let change = match total {
+                        d if d > 0 => change.red(),
+                        d if d < 0 => change.green(),
+                        _ => change.dimmed(),
+                    };
+                    let tier = p.activity.to_string();
+                    let tier = if tier == then.tier {
+                        tier.normal()
+                    } else {
+                        format!("{} → {}", then.tier, tier).yellow()
+                    };
+                    println!(
+                        "{: <20} | {: >10} → {: >10} | {: >12} | artifacts {} | {:.0}% → {:.0}% bloat | {}",
+                        p.name.bold(),
+                        format_size(then.total_bytes).dimmed(),
+                        format_size(s.total_bytes),
+                        change,
+                        history::signed_size(delta(s.artifact_bytes, then.artifact_bytes)),
+                        then.bloat_index,
+                        s.bloat_index,
+                        tier
+                    );
+                }
+                if !*all && rows.len() > display_count {
+                    println!(
+                        "\n... and {} more. Use --all to see full list.",
+                        rows.len() - display_count
+                    );
+                }
+                if !new_projects.is_empty() {
+                    println!(
+                        "\n{} {}",
+                        "Not yet recorded then:".dimmed(),
+                        new_projects.join(", ")
+                    );
+                }
+                return Ok(());
+            }
+
             println!(
                 "\n{}",
                 format!("TOP {} OFFENDERS", display_count).yellow().bold()
             );
 
-            for (p, stats) in results.iter().take(display_count) {
+            for (p, stats, _) in results.iter().take(display_count) {
                 let size_str = format_size(stats.total_bytes);
 
                 // Color coding
@@ -870,14 +2235,56 @@ fn main() -> Result<()> {
                     results.len() - 10
                 );
             }
+
+            // Largest .git directories, with the blobs that weigh their history
+            // down when asked for; finding those reads every object.
+            let mut histories: Vec<_> = results
+                .iter()
+                .filter_map(|(p, _, g)| g.as_ref().map(|g| (p, g)))
+                .collect();
+            histories.sort_by_key(|(_, g)| std::cmp::Reverse(g.git_bytes));
+            histories.truncate(display_count);
+            if !histories.is_empty() {
+                println!("\n{}", "GIT HISTORY".yellow().bold());
+                let largest: Vec<_> = histories
+                    .par_iter()
+                    .map(|(p, _)| {
+                        if *blobs {
+                            maintain::largest_blobs(&p.path, 3).unwrap_or_default()
+                        } else {
+                            Vec::new()
+                        }
+                    })
+                    .collect();
+                for ((p, g), blobs) in histories.iter().zip(largest) {
+                    println!(
+                        "{: <20} | {: >10} | {} loose objects ({}), {} packs",
+                        p.name.bold(),
+                        format_size(g.git_bytes),
+                        g.loose_objects,
+                        format_size(g.loose_bytes),
+                        g.packs
+                    );
+                    for blob in blobs {
+                        println!(
+                            "  {} {} ({})",
+                            "└─".dimmed(),
+                            blob.path,
+                            format_size(blob.size).dimmed()
+                        );
+                    }
+                }
+                if histories.iter().any(|(_, g)| g.loose_objects > 0) {
+                    println!(
+                        "\n{}",
+                        "Run 'toad ggit maintain' to pack loose objects and reclaim space."
+                            .dimmed()
+                    );
+                }
+            }
         }
         Commands::Home { path } => {
-            let mut config =
-                toad_core::GlobalConfig::load(None)?.unwrap_or_else(|| toad_core::GlobalConfig {
-                    home_pointer: PathBuf::from("."),
-                    active_context: None,
-                    project_contexts: std::collections::HashMap::new(),
-                });
+            let mut config = load_global_config()?;
 
             if let Some(new_path) = path {
                 let p = PathBuf::from(new_path);
@@ -885,10 +2292,11 @@ fn main() -> Result<()> {
                     bail!("Path does not exist: {:?}", p);
                 }
                 let abs_path = fs::canonicalize(p)?;
-                if !abs_path.join(".toad-root").exists() {
+                if !abs_path.join(ROOT_MARKER).exists() {
                     println!(
-                        "{} Path does not contain a '.toad-root' marker.",
-                        "WARNING:".yellow().bold()
+                        "{} Path does not contain a '{}' marker.",
+                        "WARNING:".yellow().bold(),
+                        ROOT_MARKER
                     );
                     print!("Initialize as a new Toad home? [y/N]: ");
                     io::stdout().flush()?;
@@ -898,8 +2306,7 @@ fn main() -> Result<()> {
                         println!("Aborted.");
                         return Ok(());
                     }
-                    let marker_content = "# Primatif Toad Workspace Root\n# This file identifies this directory as a Toad Control Plane home.\n# Do not delete this file if you want the 'toad' CLI to recognize this workspace.\n";
-                    fs::write(abs_path.join(".toad-root"), marker_content)?;
+                    ensure_root_marker(&abs_path)?;
                 }
 
                 // Register as context
@@ -914,28 +2321,20 @@ fn main() -> Result<()> {
                 };
 
                 config.home_pointer = abs_path.clone();
-                config.project_contexts.insert(
-                    name.clone(),
-                    toad_core::ProjectContext {
-                        path: abs_path.clone(),
-                        description: Some("Registered via 'toad home'".to_string()),
-                        context_type: if abs_path.join(".gitmodules").exists() {
-                            toad_core::ContextType::Hub
-                        } else {
-                            toad_core::ContextType::Generic
-                        },
-                        ai_vendors: Vec::new(),
-                        registered_at: std::time::SystemTime::now(),
-                    },
-                );
                 config.active_context = Some(name.clone());
-
-                // Create per-context storage
-                let ctx_shadows =
-                    toad_core::GlobalConfig::context_dir(&name, None)?.join("shadows");
-                fs::create_dir_all(&ctx_shadows)?;
-
-                config.save(None)?;
+                let context_type = if abs_path.join(".gitmodules").exists() {
+                    toad_core::ContextType::Hub
+                } else {
+                    toad_core::ContextType::Generic
+                };
+                register_context(
+                    &mut config,
+                    &name,
+                    &abs_path,
+                    Some("Registered via 'toad home'".to_string()),
+                    context_type,
+                    Vec::new(),
+                )?;
                 println!(
                     "{} Anchor updated and registered as context '{}' at: {:?}",
                     "SUCCESS:".green().bold(),
@@ -1845,12 +3244,7 @@ fn main() -> Result<()> {
             );
         }
         Commands::Project { subcommand } => {
-            let mut config =
-                toad_core::GlobalConfig::load(None)?.unwrap_or_else(|| toad_core::GlobalConfig {
-                    home_pointer: PathBuf::from("."),
-                    active_context: None,
-                    project_contexts: std::collections::HashMap::new(),
-                });
+            let mut config = load_global_config()?;
 
             match subcommand {
                 ProjectCommand::Register {
@@ -1885,22 +3279,14 @@ fn main() -> Result<()> {
                         Vec::new()
                     };
 
-                    let ctx = toad_core::ProjectContext {
-                        path: abs_path.clone(),
-                        description: description.clone(),
-                        context_type: detected_type,
+                    register_context(
+                        &mut config,
+                        name,
+                        &abs_path,
+                        description.clone(),
+                        detected_type,
                         ai_vendors,
-                        registered_at: std::time::SystemTime::now(),
-                    };
-
-                    config.project_contexts.insert(name.clone(), ctx);
-
-                    // Create per-context storage
-                    let ctx_shadows =
-                        toad_core::GlobalConfig::context_dir(name, None)?.join("shadows");
-                    fs::create_dir_all(&ctx_shadows)?;
-
-                    config.save(None)?;
+                    )?;
                     println!(
                         "{} Context '{}' ({}) registered at {:?}",
                         "SUCCESS:".green().bold(),
@@ -2038,15 +3424,7 @@ fn main() -> Result<()> {
                         }
                     }
 
-                    config.project_contexts.remove(name);
-
-                    // Remove per-context storage
-                    let ctx_dir = toad_core::GlobalConfig::context_dir(name, None)?;
-                    if ctx_dir.exists() {
-                        fs::remove_dir_all(&ctx_dir)?;
-                    }
-
-                    config.save(None)?;
+                    unregister_context(&mut config, name)?;
                     println!("{} Context '{}' removed.", "SUCCESS:".green().bold(), name);
                 }
                 ProjectCommand::Info { name } => {
@@ -2135,9 +3513,29 @@ fn main() -> Result<()> {
                     println!("{:<40} {:<15} BRANCH", "REPOSITORY", "STATUS");
                     println!("{:-<40} {:-<15} {:-<20}", "", "", "");
 
+                    // One read per repository, as before; in-process with libgit2.
+                    let backend = backend::select();
                     for (name, path, status) in all_repos {
-                        let branch = toad_git::branch::current_branch(&path).unwrap_or_default();
-                        println!("{:<40} {:<15} {}", name.bold(), status, branch.cyan());
+                        let branch = match backend.current_branch(&path) {
+                            Ok(Some(branch)) => branch,
+                            Ok(None) => "HEAD".to_string(),
+                            Err(_) => String::new(),
+                        };
+                        let tracking = match backend.ahead_behind(&path) {
+                            Ok(Some((0, 0))) | Ok(None) | Err(_) => String::new(),
+                            Ok(Some((ahead, 0))) => format!(" [ahead {}]", ahead),
+                            Ok(Some((0, behind))) => format!(" [behind {}]", behind),
+                            Ok(Some((ahead, behind))) => {
+                                format!(" [ahead {}, behind {}]", ahead, behind)
+                            }
+                        };
+                        println!(
+                            "{:<40} {:<15} {}{}",
+                            name.bold(),
+                            status,
+                            branch.cyan(),
+                            tracking.dimmed()
+                        );
                     }
                 }
                 GgitCommand::Commit {
@@ -2148,6 +3546,7 @@ fn main() -> Result<()> {
                     fail_fast,
                 } => {
                     println!("{}", "--- MULTI-REPO GIT COMMIT ---".blue().bold());
+                    enforce_commit_policy(&workspace, message)?;
 
                     let targets: Vec<_> = projects
                         .into_iter()
@@ -2176,135 +3575,158 @@ fn main() -> Result<()> {
                         return Ok(());
                     }
 
-                    let mut results = Vec::new();
-                    let mut submodule_failed = false;
-                    let mut submodule_changed = false;
+                    let journal_path = txn::Journal::path(&workspace)?;
+                    refuse_pending_transaction(&journal_path)?;
 
+                    // Plan: submodules first, each project after its own
+                    // submodules, then the Hub root once every submodule is done.
+                    let operation = if *cascade {
+                        "ggit commit --cascade"
+                    } else {
+                        "ggit commit"
+                    };
+                    let mut journal =
+                        txn::Journal::new(operation, txn::ResetMode::Soft, *fail_fast);
+                    let mut submodule_steps = Vec::new();
                     for p in &targets {
-                        let mut project_sub_failed = false;
-                        // 1. Commit submodules first
+                        let mut own_submodules = Vec::new();
                         for sub in &p.submodules {
                             let sub_path = workspace.root.join(&sub.path);
                             if toad_git::commit::is_dirty(&sub_path)? {
-                                println!("Committing submodule: {}", sub.name.cyan());
-                                let res = toad_git::commit::commit(&sub_path, message, &sub.name)?;
-                                if !res.success {
-                                    submodule_failed = true;
-                                    project_sub_failed = true;
-                                } else {
-                                    submodule_changed = true;
-                                }
-                                results.push(res);
-
-                                if *fail_fast && submodule_failed {
-                                    break;
-                                }
-                            }
-                        }
-
-                        if *fail_fast && submodule_failed {
-                            break;
-                        }
-
-                        // 2. Commit the project itself (Block if its own submodules failed)
-                        if project_sub_failed {
-                            println!(
-                                "{} Skipping project {} because its submodules failed.",
-                                "WARN:".yellow(),
-                                p.name.cyan()
-                            );
-                            continue;
-                        }
-
-                        if toad_git::commit::is_dirty(&p.path)? {
-                            println!("Committing project: {}", p.name.cyan());
-                            let res = toad_git::commit::commit(&p.path, message, &p.name)?;
-                            if !res.success && *fail_fast {
-                                results.push(res);
-                                break;
+                                own_submodules.push(journal.push_step(
+                                    &sub.name,
+                                    &sub_path,
+                                    txn::Action::Commit {
+                                        message: message.clone(),
+                                    },
+                                    Vec::new(),
+                                ));
                             }
-                            results.push(res);
                         }
-                    }
-
-                    // 3. Cascade to Hub Root if requested (Block if ANY submodule failed)
-                    if *cascade && submodule_changed {
-                        if submodule_failed {
-                            println!(
-                                "{} Aborting Hub Root cascade because one or more submodules failed.",
-                                "ERROR:".red().bold()
+                        // Committed submodules dirty their parent, so it gets a step either way.
+                        if !own_submodules.is_empty() || toad_git::commit::is_dirty(&p.path)? {
+                            journal.push_step(
+                                &p.name,
+                                &p.path,
+                                txn::Action::Commit {
+                                    message: message.clone(),
+                                },
+                                own_submodules.clone(),
                             );
-                        } else {
-                            let root_path = &workspace.root;
-                            if toad_git::commit::is_dirty(root_path)? {
-                                println!("Cascading commit to Hub Root...");
-                                let res = toad_git::commit::commit(root_path, message, "Hub Root")?;
-                                results.push(res);
-                            }
                         }
+                        submodule_steps.extend(own_submodules);
                     }
-
-                    // Summary
-                    println!("\n--- COMMIT SUMMARY ---");
-                    let mut any_fail = false;
-                    for res in results {
-                        let status = if res.success {
-                            "OK".green()
-                        } else {
-                            any_fail = true;
-                            "FAIL".red()
-                        };
-                        println!("{:<30} {}", res.project_name.bold(), status);
-                        if !res.success {
-                            println!("  Error: {}", res.stderr.dimmed());
-                        }
+                    if *cascade && !submodule_steps.is_empty() {
+                        journal.push_step(
+                            "Hub Root",
+                            &workspace.root,
+                            txn::Action::CascadeCommit {
+                                subject: message.clone(),
+                            },
+                            submodule_steps,
+                        );
                     }
 
-                    if any_fail {
-                        std::process::exit(1);
+                    if journal.steps.is_empty() {
+                        println!("Nothing to commit.");
+                        return Ok(());
                     }
+                    run_transaction(journal, &journal_path, "COMMIT")?;
                 }
                 GgitCommand::Push {
                     query,
                     tag,
                     fail_fast,
+                    dry_run,
                 } => {
                     println!("{}", "--- MULTI-REPO GIT PUSH ---".blue().bold());
-                    let targets: Vec<_> = projects
-                        .into_iter()
-                        .filter(|p| {
-                            let name_match = match query {
-                                Some(ref q) => p.name.to_lowercase().contains(&q.to_lowercase()),
-                                None => true,
-                            };
-                            let tag_match = match tag {
-                                Some(ref t) => {
-                                    let target = if t.starts_with('#') {
-                                        t.clone()
-                                    } else {
-                                        format!("#{}", t)
-                                    };
-                                    p.tags.contains(&target)
-                                }
-                                None => true,
-                            };
-                            name_match && tag_match
-                        })
-                        .collect();
+                    let targets = filter_projects(projects, query, tag);
 
                     if targets.is_empty() {
                         println!("No projects found matching filters.");
                         return Ok(());
                     }
 
+                    // 1. Pre-flight Check (each check fetches, so they run in parallel)
+                    println!("Running push safety checks...");
+                    let policy = policy::ContextPolicy::load(&workspace)?.push;
+                    let mut repos = Vec::new();
+                    for p in &targets {
+                        for sub in &p.submodules {
+                            repos.push((
+                                workspace.root.join(&sub.path),
+                                format!("{} > {}", p.name, sub.name),
+                                Some((p.path.clone(), PathBuf::from(&sub.path))),
+                            ));
+                        }
+                        repos.push((p.path.clone(), p.name.clone(), None));
+                    }
+                    let (preflight_results, outgoing): (Vec<_>, Vec<_>) = repos
+                        .par_iter()
+                        .map(|(path, name, parent)| {
+                            push::preflight(
+                                path,
+                                name,
+                                parent.as_ref().map(|(p, _)| p.as_path()),
+                                parent.as_ref().map(|(_, s)| s.as_path()),
+                                &policy,
+                            )
+                        })
+                        .collect::<Result<Vec<_>>>()?
+                        .into_iter()
+                        .unzip();
+
+                    for (res, outgoing) in preflight_results.iter().zip(&outgoing) {
+                        let branch = outgoing.branch.as_deref().unwrap_or("(detached)");
+                        let upstream = outgoing.upstream.as_deref().unwrap_or("(no upstream)");
+                        println!(
+                            "\n{} {} {} → {}",
+                            "»".blue(),
+                            res.project_name.bold(),
+                            branch.cyan(),
+                            upstream.dimmed()
+                        );
+                        if outgoing.commits.is_empty() {
+                            println!("  {}", "Nothing to push".dimmed());
+                        }
+                        for line in &outgoing.commits {
+                            println!("  {} {}", "└─".dimmed(), line);
+                        }
+                        for warning in &outgoing.warnings {
+                            println!("  {} {}", "WARN:".yellow(), warning);
+                        }
+                    }
+
+                    if report_preflight_failures(&preflight_results) {
+                        std::process::exit(1);
+                    }
+
+                    if *dry_run {
+                        println!("\n{}", "--- DRY RUN COMPLETE ---".green().bold());
+                        return Ok(());
+                    }
+
+                    // 2. Perform Push
+                    let up_to_date = |path: &PathBuf| {
+                        repos
+                            .iter()
+                            .zip(&outgoing)
+                            .any(|((p, _, _), out)| p == path && out.is_up_to_date())
+                    };
                     let mut results = Vec::new();
+                    let mut skipped = Vec::new();
                     let mut any_sub_failed = false;
 
+                    println!();
                     for p in targets {
                         let mut project_sub_failed = false;
                         // Push submodules first
                         for sub in p.submodules {
                             let sub_path = workspace.root.join(&sub.path);
+                            if up_to_date(&sub_path) {
+                                skipped.push(sub.name.clone());
+                                continue;
+                            }
                             println!("Pushing submodule: {}", sub.name.cyan());
                             let res = toad_git::remote::push(&sub_path, &sub.name, None, None)?;
                             if !res.success {
@@ -2332,6 +3754,10 @@ fn main() -> Result<()> {
                             continue;
                         }
 
+                        if up_to_date(&p.path) {
+                            skipped.push(p.name.clone());
+                            continue;
+                        }
                         println!("Pushing project: {}", p.name.cyan());
                         let res = toad_git::remote::push(&p.path, &p.name, None, None)?;
                         if !res.success && *fail_fast {
@@ -2353,6 +3779,9 @@ fn main() -> Result<()> {
                         };
                         println!("{:<30} {}", res.project_name.bold(), status);
                     }
+                    for name in skipped {
+                        println!("{:<30} {}", name.bold(), "UP-TO-DATE".dimmed());
+                    }
 
                     if any_fail {
                         std::process::exit(1);
@@ -2391,13 +3820,36 @@ fn main() -> Result<()> {
                         return Ok(());
                     }
 
+                    // Unreachable remotes fall back to their local mirror, if any.
+                    let mirrors = mirror::mirrors_root()?;
+                    let pull = |path: &std::path::Path, name: &str| -> Result<_> {
+                        let res = toad_git::remote::pull(path, name)?;
+                        if res.success || !mirror::is_offline(&res.stderr) {
+                            return Ok(res);
+                        }
+                        match mirror::pull_from_mirror(&mirrors, path, name) {
+                            Some(fallback) => {
+                                let fallback = fallback?;
+                                if fallback.success {
+                                    println!(
+                                        "{} Remote unreachable; pulled {} from its local mirror. Pull again online to reconcile.",
+                                        "WARN:".yellow(),
+                                        name.cyan()
+                                    );
+                                }
+                                Ok(fallback)
+                            }
+                            None => Ok(res),
+                        }
+                    };
+
                     let mut results = Vec::new();
                     let mut any_failed = false;
 
                     for p in targets {
                         // Pull project first
                         println!("Pulling project: {}", p.name.cyan());
-                        let res = toad_git::remote::pull(&p.path, &p.name)?;
+                        let res = pull(&p.path, &p.name)?;
                         let project_failed = !res.success;
                         if project_failed {
                             any_failed = true;
@@ -2420,7 +3872,7 @@ fn main() -> Result<()> {
                         for sub in p.submodules {
                             let sub_path = workspace.root.join(&sub.path);
                             println!("Pulling submodule: {}", sub.name.cyan());
-                            let res = toad_git::remote::pull(&sub_path, &sub.name)?;
+                            let res = pull(&sub_path, &sub.name)?;
                             if !res.success {
                                 any_failed = true;
                             }
@@ -2571,6 +4023,9 @@ fn main() -> Result<()> {
                         return Ok(());
                     }
 
+                    let journal_path = txn::Journal::path(&workspace)?;
+                    refuse_pending_transaction(&journal_path)?;
+
                     let mut preflight_results = Vec::new();
                     let mut any_issues = false;
 
@@ -2601,15 +4056,7 @@ fn main() -> Result<()> {
                     }
 
                     if any_issues && !*force {
-                        println!("\n{} Safety checks failed:", "ERROR:".red().bold());
-                        for res in preflight_results {
-                            if !res.issues.is_empty() {
-                                println!("  » {}:", res.project_name.cyan());
-                                for issue in res.issues {
-                                    println!("    - {}", issue.yellow());
-                                }
-                            }
-                        }
+                        report_preflight_failures(&preflight_results);
                         println!(
                             "\nUse {} to skip safety checks (Dangerous).",
                             "--force".bold()
@@ -2619,30 +4066,128 @@ fn main() -> Result<()> {
 
                     // 2. Perform Sync
                     println!("\nSynchronizing repositories...");
-                    let mut results = Vec::new();
+                    let mut journal = txn::Journal::new("ggit sync", txn::ResetMode::Keep, false);
                     for p in targets {
-                        // Pull project
-                        println!("Updating project: {}", p.name.cyan());
-                        let res = toad_git::remote::pull(&p.path, &p.name)?;
-                        results.push(res);
-
-                        // Sync submodules
+                        journal.push_step(&p.name, &p.path, txn::Action::Pull, Vec::new());
                         if !p.submodules.is_empty() {
-                            println!("Aligning submodules for {}...", p.name.cyan());
-                            // git submodule update --init --recursive
-                            let sub_res = toad_git::run_git(
-                                &p.path,
-                                &["submodule", "update", "--init", "--recursive"],
+                            // The update moves the submodules, so their heads are recorded too.
+                            for sub in &p.submodules {
+                                journal.record_head(
+                                    &format!("{} > {}", p.name, sub.name),
+                                    &workspace.root.join(&sub.path),
+                                );
+                            }
+                            journal.push_step(
                                 &format!("{} (submodules)", p.name),
-                            )?;
-                            results.push(sub_res);
+                                &p.path,
+                                txn::Action::SubmoduleUpdate,
+                                Vec::new(),
+                            );
                         }
                     }
+                    run_transaction(journal, &journal_path, "SYNC")?;
+                }
+                GgitCommand::Resume => {
+                    let journal_path = txn::Journal::path(&workspace)?;
+                    let Some(journal) = txn::Journal::load(&journal_path)? else {
+                        println!("No interrupted operation to resume.");
+                        return Ok(());
+                    };
+                    println!("{}", "--- RESUME TRANSACTION ---".blue().bold());
+                    let remaining = journal
+                        .steps
+                        .iter()
+                        .filter(|s| s.state != txn::StepState::Done)
+                        .count();
+                    println!(
+                        "Resuming '{}' ({} of {} steps remaining)...",
+                        journal.operation.cyan(),
+                        remaining,
+                        journal.steps.len()
+                    );
+                    let title = if journal.operation.starts_with("ggit sync") {
+                        "SYNC"
+                    } else if journal.operation.starts_with("ggit release") {
+                        "RELEASE"
+                    } else {
+                        "COMMIT"
+                    };
+                    run_transaction(journal, &journal_path, title)?;
+                }
+                GgitCommand::Rollback { yes, dry_run } => {
+                    let journal_path = txn::Journal::path(&workspace)?;
+                    let Some(journal) = txn::Journal::load(&journal_path)? else {
+                        println!("No interrupted operation to roll back.");
+                        return Ok(());
+                    };
+                    println!("{}", "--- ROLLBACK PREVIEW ---".blue().bold());
+                    println!("Operation: {}", journal.operation.cyan());
+                    let moved = txn::moved_heads(&journal);
+                    for (head, current) in &moved {
+                        println!(
+                            "  {} {:<40} {} -> {}",
+                            "»".blue(),
+                            head.name,
+                            current.as_deref().map(git::short).unwrap_or("?"),
+                            head.head.as_deref().map(git::short).unwrap_or("?")
+                        );
+                    }
+                    let tags = txn::created_tags(&journal);
+                    for (step, name) in &tags {
+                        println!(
+                            "  {} {:<40} delete tag {}",
+                            "»".blue(),
+                            step.name,
+                            name.yellow()
+                        );
+                    }
+                    if moved.is_empty() && tags.is_empty() {
+                        println!("Every repository is still at its recorded HEAD.");
+                    }
 
-                    // Summary
-                    println!("\n--- SYNC SUMMARY ---");
+                    if *dry_run {
+                        println!("\n{}", "--- DRY RUN COMPLETE ---".yellow().bold());
+                        return Ok(());
+                    }
+
+                    if (!moved.is_empty() || !tags.is_empty()) && !*yes {
+                        let mode = match journal.rollback {
+                            txn::ResetMode::Soft => "committed changes stay staged",
+                            txn::ResetMode::Keep => "local changes are kept",
+                        };
+                        print!(
+                            "\nReset {} repositories ({}) and delete {} tag(s)? [y/N]: ",
+                            moved.len(),
+                            mode,
+                            tags.len()
+                        );
+                        io::stdout().flush()?;
+                        let mut input = String::new();
+                        io::stdin().read_line(&mut input)?;
+                        if !input.trim().to_lowercase().starts_with('y') {
+                            println!("Aborted.");
+                            return Ok(());
+                        }
+                    }
+
+                    // Undo in reverse order so a Hub root is reset before its submodules.
                     let mut any_fail = false;
-                    for res in results {
+                    println!("\n--- ROLLBACK SUMMARY ---");
+                    for (step, name) in &tags {
+                        let res = toad_git::run_git(&step.path, &["tag", "-d", name], &step.name)?;
+                        let status = if res.success {
+                            "OK".green()
+                        } else {
+                            any_fail = true;
+                            "FAIL".red()
+                        };
+                        println!("{:<40} {}", res.project_name.bold(), status);
+                        if !res.success {
+                            println!("  Error: {}", res.stderr.dimmed());
+                        }
+                    }
+                    for (head, _) in moved.iter().rev() {
+                        let res = txn::reset(head, journal.rollback)?;
                         let status = if res.success {
                             "OK".green()
                         } else {
@@ -2656,8 +4201,14 @@ fn main() -> Result<()> {
                     }
 
                     if any_fail {
+                        println!(
+                            "\n{} The journal was kept at {:?}; fix the failures and re-run.",
+                            "WARN:".yellow(),
+                            journal_path
+                        );
                         std::process::exit(1);
                     }
+                    fs::remove_file(&journal_path)?;
                 }
                 GgitCommand::Branches { query, tag, all } => {
                     println!("{}", "--- MULTI-REPO BRANCH LIST ---".green().bold());
@@ -2726,53 +4277,895 @@ fn main() -> Result<()> {
                         }
                     }
                 }
-                GgitCommand::Align { query, tag } => {
-                    println!("{}", "--- SUBMODULE ALIGNMENT ---".blue().bold());
-
-                    let targets: Vec<_> = projects
-                        .into_iter()
-                        .filter(|p| {
-                            let name_match = match query {
-                                Some(ref q) => p.name.to_lowercase().contains(&q.to_lowercase()),
-                                None => true,
-                            };
-                            let tag_match = match tag {
-                                Some(ref t) => {
-                                    let target = if t.starts_with('#') {
-                                        t.clone()
-                                    } else {
-                                        format!("#{}", t)
-                                    };
-                                    p.tags.contains(&target)
-                                }
-                                None => true,
-                            };
-                            name_match && tag_match
-                        })
-                        .collect();
-
+                GgitCommand::PruneBranches {
+                    query,
+                    tag,
+                    stale,
+                    remote,
+                    yes,
+                    dry_run,
+                } => {
+                    println!("{}", "--- BRANCH PRUNE PREVIEW ---".blue().bold());
+                    let targets = filter_projects(projects, query, tag);
                     if targets.is_empty() {
                         println!("No projects found matching filters.");
                         return Ok(());
                     }
 
-                    let mut results = Vec::new();
+                    let mut repos = Vec::new();
                     for p in targets {
-                        if p.submodules.is_empty() {
-                            continue;
+                        repos.push((p.name.clone(), p.path.clone()));
+                        for sub in p.submodules {
+                            repos.push((
+                                format!("{} > {}", p.name, sub.name),
+                                workspace.root.join(&sub.path),
+                            ));
                         }
+                    }
 
-                        println!("Aligning submodules for {}...", p.name.cyan());
-                        for sub in p.submodules {
-                            let res =
-                                toad_git::align::align_submodule(&p.path, &sub.path, &sub.name)?;
-                            results.push(res);
+                    if *remote {
+                        for (_, path) in &repos {
+                            let _ = git::probe(path, &["fetch", "--quiet", "--prune"]);
                         }
                     }
 
-                    // Summary
-                    println!("\n--- ALIGNMENT SUMMARY ---");
-                    for res in results {
+                    let policy = policy::ContextPolicy::load(&workspace)?;
+                    let now = std::time::SystemTime::now()
+                        .duration_since(std::time::UNIX_EPOCH)?
+                        .as_secs();
+                    let mut plans = Vec::new();
+                    for (name, path) in repos {
+                        let plan = prune::plan(&path, &policy, *stale, *remote, now);
+                        if !plan.candidates.is_empty() {
+                            plans.push((name, path, plan));
+                        }
+                    }
+
+                    if plans.is_empty() {
+                        println!("No merged or stale branches to prune.");
+                        return Ok(());
+                    }
+
+                    let mut total = 0;
+                    for (name, _, plan) in &plans {
+                        println!(
+                            "\n{} {} (default: {})",
+                            "»".blue(),
+                            name.bold(),
+                            plan.default_branch.as_deref().unwrap_or_default().cyan()
+                        );
+                        for c in &plan.candidates {
+                            let reason = match c.reason {
+                                prune::Reason::Merged => "merged".green(),
+                                prune::Reason::Stale(days) => {
+                                    format!("stale ({} days)", days).yellow()
+                                }
+                            };
+                            let scope = if c.remote.is_some() {
+                                "remote".red()
+                            } else {
+                                "local".dimmed()
+                            };
+                            println!(
+                                "  {} {:<8} {:<18} {}",
+                                "└─".dimmed(),
+                                scope,
+                                reason,
+                                c.display_name()
+                            );
+                            total += 1;
+                        }
+                    }
+
+                    if *dry_run {
+                        println!("\n{}", "--- DRY RUN COMPLETE ---".green().bold());
+                        return Ok(());
+                    }
+
+                    if !*yes {
+                        print!(
+                            "\nDelete {} branches across {} repositories? [y/N]: ",
+                            total,
+                            plans.len()
+                        );
+                        io::stdout().flush()?;
+                        let mut input = String::new();
+                        io::stdin().read_line(&mut input)?;
+                        if !input.trim().to_lowercase().starts_with('y') {
+                            println!("Aborted.");
+                            return Ok(());
+                        }
+                    }
+
+                    let mut results = Vec::new();
+                    for (name, path, plan) in &plans {
+                        println!("Pruning {}...", name.cyan());
+                        results.extend(prune::apply(path, name, plan)?);
+                    }
+
+                    println!("\n--- PRUNE SUMMARY ---");
+                    let mut any_fail = false;
+                    for res in &results {
+                        let status = if res.success {
+                            "OK".green()
+                        } else {
+                            any_fail = true;
+                            "FAIL".red()
+                        };
+                        println!("{:<40} {}", res.project_name.bold(), status);
+                        if !res.success {
+                            println!("  Error: {}", res.stderr.trim().dimmed());
+                        }
+                    }
+
+                    let restores: Vec<String> = plans
+                        .iter()
+                        .flat_map(|(_, path, plan)| {
+                            plan.candidates
+                                .iter()
+                                .filter(|c| {
+                                    c.backup_ref()
+                                        .is_some_and(|backup| git::ref_exists(path, &backup))
+                                })
+                                .filter_map(|c| c.restore_command(path))
+                        })
+                        .collect();
+                    if !restores.is_empty() {
+                        println!(
+                            "\nStale branches were backed up under {}/. To restore one:",
+                            prune::BACKUP_PREFIX
+                        );
+                        for cmd in restores {
+                            println!("  {}", cmd.dimmed());
+                        }
+                    }
+                    if any_fail {
+                        std::process::exit(1);
+                    }
+                }
+                GgitCommand::Maintain {
+                    query,
+                    tag,
+                    dry_run,
+                } => {
+                    println!("{}", "--- REPOSITORY MAINTENANCE ---".blue().bold());
+                    let targets = filter_projects(projects, query, tag);
+
+                    let mut repos = Vec::new();
+                    if workspace.root.join(".gitmodules").exists()
+                        && !targets.iter().any(|p| p.path == workspace.root)
+                    {
+                        repos.push(("Hub Root".to_string(), workspace.root.clone()));
+                    }
+                    for p in targets {
+                        repos.push((p.name.clone(), p.path.clone()));
+                        for sub in p.submodules {
+                            repos.push((
+                                format!("{} > {}", p.name, sub.name),
+                                workspace.root.join(&sub.path),
+                            ));
+                        }
+                    }
+                    repos.retain(|(_, path)| path.join(".git").exists());
+                    if repos.is_empty() {
+                        println!("No repositories found matching filters.");
+                        return Ok(());
+                    }
+
+                    if *dry_run {
+                        let mut sized: Vec<_> = repos
+                            .par_iter()
+                            .filter_map(|(name, path)| {
+                                maintain::footprint(path).map(|fp| (name, fp))
+                            })
+                            .collect();
+                        sized.sort_by_key(|(_, fp)| std::cmp::Reverse(fp.git_bytes));
+                        for (name, fp) in &sized {
+                            println!(
+                                "  {} {:<30} {:>10}  {} loose ({}), {} packs",
+                                "»".blue(),
+                                name.bold(),
+                                format_size(fp.git_bytes),
+                                fp.loose_objects,
+                                format_size(fp.loose_bytes).dimmed(),
+                                fp.packs
+                            );
+                        }
+                        println!("\n{}", "--- DRY RUN COMPLETE ---".yellow().bold());
+                        return Ok(());
+                    }
+
+                    println!("Maintaining {} repositories...", repos.len());
+                    let pb = ProgressBar::new(repos.len() as u64);
+                    pb.set_style(
+                        ProgressStyle::default_bar()
+                            .template("{spinner:.green} [{elapsed_precise}] [{bar:40.green/black}] {pos}/{len}")?
+                            .progress_chars("■-"),
+                    );
+                    let reports: Vec<_> = repos
+                        .par_iter()
+                        .map(|(name, path)| {
+                            let report = maintain::run(path, name);
+                            pb.inc(1);
+                            (name, report)
+                        })
+                        .collect();
+                    pb.finish_and_clear();
+
+                    println!("\n--- MAINTENANCE SUMMARY ---");
+                    let mut any_fail = false;
+                    let mut reclaimed = 0;
+                    for (name, report) in &reports {
+                        match report {
+                            Ok(report) if report.success() => {
+                                reclaimed += report.reclaimed();
+                                println!(
+                                    "{:<40} {}  {} reclaimed ({} -> {})",
+                                    name.bold(),
+                                    "OK".green(),
+                                    format_size(report.reclaimed()).bold(),
+                                    format_size(report.before).dimmed(),
+                                    format_size(report.after).dimmed()
+                                );
+                            }
+                            Ok(report) => {
+                                any_fail = true;
+                                println!("{:<40} {}", name.bold(), "FAIL".red());
+                                if let Some(res) = report.results.iter().find(|r| !r.success) {
+                                    println!(
+                                        "  Error: {}: {}",
+                                        res.project_name,
+                                        res.stderr.trim().dimmed()
+                                    );
+                                }
+                            }
+                            Err(e) => {
+                                any_fail = true;
+                                println!("{:<40} {}", name.bold(), "FAIL".red());
+                                println!("  Error: {}", e.to_string().dimmed());
+                            }
+                        }
+                    }
+                    println!(
+                        "\n{} Reclaimed {} across {} repositories.",
+                        "■".green(),
+                        format_size(reclaimed).bold(),
+                        reports.len()
+                    );
+                    if any_fail {
+                        std::process::exit(1);
+                    }
+                }
+                GgitCommand::Worktree { subcommand } => {
+                    let base_name = workspace
+                        .active_context
+                        .clone()
+                        .or_else(|| {
+                            workspace
+                                .root
+                                .file_name()
+                                .map(|n| n.to_string_lossy().to_string())
+                        })
+                        .unwrap_or_else(|| "toad".to_string());
+                    match subcommand {
+                        WorktreeCommand::Add {
+                            branch,
+                            query,
+                            tag,
+                            create,
+                            dry_run,
+                        } => {
+                            println!("{}", "--- WORKTREE ADD ---".blue().bold());
+                            let targets = filter_projects(projects, query, tag);
+                            if targets.is_empty() {
+                                println!("No projects found matching filters.");
+                                return Ok(());
+                            }
+                            let tree = worktree::tree_root(&workspace.root, branch);
+                            let ctx_name = worktree::context_name(&base_name, branch);
+
+                            let mut plan = Vec::new();
+                            for p in &targets {
+                                let target = tree.join("projects").join(&p.name);
+                                let source = worktree::source(&p.path, branch, *create);
+                                let label = match &source {
+                                    _ if target.exists() => "already exists".dimmed(),
+                                    Some(worktree::Source::Local) => "local branch".normal(),
+                                    Some(worktree::Source::Remote(r)) => {
+                                        format!("new, tracking {}/{}", r, branch).normal()
+                                    }
+                                    Some(worktree::Source::NewFromHead) => {
+                                        "new branch from HEAD".normal()
+                                    }
+                                    None => "branch not found (skipped)".yellow(),
+                                };
+                                println!("  {} {:<30} {}", "»".blue(), p.name.bold(), label);
+                                if let (Some(source), false) = (source, target.exists()) {
+                                    plan.push((p, target, source));
+                                }
+                            }
+                            println!("\nTree: {:?}", tree);
+
+                            if *dry_run {
+                                println!("\n{}", "--- DRY RUN COMPLETE ---".yellow().bold());
+                                return Ok(());
+                            }
+                            if plan.is_empty() {
+                                println!("Nothing to add. Use --create to branch from HEAD.");
+                                return Ok(());
+                            }
+
+                            fs::create_dir_all(tree.join("projects"))?;
+                            worktree::exclude_from(&workspace.root)?;
+                            ensure_root_marker(&tree)?;
+
+                            let rewrites = mirror::rewrite_args(&mirror::mirrors_root()?);
+                            let mut any_fail = false;
+                            println!("\n--- WORKTREE SUMMARY ---");
+                            for (p, target, source) in &plan {
+                                for res in worktree::add(
+                                    &p.path, &p.name, target, branch, source, &rewrites,
+                                )? {
+                                    let status = if res.success {
+                                        "OK".green()
+                                    } else {
+                                        any_fail = true;
+                                        "FAIL".red()
+                                    };
+                                    println!("{:<40} {}", res.project_name.bold(), status);
+                                    if !res.success {
+                                        println!("  Error: {}", res.stderr.dimmed());
+                                    }
+                                }
+                            }
+
+                            let mut config = load_global_config()?;
+                            if !config.project_contexts.contains_key(&ctx_name) {
+                                register_context(
+                                    &mut config,
+                                    &ctx_name,
+                                    &tree,
+                                    Some(format!(
+                                        "Temporary worktrees for '{}' (remove with `toad ggit worktree remove {}`)",
+                                        branch, branch
+                                    )),
+                                    toad_core::ContextType::Pond,
+                                    Vec::new(),
+                                )?;
+                            }
+                            println!(
+                                "\n{} Context '{}' registered at {:?}. Switch with `toad project switch {}`.",
+                                "SUCCESS:".green().bold(),
+                                ctx_name,
+                                tree,
+                                ctx_name
+                            );
+
+                            if any_fail {
+                                std::process::exit(1);
+                            }
+                        }
+                        WorktreeCommand::Remove {
+                            branch,
+                            force,
+                            unlock,
+                            yes,
+                            dry_run,
+                        } => {
+                            println!("{}", "--- WORKTREE REMOVE ---".blue().bold());
+                            let tree = worktree::tree_root(&workspace.root, branch);
+                            let ctx_name = worktree::context_name(&base_name, branch);
+                            let linked = worktree::list(&tree)?;
+
+                            let mut dirty = 0;
+                            let mut locked = 0;
+                            for wt in &linked {
+                                let mut notes = Vec::new();
+                                if !wt.changes.is_empty() {
+                                    dirty += 1;
+                                    notes.push(format!(
+                                        "{} uncommitted change(s)",
+                                        wt.changes.len()
+                                    ));
+                                }
+                                match wt.locked.as_deref() {
+                                    Some("") => notes.push("locked".to_string()),
+                                    Some(reason) => notes.push(format!("locked: {}", reason)),
+                                    None => {}
+                                }
+                                if wt.locked.is_some() {
+                                    locked += 1;
+                                }
+                                let state = if notes.is_empty() {
+                                    "clean".dimmed()
+                                } else {
+                                    notes.join(", ").yellow()
+                                };
+                                println!("  {} {:<30} {}", "»".blue(), wt.name.bold(), state);
+                                for line in &wt.changes {
+                                    println!("    - {}", line);
+                                }
+                            }
+                            if linked.is_empty() {
+                                println!("No worktrees found in {:?}.", tree);
+                            }
+
+                            if dirty > 0 && !*force {
+                                println!(
+                                    "\n{} {} worktree(s) have uncommitted work. Commit it, or re-run with {} to discard it.",
+                                    "ERROR:".red().bold(),
+                                    dirty,
+                                    "--force".bold()
+                                );
+                                std::process::exit(1);
+                            }
+                            if locked > 0 && !*unlock {
+                                println!(
+                                    "\n{} {} worktree(s) are locked. Unlock them with `git worktree unlock`, or re-run with {} to remove them anyway.",
+                                    "ERROR:".red().bold(),
+                                    locked,
+                                    "--unlock".bold()
+                                );
+                                std::process::exit(1);
+                            }
+
+                            let mut config = toad_core::GlobalConfig::load(None)?;
+                            if let Some(cfg) = &config {
+                                if cfg.active_context.as_deref() == Some(ctx_name.as_str()) {
+                                    println!(
+                                        "\n{} '{}' is the active context; switch to another one first.",
+                                        "ERROR:".red().bold(),
+                                        ctx_name
+                                    );
+                                    std::process::exit(1);
+                                }
+                            }
+
+                            if *dry_run {
+                                println!("\n{}", "--- DRY RUN COMPLETE ---".yellow().bold());
+                                return Ok(());
+                            }
+
+                            if !linked.is_empty() && !*yes {
+                                print!(
+                                    "\nRemove {} worktree(s) for '{}'? [y/N]: ",
+                                    linked.len(),
+                                    branch
+                                );
+                                io::stdout().flush()?;
+                                let mut input = String::new();
+                                io::stdin().read_line(&mut input)?;
+                                if !input.trim().to_lowercase().starts_with('y') {
+                                    println!("Aborted.");
+                                    return Ok(());
+                                }
+                            }
+
+                            let mut any_fail = false;
+                            println!("\n--- WORKTREE SUMMARY ---");
+                            for wt in &linked {
+                                let res = worktree::remove(wt, *unlock)?;
+                                let status = if res.success {
+                                    "OK".green()
+                                } else {
+                                    any_fail = true;
+                                    "FAIL".red()
+                                };
+                                println!("{:<40} {}", res.project_name.bold(), status);
+                                if !res.success {
+                                    println!("  Error: {}", res.stderr.dimmed());
+                                }
+                            }
+                            if any_fail {
+                                std::process::exit(1);
+                            }
+
+                            worktree::remove_tree(&tree)?;
+                            if let Some(cfg) = config.as_mut() {
+                                if unregister_context(cfg, &ctx_name)? {
+                                    println!("Context '{}' unregistered.", ctx_name);
+                                }
+                            }
+                        }
+                    }
+                }
+                GgitCommand::Remote {
+                    subcommand,
+                    query,
+                    tag,
+                } => {
+                    let targets = filter_projects(projects, query, tag);
+                    if targets.is_empty() {
+                        println!("No projects found matching filters.");
+                        return Ok(());
+                    }
+
+                    // (display name, path, short name used for `{name}` templates)
+                    let mut repos = Vec::new();
+                    for p in targets {
+                        repos.push((p.name.clone(), p.path.clone(), p.name.clone()));
+                        for sub in p.submodules {
+                            repos.push((
+                                format!("{} > {}", p.name, sub.name),
+                                workspace.root.join(&sub.path),
+                                sub.name.clone(),
+                            ));
+                        }
+                    }
+                    if workspace.root.join(".gitmodules").exists()
+                        && !repos.iter().any(|(_, path, _)| *path == workspace.root)
+                    {
+                        let hub_name = workspace
+                            .root
+                            .file_name()
+                            .and_then(|n| n.to_str())
+                            .unwrap_or("hub")
+                            .to_string();
+                        repos.push(("Hub Root".to_string(), workspace.root.clone(), hub_name));
+                    }
+
+                    match subcommand {
+                        RemoteCommand::List => {
+                            println!("{}", "--- MULTI-REPO REMOTES ---".green().bold());
+                            println!("{:<40} {:<12} URL", "REPOSITORY", "REMOTE");
+                            println!("{:-<40} {:-<12} {:-<30}", "", "", "");
+                            for (name, path, _) in &repos {
+                                let entries = remotes::list(path);
+                                if entries.is_empty() {
+                                    println!("{:<40} {}", name.bold(), "(no remotes)".dimmed());
+                                }
+                                for entry in entries {
+                                    println!(
+                                        "{:<40} {:<12} {}",
+                                        name.bold(),
+                                        entry.name.cyan(),
+                                        entry.url
+                                    );
+                                }
+                            }
+                        }
+                        RemoteCommand::SetUrl {
+                            from,
+                            to,
+                            remote,
+                            yes,
+                            dry_run,
+                        } => {
+                            println!("{}", "--- REMOTE URL REWRITE ---".blue().bold());
+                            let changes: Vec<_> = repos
+                                .iter()
+                                .flat_map(|(name, path, _)| {
+                                    remotes::plan_set_url(name, path, from, to, remote.as_deref())
+                                })
+                                .collect();
+                            if changes.is_empty() {
+                                println!("No URLs contain '{}'.", from);
+                                return Ok(());
+                            }
+
+                            println!("{:<40} {:<24} URL", "REPOSITORY", "TARGET");
+                            println!("{:-<40} {:-<24} {:-<30}", "", "", "");
+                            for c in &changes {
+                                println!(
+                                    "{:<40} {:<24} {}",
+                                    c.repo.bold(),
+                                    c.target_label().cyan(),
+                                    c.old.dimmed()
+                                );
+                                println!("{:<40} {:<24} {} {}", "", "", "→".green(), c.new.green());
+                            }
+
+                            if *dry_run {
+                                println!("\n{}", "--- DRY RUN COMPLETE ---".green().bold());
+                                return Ok(());
+                            }
+                            if !*yes {
+                                print!("\nRewrite {} URLs? [y/N]: ", changes.len());
+                                io::stdout().flush()?;
+                                let mut input = String::new();
+                                io::stdin().read_line(&mut input)?;
+                                if !input.trim().to_lowercase().starts_with('y') {
+                                    println!("Aborted.");
+                                    return Ok(());
+                                }
+                            }
+
+                            let mut results = Vec::new();
+                            for c in &changes {
+                                results.push(remotes::apply(c)?);
+                            }
+                            // Re-sync submodule config from the rewritten .gitmodules files.
+                            let mut synced = Vec::new();
+                            for c in &changes {
+                                if matches!(c.target, remotes::UrlTarget::Submodule(_))
+                                    && !synced.contains(&c.path)
+                                {
+                                    println!("Syncing submodule URLs in {}...", c.repo.cyan());
+                                    results.push(remotes::sync_submodules(&c.path, &c.repo)?);
+                                    synced.push(c.path.clone());
+                                }
+                            }
+
+                            println!("\n--- REMOTE SUMMARY ---");
+                            let mut any_fail = false;
+                            for res in &results {
+                                let status = if res.success {
+                                    "OK".green()
+                                } else {
+                                    any_fail = true;
+                                    "FAIL".red()
+                                };
+                                println!("{:<40} {}", res.project_name.bold(), status);
+                                if !res.success {
+                                    println!("  Error: {}", res.stderr.trim().dimmed());
+                                }
+                            }
+                            if !synced.is_empty() {
+                                println!(
+                                    "\n{} .gitmodules changed in {} repositories; commit them to share the new URLs.",
+                                    "NOTE:".yellow().bold(),
+                                    synced.len()
+                                );
+                            }
+                            if any_fail {
+                                std::process::exit(1);
+                            }
+                        }
+                        RemoteCommand::Rename { old, new, dry_run } => {
+                            println!("{}", "--- REMOTE RENAME ---".blue().bold());
+                            let mut results = Vec::new();
+                            for (name, path, _) in &repos {
+                                let existing = remotes::list(path);
+                                if !existing.iter().any(|r| r.name == *old) {
+                                    continue;
+                                }
+                                if existing.iter().any(|r| r.name == *new) {
+                                    println!(
+                                        "{} {} already has a remote named '{}', skipping.",
+                                        "WARN:".yellow(),
+                                        name.cyan(),
+                                        new
+                                    );
+                                    continue;
+                                }
+                                println!("{:<40} {} → {}", name.bold(), old, new.green());
+                                if !*dry_run {
+                                    results.push(toad_git::run_git(
+                                        path,
+                                        &["remote", "rename", old, new],
+                                        name,
+                                    )?);
+                                }
+                            }
+                            if *dry_run {
+                                println!("\n{}", "--- DRY RUN COMPLETE ---".green().bold());
+                                return Ok(());
+                            }
+                            if results.iter().any(|r| !r.success) {
+                                for res in results.iter().filter(|r| !r.success) {
+                                    println!(
+                                        "{} {}: {}",
+                                        "FAIL".red(),
+                                        res.project_name.bold(),
+                                        res.stderr.trim().dimmed()
+                                    );
+                                }
+                                std::process::exit(1);
+                            }
+                            println!(
+                                "\n{} Renamed '{}' in {} repositories.",
+                                "SUCCESS:".green().bold(),
+                                old,
+                                results.len()
+                            );
+                        }
+                        RemoteCommand::Add {
+                            name: remote_name,
+                            url,
+                            dry_run,
+                        } => {
+                            println!("{}", "--- REMOTE ADD ---".blue().bold());
+                            let mut results = Vec::new();
+                            for (name, path, short) in &repos {
+                                if remotes::list(path).iter().any(|r| r.name == *remote_name) {
+                                    println!(
+                                        "{:<40} {}",
+                                        name.bold(),
+                                        format!("'{}' exists, skipping", remote_name).dimmed()
+                                    );
+                                    continue;
+                                }
+                                let expanded = remotes::expand_url(url, short);
+                                println!("{:<40} {} {}", name.bold(), remote_name.cyan(), expanded);
+                                if !*dry_run {
+                                    results.push(toad_git::run_git(
+                                        path,
+                                        &["remote", "add", remote_name, &expanded],
+                                        name,
+                                    )?);
+                                }
+                            }
+                            if *dry_run {
+                                println!("\n{}", "--- DRY RUN COMPLETE ---".green().bold());
+                                return Ok(());
+                            }
+                            if results.iter().any(|r| !r.success) {
+                                for res in results.iter().filter(|r| !r.success) {
+                                    println!(
+                                        "{} {}: {}",
+                                        "FAIL".red(),
+                                        res.project_name.bold(),
+                                        res.stderr.trim().dimmed()
+                                    );
+                                }
+                                std::process::exit(1);
+                            }
+                            println!(
+                                "\n{} Added '{}' to {} repositories.",
+                                "SUCCESS:".green().bold(),
+                                remote_name,
+                                results.len()
+                            );
+                        }
+                    }
+                }
+                GgitCommand::Align {
+                    query,
+                    tag,
+                    dry_run,
+                    force,
+                } => {
+                    println!("{}", "--- SUBMODULE ALIGNMENT ---".blue().bold());
+
+                    let targets: Vec<_> = projects
+                        .into_iter()
+                        .filter(|p| {
+                            let name_match = match query {
+                                Some(ref q) => p.name.to_lowercase().contains(&q.to_lowercase()),
+                                None => true,
+                            };
+                            let tag_match = match tag {
+                                Some(ref t) => {
+                                    let target = if t.starts_with('#') {
+                                        t.clone()
+                                    } else {
+                                        format!("#{}", t)
+                                    };
+                                    p.tags.contains(&target)
+                                }
+                                None => true,
+                            };
+                            name_match && tag_match
+                        })
+                        .collect();
+
+                    if targets.is_empty() {
+                        println!("No projects found matching filters.");
+                        return Ok(());
+                    }
+
+                    let checks: Vec<(ProjectDetail, Vec<(SubmoduleDetail, align::AlignCheck)>)> =
+                        targets
+                            .into_iter()
+                            .filter(|p| !p.submodules.is_empty())
+                            .map(|p| {
+                                let subs = p
+                                    .submodules
+                                    .iter()
+                                    .map(|sub| {
+                                        let check = align::inspect(
+                                            &sub.name,
+                                            &workspace.root.join(&sub.path),
+                                            sub.expected_commit.as_deref(),
+                                        );
+                                        (sub.clone(), check)
+                                    })
+                                    .collect();
+                                (p, subs)
+                            })
+                            .collect();
+
+                    let mut at_risk = 0;
+                    for (p, subs) in &checks {
+                        println!("{} {}", "»".blue(), p.name.bold());
+                        for (_, check) in subs {
+                            let target = check
+                                .expected
+                                .as_deref()
+                                .map(git::short)
+                                .unwrap_or("(unrecorded)");
+                            let action = match &check.actual {
+                                None => format!("initialize at {}", target),
+                                Some(_) if check.is_aligned() => "already aligned".to_string(),
+                                Some(actual) => {
+                                    format!(
+                                        "checkout {} (currently {})",
+                                        target,
+                                        git::short(actual)
+                                    )
+                                }
+                            };
+                            println!("  {} {}: {}", "└─".dimmed(), check.name.cyan(), action);
+                            if !check.unreachable.is_empty() {
+                                println!(
+                                    "      {} {} commit(s) would become unreachable:",
+                                    "!".red().bold(),
+                                    check.unreachable.len()
+                                );
+                                for line in &check.unreachable {
+                                    println!("        - {}", line);
+                                }
+                            }
+                            if !check.uncommitted.is_empty() {
+                                println!(
+                                    "      {} {} uncommitted change(s) would be discarded:",
+                                    "!".red().bold(),
+                                    check.uncommitted.len()
+                                );
+                                for line in &check.uncommitted {
+                                    println!("        - {}", line);
+                                }
+                            }
+                            if check.loses_work() {
+                                at_risk += 1;
+                            }
+                        }
+                    }
+
+                    if *dry_run {
+                        println!("\n{}", "--- DRY RUN COMPLETE ---".yellow().bold());
+                        return Ok(());
+                    }
+
+                    if at_risk > 0 && !*force {
+                        println!(
+                            "\n{} Aligning would lose work in {} submodule(s).",
+                            "ERROR:".red().bold(),
+                            at_risk
+                        );
+                        println!(
+                            "Commit or branch it first, or re-run with --force (a backup ref is created under {}).",
+                            align::BACKUP_PREFIX
+                        );
+                        std::process::exit(1);
+                    }
+
+                    let timestamp = std::time::SystemTime::now()
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .map(|d| d.as_secs())
+                        .unwrap_or(0);
+                    let mut results = Vec::new();
+                    for (p, subs) in checks {
+                        println!("\nAligning submodules for {}...", p.name.cyan());
+                        for (sub, check) in subs {
+                            if check.actual.is_some() && !check.is_aligned() {
+                                match align::backup(&check.path, timestamp) {
+                                    Ok(refname) => println!(
+                                        "  {} {} {}",
+                                        "Backup:".dimmed(),
+                                        sub.name.cyan(),
+                                        refname
+                                    ),
+                                    Err(e) => {
+                                        println!(
+                                            "  {} Could not back up {}: {}",
+                                            "ERROR:".red().bold(),
+                                            sub.name,
+                                            e
+                                        );
+                                        std::process::exit(1);
+                                    }
+                                }
+                            }
+                            let res =
+                                toad_git::align::align_submodule(&p.path, &sub.path, &sub.name)?;
+                            results.push(res);
+                        }
+                    }
+
+                    // Summary
+                    println!("\n--- ALIGNMENT SUMMARY ---");
+                    for res in results {
                         let status = if res.success {
                             "OK".green()
                         } else {
@@ -2781,6 +5174,1802 @@ fn main() -> Result<()> {
                         println!("{:<30} {}", res.project_name.bold(), status);
                     }
                 }
+                GgitCommand::Rebase {
+                    onto,
+                    query,
+                    tag,
+                    abort,
+                    cont,
+                    fail_fast,
+                } => {
+                    let targets = filter_projects(projects, query, tag);
+                    run_group_integration(
+                        &workspace,
+                        targets,
+                        integrate::Strategy::Rebase,
+                        onto.as_deref(),
+                        *abort,
+                        *cont,
+                        *fail_fast,
+                    )?;
+                }
+                GgitCommand::Merge {
+                    branch,
+                    query,
+                    tag,
+                    abort,
+                    cont,
+                    fail_fast,
+                } => {
+                    let targets = filter_projects(projects, query, tag);
+                    run_group_integration(
+                        &workspace,
+                        targets,
+                        integrate::Strategy::Merge,
+                        branch.as_deref(),
+                        *abort,
+                        *cont,
+                        *fail_fast,
+                    )?;
+                }
+                GgitCommand::Release {
+                    version,
+                    query,
+                    tag,
+                    message,
+                    yes,
+                    dry_run,
+                } => {
+                    let version = release::normalize_version(version)?;
+                    let tag_name = release::tag_name(&version);
+                    let message = message
+                        .clone()
+                        .unwrap_or_else(|| format!("chore(release): {}", tag_name));
+                    enforce_commit_policy(&workspace, &message)?;
+                    println!(
+                        "{}",
+                        format!("--- RELEASE PREFLIGHT: {} ---", tag_name)
+                            .blue()
+                            .bold()
+                    );
+
+                    let targets = filter_projects(projects, query, tag);
+                    if targets.is_empty() {
+                        println!("No projects found matching filters.");
+                        return Ok(());
+                    }
+
+                    let journal_path = txn::Journal::path(&workspace)?;
+                    refuse_pending_transaction(&journal_path)?;
+
+                    // Reversed, submodules release before their parents and the Hub root last.
+                    let mut repos = hub_and_submodule_repos(&workspace, targets);
+                    repos.reverse();
+                    let (plans, blockers) = release::plan(&repos, &version)?;
+
+                    for plan in &plans {
+                        println!("\n{} {}", "»".blue(), plan.name.bold());
+                        for edit in &plan.edits {
+                            println!("  {}", edit.file.display().to_string().cyan());
+                            for change in &edit.changes {
+                                println!(
+                                    "    {:<24} {} → {}",
+                                    change.item,
+                                    change.from.dimmed(),
+                                    change.to.green()
+                                );
+                            }
+                        }
+                        if plan.edits.is_empty() {
+                            println!("  {}", "No manifests to bump".dimmed());
+                        }
+                        println!("  {} {}", "tag:".dimmed(), tag_name.yellow());
+                    }
+
+                    if !blockers.is_empty() {
+                        println!("\n{} Release blocked:", "ERROR:".red().bold());
+                        for b in &blockers {
+                            println!("  - {}", b.yellow());
+                        }
+                        std::process::exit(1);
+                    }
+
+                    if *dry_run {
+                        println!("\n{}", "--- DRY RUN COMPLETE ---".green().bold());
+                        return Ok(());
+                    }
+
+                    if !*yes
+                        && !confirm(&format!(
+                            "\nRelease {} across {} repositories?",
+                            tag_name,
+                            plans.len()
+                        ))?
+                    {
+                        return Ok(());
+                    }
+
+                    let journal = release::journal(&plans, &tag_name, &message);
+                    run_transaction(journal, &journal_path, "RELEASE")?;
+
+                    println!(
+                        "\n{} Tagged {} locally. Push with 'git push --follow-tags' when ready.",
+                        "SUCCESS:".green().bold(),
+                        tag_name
+                    );
+                }
+            }
+        }
+        Commands::Bisect {
+            good,
+            bad,
+            project,
+            command,
+        } => {
+            println!("{}", "--- ECOSYSTEM BISECT ---".blue().bold());
+            let root = workspace.root.clone();
+            let ends = bisect::endpoints(&root, good, bad)?;
+            let repos = bisect::repos(&root, &ends.good, &ends.bad);
+
+            let test_dir =
+                bisect::test_dir(&root, project.as_deref(), || load_projects(&workspace))?;
+
+            let issues = bisect::safety_issues(&repos);
+            if !issues.is_empty() {
+                println!("\n{} Safety checks failed:", "ERROR:".red().bold());
+                for (name, issue) in issues {
+                    println!("  » {}:", name.cyan());
+                    println!("    - {}", issue.yellow());
+                }
+                std::process::exit(1);
+            }
+
+            let original = lock::snapshot(&root, &repos);
+            println!(
+                "Testing {:?} with `{}` across {} repositories...",
+                test_dir,
+                command.join(" "),
+                repos.len()
+            );
+
+            let result = bisect::run(
+                &root,
+                ends.hub_refs,
+                &ends.good,
+                &ends.bad,
+                |label, state| {
+                    bisect::apply(&root, state)?;
+                    let verdict = bisect::Verdict::from_code(bisect::run_test(&test_dir, command)?);
+                    let tag = match verdict {
+                        bisect::Verdict::Good => "GOOD".green(),
+                        bisect::Verdict::Bad => "BAD".red(),
+                        bisect::Verdict::Skip => "SKIP".yellow(),
+                    };
+                    println!("  {:<6} {}", tag, label);
+                    Ok(verdict)
+                },
+            );
+
+            println!("\nRestoring original checkouts...");
+            for (name, err) in bisect::restore(&root, &original)? {
+                println!("  {} {}: {}", "WARN:".yellow(), name, err);
+            }
+
+            match result {
+                Ok(report) => {
+                    println!("\n{}", "--- BISECT RESULT ---".blue().bold());
+                    for line in report {
+                        println!("{}", line);
+                    }
+                }
+                Err(e) => {
+                    println!("\n{} {}", "ERROR:".red().bold(), e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Commands::Changelog {
+            range,
+            query,
+            tag,
+            version,
+            write,
+        } => {
+            println!("{}", "--- ECOSYSTEM CHANGELOG ---".blue().bold());
+            let Some((from, to)) = range.split_once("..") else {
+                bail!("Expected a range such as v1.0.0..v1.1.0 or old.lock..new.lock");
+            };
+            let root = workspace.root.clone();
+
+            // (from, to) commits per repository path relative to the root ("." for the Hub).
+            let is_file = |r: &str| std::path::Path::new(r).is_file();
+            let lock_state = |file: &str| bisect::lock_state(std::path::Path::new(file));
+            let lock_mode = match (is_file(from), is_file(to)) {
+                (true, true) => true,
+                (false, false) => false,
+                _ => bail!("Both ends of the range must be lockfiles, or both refs"),
+            };
+            let states = if lock_mode {
+                Some((lock_state(from)?, lock_state(to)?))
+            } else if root.join(".gitmodules").exists() {
+                // Submodules without the tags fall back to the Hub's recorded pointers.
+                bisect::hub_state(&root, from)
+                    .ok()
+                    .zip(bisect::hub_state(&root, to).ok())
+            } else {
+                None
+            };
+
+            let targets = filter_projects(load_projects(&workspace)?, query, tag);
+            let rel = |path: &std::path::Path| match path.strip_prefix(&root) {
+                Ok(p) if p.as_os_str().is_empty() => bisect::HUB.to_string(),
+                Ok(p) => p.to_string_lossy().to_string(),
+                Err(_) => path.to_string_lossy().to_string(),
+            };
+            // (display name, crate label, path)
+            let mut repos = Vec::new();
+            if root.join(".gitmodules").exists() && !targets.iter().any(|p| p.path == root) {
+                repos.push(("Hub Root".to_string(), None, root.clone()));
+            }
+            for p in targets {
+                let label = (p.path != root).then(|| p.name.clone());
+                repos.push((p.name.clone(), label, p.path.clone()));
+                for sub in p.submodules {
+                    repos.push((
+                        format!("{} > {}", p.name, sub.name),
+                        Some(sub.name.clone()),
+                        root.join(&sub.path),
+                    ));
+                }
+            }
+
+            // Unfiltered runs also cover every repository the Hub or lockfile records.
+            if let (None, None, Some((_, new))) = (query, tag, &states) {
+                for key in new.keys().filter(|k| *k != bisect::HUB) {
+                    if !repos.iter().any(|(_, _, path)| rel(path) == *key) {
+                        let label = key.rsplit('/').next().unwrap_or(key).to_string();
+                        repos.push((key.clone(), Some(label), root.join(key)));
+                    }
+                }
+            }
+
+            let mut skipped_repos = Vec::new();
+            let mut ranges = Vec::new();
+            for (name, label, path) in repos {
+                let has = |r: &str| git::ref_exists(&path, &format!("{}^{{commit}}", r));
+                let pointers = states.as_ref().and_then(|(old, new)| {
+                    let key = rel(&path);
+                    old.get(&key).cloned().zip(new.get(&key).cloned())
+                });
+                let span = if !lock_mode && has(from) && has(to) {
+                    Some((from.to_string(), to.to_string()))
+                } else {
+                    pointers
+                };
+                match span {
+                    Some((a, b)) if a == b => {}
+                    Some((a, b)) => ranges.push((name, label, path, a, b)),
+                    None => skipped_repos.push(name),
+                }
+            }
+
+            let collected: Vec<_> = ranges
+                .par_iter()
+                .map(|(_, _, path, a, b)| changelog::entries(path, a, b))
+                .collect();
+            let mut sections = Vec::new();
+            for ((name, label, path, _, _), res) in ranges.into_iter().zip(collected) {
+                match res {
+                    Ok((entries, skipped)) => {
+                        println!(
+                            "{} {:<30} {} entries ({} other commits left out)",
+                            "»".blue(),
+                            name.bold(),
+                            entries.len(),
+                            skipped
+                        );
+                        if !entries.is_empty() {
+                            sections.push((label, path, entries));
+                        }
+                    }
+                    Err(e) => println!("{} {}: {}", "WARN:".yellow(), name, e),
+                }
+            }
+            if !skipped_repos.is_empty() {
+                println!(
+                    "{} No '{}' and '{}' in: {}",
+                    "WARN:".yellow(),
+                    from,
+                    to,
+                    skipped_repos.join(", ").dimmed()
+                );
+            }
+            if sections.is_empty() {
+                println!("\nNo changelog entries in this range.");
+                return Ok(());
+            }
+
+            let version = version.clone().unwrap_or_else(|| {
+                if lock_mode {
+                    std::path::Path::new(to)
+                        .file_stem()
+                        .map(|s| s.to_string_lossy().to_string())
+                        .unwrap_or_else(|| to.to_string())
+                } else {
+                    to.to_string()
+                }
+            });
+            let date = chrono::Local::now().format("%Y-%m-%d").to_string();
+            let groups: Vec<(Option<&str>, &[changelog::Entry])> = sections
+                .iter()
+                .map(|(label, _, entries)| (label.as_deref(), entries.as_slice()))
+                .collect();
+            let hub_section = changelog::render(&version, &date, &groups);
+
+            if !*write {
+                println!("\n{}\n", "--- HUB CHANGELOG ---".blue().bold());
+                print!("{}", hub_section);
+                for (label, _, entries) in &sections {
+                    let Some(label) = label else { continue };
+                    println!("\n{}\n", format!("--- {} ---", label).blue().bold());
+                    print!("{}", changelog::render(&version, &date, &[(None, entries)]));
+                }
+                return Ok(());
+            }
+
+            let mut written = Vec::new();
+            for (label, path, entries) in &sections {
+                if label.is_some() {
+                    let file = path.join("CHANGELOG.md");
+                    changelog::prepend(
+                        &file,
+                        &changelog::render(&version, &date, &[(None, entries)]),
+                    )?;
+                    written.push(file);
+                }
+            }
+            if root.join(".git").exists() {
+                let file = root.join("CHANGELOG.md");
+                changelog::prepend(&file, &hub_section)?;
+                written.push(file);
+            }
+            println!();
+            for file in &written {
+                println!("{} {:?}", "SUCCESS:".green().bold(), file);
+            }
+        }
+        Commands::Change { subcommand } => {
+            let registry_path = change::ChangeRegistry::path(&workspace)?;
+            let mut registry = change::ChangeRegistry::load(&registry_path)?;
+            match subcommand {
+                ChangeCommand::Start {
+                    name,
+                    query,
+                    tag,
+                    branch,
+                    yes,
+                    dry_run,
+                } => {
+                    println!("{}", "--- CHANGE START ---".blue().bold());
+                    if registry.changes.contains_key(name) {
+                        println!(
+                            "{} Change '{}' already exists; see `toad change status {}`.",
+                            "ERROR:".red().bold(),
+                            name,
+                            name
+                        );
+                        std::process::exit(1);
+                    }
+                    let branch = branch.clone().unwrap_or_else(|| name.clone());
+
+                    let repos = change::repos(
+                        &workspace.root,
+                        filter_projects(load_projects(&workspace)?, &None, tag),
+                        query,
+                    );
+                    if repos.is_empty() {
+                        println!("No repositories found matching filters.");
+                        return Ok(());
+                    }
+
+                    let mut any_issues = false;
+                    for (label, path) in &repos {
+                        let issues = change::start_issues(path, &branch);
+                        println!("  {} {}", "»".blue(), label.bold());
+                        for issue in &issues {
+                            println!("    - {}", issue.yellow());
+                        }
+                        any_issues |= !issues.is_empty();
+                    }
+                    if any_issues {
+                        println!("\n{} Safety checks failed.", "ERROR:".red().bold());
+                        std::process::exit(1);
+                    }
+
+                    if *dry_run {
+                        println!("\n{}", "--- DRY RUN COMPLETE ---".yellow().bold());
+                        return Ok(());
+                    }
+
+                    if !*yes
+                        && !confirm(&format!(
+                            "\nCreate branch '{}' in {} repositories?",
+                            branch,
+                            repos.len()
+                        ))?
+                    {
+                        return Ok(());
+                    }
+
+                    let outcomes =
+                        change::start_all(&mut registry, &registry_path, name, &branch, &repos)?;
+                    let mut any_fail = false;
+                    println!("\n--- CHANGE SUMMARY ---");
+                    for (label, res) in outcomes {
+                        let status = match &res {
+                            Some(res) if !res.success => {
+                                any_fail = true;
+                                "FAIL".red()
+                            }
+                            Some(_) => "OK".green(),
+                            None => "ADOPTED".cyan(),
+                        };
+                        println!("{:<40} {}", label.bold(), status);
+                        if let Some(res) = res.filter(|res| !res.success) {
+                            println!("  Error: {}", res.stderr.dimmed());
+                        }
+                    }
+                    if any_fail {
+                        std::process::exit(1);
+                    }
+                }
+                ChangeCommand::Status { name } => {
+                    println!("{}", "--- CHANGE STATUS ---".blue().bold());
+                    let sets: Vec<&change::ChangeSet> = match name {
+                        Some(n) => match registry.changes.get(n) {
+                            Some(set) => vec![set],
+                            None => bail!("No change named '{}'", n),
+                        },
+                        None => registry.changes.values().collect(),
+                    };
+                    if sets.is_empty() {
+                        println!("No changes in progress. Start one with `toad change start`.");
+                        return Ok(());
+                    }
+
+                    let backend = backend::select();
+                    for set in sets {
+                        println!(
+                            "\n{} {} ({})",
+                            "»".blue(),
+                            set.name.bold(),
+                            set.branch.cyan()
+                        );
+                        for member in &set.members {
+                            let st = change::status(backend.as_ref(), member, &set.branch);
+                            let merge = match st.merge {
+                                change::MergeState::Missing => "branch deleted".dimmed(),
+                                change::MergeState::Empty => "no commits".dimmed(),
+                                change::MergeState::Unmerged => "unmerged".yellow(),
+                                change::MergeState::Merged => {
+                                    format!("merged into {}", member.base).green()
+                                }
+                                change::MergeState::MergedRemote => {
+                                    format!("merged into remote {}", member.base).green()
+                                }
+                            };
+                            let push = match st.push {
+                                change::PushState::NotPushed => "not pushed".to_string(),
+                                change::PushState::UpToDate => "pushed".to_string(),
+                                change::PushState::Ahead(n) => format!("↑{} to push", n),
+                                change::PushState::Behind(n) => format!("↓{} on remote", n),
+                                change::PushState::Diverged(a, b) => {
+                                    format!("diverged ↑{} ↓{}", a, b)
+                                }
+                            };
+                            println!(
+                                "  {} {:<36} {:>3} commit(s)  {:<14} {}{}",
+                                "└─".dimmed(),
+                                member.name,
+                                st.commits.len(),
+                                push,
+                                merge,
+                                if st.checked_out {
+                                    ""
+                                } else {
+                                    " (not checked out)"
+                                }
+                            );
+                            for line in &st.commits {
+                                println!("       {}", line.dimmed());
+                            }
+                        }
+                    }
+                }
+                ChangeCommand::Finish {
+                    name,
+                    merge,
+                    yes,
+                    dry_run,
+                } => {
+                    println!("{}", "--- CHANGE FINISH ---".blue().bold());
+                    let Some(set) = registry.changes.get(name).cloned() else {
+                        bail!("No change named '{}'", name);
+                    };
+
+                    // (member, merge it first?) for every repository with a branch left.
+                    let mut plan = Vec::new();
+                    let mut blockers = Vec::new();
+                    let backend = backend::select();
+                    for member in &set.members {
+                        let st = change::status(backend.as_ref(), member, &set.branch);
+                        let action = match st.merge {
+                            change::MergeState::Missing => continue,
+                            change::MergeState::Empty => "delete empty branch".to_string(),
+                            change::MergeState::Merged | change::MergeState::MergedRemote => {
+                                "delete merged branch".to_string()
+                            }
+                            change::MergeState::Unmerged if *merge => format!(
+                                "merge {} commit(s) into {}, then delete",
+                                st.commits.len(),
+                                member.base
+                            ),
+                            change::MergeState::Unmerged => {
+                                blockers.push(member.name.clone());
+                                format!("{} unmerged commit(s)", st.commits.len())
+                            }
+                        };
+                        let needs_merge = st.merge == change::MergeState::Unmerged;
+                        let dirty = git::tracked_changes(&member.path) > 0;
+                        if dirty && (needs_merge || st.checked_out) {
+                            blockers.push(member.name.clone());
+                        }
+                        println!(
+                            "  {} {:<40} {}{}",
+                            "»".blue(),
+                            member.name,
+                            action,
+                            if dirty && (needs_merge || st.checked_out) {
+                                " (uncommitted changes)".yellow()
+                            } else {
+                                "".normal()
+                            }
+                        );
+                        plan.push((member.clone(), needs_merge));
+                    }
+
+                    if !blockers.is_empty() {
+                        println!(
+                            "\n{} Cannot finish '{}': {} repositories have unmerged commits or uncommitted changes.",
+                            "ERROR:".red().bold(),
+                            name,
+                            blockers.len()
+                        );
+                        println!(
+                            "Merge them upstream first, or re-run with {} to merge locally.",
+                            "--merge".bold()
+                        );
+                        std::process::exit(1);
+                    }
+
+                    if *dry_run {
+                        println!("\n{}", "--- DRY RUN COMPLETE ---".yellow().bold());
+                        return Ok(());
+                    }
+
+                    if !plan.is_empty()
+                        && !*yes
+                        && !confirm(&format!("\nFinish change '{}'?", name))?
+                    {
+                        return Ok(());
+                    }
+
+                    let mut results = Vec::new();
+                    for (member, needs_merge) in &plan {
+                        let steps = change::finish(member, &set.branch, *needs_merge)?;
+                        // One row per member: its failing step, or the last one.
+                        results.extend(steps.iter().find(|r| !r.success).or(steps.last()).cloned());
+                    }
+
+                    if print_summary("FINISH", &results) {
+                        println!(
+                            "\n{} '{}' is kept so the remaining repositories can be finished later.",
+                            "WARN:".yellow(),
+                            name
+                        );
+                        std::process::exit(1);
+                    }
+                    registry.changes.remove(name);
+                    registry.save(&registry_path)?;
+                }
+            }
+        }
+        Commands::Clone {
+            manifest,
+            dest,
+            name,
+            dry_run,
+        } => {
+            let spec = bootstrap::Manifest::load(manifest)?;
+            if spec.projects.is_empty() {
+                println!("No projects with remotes listed in {:?}.", manifest);
+                return Ok(());
+            }
+
+            let (root, projects_dir) = bootstrap::target_dirs(
+                dest.as_deref(),
+                discovered
+                    .as_ref()
+                    .ok()
+                    .map(|ws| (ws.root.as_path(), ws.projects_dir.as_path())),
+                &std::env::current_dir()?,
+            );
+
+            println!("{}", "--- ECOSYSTEM CLONE ---".blue().bold());
+            println!("Target: {:?}\n", projects_dir);
+            if let Some(hub) = &spec.hub_remote {
+                println!(
+                    "{} The lock's Hub root ({}) is not cloned; its projects are restored on their own.\n",
+                    "NOTE:".yellow(),
+                    hub
+                );
+            }
+            for p in &spec.projects {
+                let branch = p.branch.as_deref().unwrap_or("default branch");
+                let exists = p.target(&projects_dir).exists();
+                println!(
+                    "{} {:<24} {} ({}){}",
+                    "»".blue(),
+                    p.name.bold(),
+                    p.remote.dimmed(),
+                    branch.cyan(),
+                    if exists { " — exists, skipping" } else { "" }.yellow()
+                );
+            }
+
+            if *dry_run {
+                println!("\n{}", "--- DRY RUN COMPLETE ---".green().bold());
+                return Ok(());
+            }
+
+            fs::create_dir_all(&projects_dir)?;
+            let root = fs::canonicalize(root)?;
+            ensure_root_marker(&root)?;
+
+            let mirrors_root = mirror::mirrors_root()?;
+            let rewrites = mirror::rewrite_args(&mirrors_root);
+            if rewrites.is_empty() {
+                println!("\nCloning {} repositories...", spec.projects.len());
+            } else {
+                println!(
+                    "\nCloning {} repositories ({} local mirror(s) available)...",
+                    spec.projects.len(),
+                    mirror::list(&mirrors_root).len()
+                );
+            }
+            let outcomes = bootstrap::clone_all(&spec, &projects_dir, &rewrites);
+
+            println!("\n--- CLONE SUMMARY ---");
+            let mut any_failed = false;
+            for (p, outcome) in &outcomes {
+                let status = match outcome {
+                    bootstrap::CloneOutcome::Cloned => "OK".green(),
+                    bootstrap::CloneOutcome::Exists => "EXISTS".dimmed(),
+                    bootstrap::CloneOutcome::Failed(_) => {
+                        any_failed = true;
+                        "FAIL".red()
+                    }
+                };
+                println!("{:<40} {}", p.name.bold(), status);
+                if let bootstrap::CloneOutcome::Failed(err) = outcome {
+                    println!("  Error: {}", err.dimmed());
+                }
+            }
+
+            // Register the workspace as a context and seed its tags.
+            let mut config = load_global_config()?;
+            let ctx_name = bootstrap::context_name(name.as_deref(), &spec, &root);
+            match bootstrap::registration(&config, &ctx_name, &root) {
+                bootstrap::Registration::Registered => {
+                    println!("\nContext '{}' is already registered.", ctx_name);
+                }
+                bootstrap::Registration::Taken(path) => {
+                    println!(
+                        "\n{} Context '{}' already points at {:?}; not re-registering. Use --name to pick another.",
+                        "WARNING:".yellow().bold(),
+                        ctx_name,
+                        path
+                    );
+                }
+                bootstrap::Registration::New => {
+                    if config.active_context.is_none() {
+                        config.active_context = Some(ctx_name.clone());
+                    }
+                    let description = spec
+                        .context
+                        .description
+                        .clone()
+                        .unwrap_or_else(|| format!("Cloned from {:?}", manifest));
+                    register_context(
+                        &mut config,
+                        &ctx_name,
+                        &root,
+                        Some(description),
+                        toad_core::ContextType::Pond,
+                        Vec::new(),
+                    )?;
+                    println!(
+                        "\n{} Context '{}' registered at {:?}",
+                        "SUCCESS:".green().bold(),
+                        ctx_name,
+                        root
+                    );
+                }
+            }
+
+            let tags_path = match &discovered {
+                Ok(ws) if ws.root == root => ws.tags_path(),
+                _ => toad_core::GlobalConfig::context_dir(&ctx_name, None)?
+                    .join("shadows")
+                    .join("tags.json"),
+            };
+            let mut tag_reg = TagRegistry::load(&tags_path)?;
+            let tagged = bootstrap::seed_tags(&mut tag_reg, &outcomes);
+            if tagged > 0 {
+                tag_reg.save(&tags_path)?;
+                println!("Applied {} tags from the manifest.", tagged);
+            }
+
+            if any_failed {
+                std::process::exit(1);
+            }
+            if config.active_context.as_deref() != Some(ctx_name.as_str()) {
+                println!("Run 'toad project switch {}' to start using it.", ctx_name);
+            }
+        }
+        Commands::Grep {
+            pattern,
+            query,
+            tag,
+            ignore_case,
+            fixed_strings,
+            count,
+            files_with_matches,
+            json,
+        } => {
+            let targets = filter_projects(load_projects(&workspace)?, query, tag);
+            if targets.is_empty() {
+                println!("No projects found matching filters.");
+                return Ok(());
+            }
+
+            // Submodules inherit their parent project's artifact dirs.
+            let mut repos = Vec::new();
+            for p in &targets {
+                repos.push((p.name.clone(), p.path.clone(), p.artifact_dirs.clone()));
+                for sub in &p.submodules {
+                    repos.push((
+                        format!("{} > {}", p.name, sub.name),
+                        workspace.root.join(&sub.path),
+                        p.artifact_dirs.clone(),
+                    ));
+                }
+            }
+
+            let opts = grep::GrepOptions {
+                pattern: pattern.clone(),
+                ignore_case: *ignore_case,
+                fixed_strings: *fixed_strings,
+                mode: if *count {
+                    grep::Mode::Count
+                } else if *files_with_matches {
+                    grep::Mode::FilesWithMatches
+                } else {
+                    grep::Mode::Lines
+                },
+            };
+
+            let searched: Vec<_> = repos
+                .par_iter()
+                .map(|(name, path, artifacts)| (name.clone(), grep::search(path, &opts, artifacts)))
+                .collect();
+
+            let mut results = Vec::new();
+            for (name, res) in searched {
+                match res {
+                    Ok(hits) if !hits.is_empty() => results.push(grep::RepoHits {
+                        project: name,
+                        hits,
+                    }),
+                    Ok(_) => {}
+                    Err(e) => eprintln!("{} {}: {}", "WARN:".yellow(), name, e),
+                }
+            }
+
+            if *json {
+                println!("{}", serde_json::to_string_pretty(&results)?);
+                return Ok(());
+            }
+            if results.is_empty() {
+                println!("No matches found.");
+                return Ok(());
+            }
+
+            let mut total = 0;
+            for repo in &results {
+                println!("\n{} {}", "»".blue(), repo.project.bold());
+                for hit in &repo.hits {
+                    match opts.mode {
+                        grep::Mode::Lines => {
+                            total += 1;
+                            println!(
+                                "  {}:{}: {}",
+                                hit.path.cyan(),
+                                hit.line.unwrap_or_default().to_string().yellow(),
+                                hit.text.as_deref().unwrap_or_default()
+                            );
+                        }
+                        grep::Mode::Count => {
+                            let n = hit.count.unwrap_or_default();
+                            total += n;
+                            println!("  {}:{}", hit.path.cyan(), n.to_string().yellow());
+                        }
+                        grep::Mode::FilesWithMatches => {
+                            total += 1;
+                            println!("  {}", hit.path.cyan());
+                        }
+                    }
+                }
+            }
+            let unit = if opts.mode == grep::Mode::FilesWithMatches {
+                "files"
+            } else {
+                "matches"
+            };
+            println!(
+                "\n{} {} across {} repositories.",
+                total.to_string().bold(),
+                unit,
+                results.len()
+            );
+        }
+        Commands::Risk { query, tag, json } => {
+            let targets = filter_projects(load_projects(&workspace)?, query, tag);
+
+            // Submodules inherit their parent project's artifact dirs.
+            let mut repos = Vec::new();
+            if workspace.root.join(".gitmodules").exists()
+                && !targets.iter().any(|p| p.path == workspace.root)
+            {
+                repos.push(("Hub Root".to_string(), workspace.root.clone(), Vec::new()));
+            }
+            for p in &targets {
+                repos.push((p.name.clone(), p.path.clone(), p.artifact_dirs.clone()));
+                for sub in &p.submodules {
+                    repos.push((
+                        format!("{} > {}", p.name, sub.name),
+                        workspace.root.join(&sub.path),
+                        p.artifact_dirs.clone(),
+                    ));
+                }
+            }
+            if repos.is_empty() {
+                println!("No projects found matching filters.");
+                return Ok(());
+            }
+
+            let scanned = repos.len();
+            let mut at_risk: Vec<risk::RepoRisk> = repos
+                .par_iter()
+                .filter(|(_, path, _)| path.join(".git").exists())
+                .map(|(name, path, artifacts)| risk::scan(name, path, artifacts))
+                .filter(|r| !r.is_empty())
+                .collect();
+            // Largest exposure first.
+            at_risk.sort_by(|a, b| {
+                (b.commits(), b.files(), b.stashes)
+                    .cmp(&(a.commits(), a.files(), a.stashes))
+                    .then_with(|| a.name.cmp(&b.name))
+            });
+
+            if *json {
+                println!("{}", serde_json::to_string_pretty(&at_risk)?);
+                return Ok(());
+            }
+
+            println!("{}", "--- AT-RISK WORK ---".blue().bold());
+            if at_risk.is_empty() {
+                println!(
+                    "{} Nothing local-only across {} repositories.",
+                    "SUCCESS:".green().bold(),
+                    scanned
+                );
+                return Ok(());
+            }
+
+            let plural = |n: usize, one: &str, many: &str| {
+                format!("{} {}", n, if n == 1 { one } else { many })
+            };
+            for r in &at_risk {
+                println!(
+                    "\n{} {} ({}, {})",
+                    "»".blue(),
+                    r.name.bold(),
+                    plural(r.commits(), "commit", "commits").yellow(),
+                    plural(r.files(), "file", "files").yellow()
+                );
+                if r.modified > 0 {
+                    println!(
+                        "  {} {} uncommitted",
+                        "└─".dimmed(),
+                        plural(r.modified, "tracked file", "tracked files")
+                    );
+                }
+                if !r.untracked.is_empty() {
+                    let shown: Vec<&str> = r.untracked.iter().take(3).map(|s| s.as_str()).collect();
+                    let more = if r.untracked.len() > shown.len() {
+                        format!(", +{} more", r.untracked.len() - shown.len())
+                    } else {
+                        String::new()
+                    };
+                    println!(
+                        "  {} {} untracked ({}{})",
+                        "└─".dimmed(),
+                        plural(r.untracked.len(), "file", "files"),
+                        shown.join(", ").dimmed(),
+                        more.dimmed()
+                    );
+                }
+                if r.stashes > 0 {
+                    println!(
+                        "  {} {}",
+                        "└─".dimmed(),
+                        plural(r.stashes, "stash", "stashes")
+                    );
+                }
+                for b in &r.branches {
+                    let commits = plural(b.commits, "commit", "commits");
+                    match &b.upstream {
+                        Some(u) => println!(
+                            "  {} {}: {} ahead of {}",
+                            "└─".dimmed(),
+                            b.branch.cyan(),
+                            commits,
+                            u
+                        ),
+                        None => println!(
+                            "  {} {}: {} on no remote",
+                            "└─".dimmed(),
+                            b.branch.cyan(),
+                            commits
+                        ),
+                    }
+                }
+                if r.detached > 0 {
+                    println!(
+                        "  {} {}: {} reachable from no branch",
+                        "└─".dimmed(),
+                        "detached HEAD".red(),
+                        plural(r.detached, "commit", "commits")
+                    );
+                }
+            }
+
+            let commits: usize = at_risk.iter().map(|r| r.commits()).sum();
+            let files: usize = at_risk.iter().map(|r| r.files()).sum();
+            let stashes: usize = at_risk.iter().map(|r| r.stashes).sum();
+            println!(
+                "\n{} {} of {} repositories hold local-only work: {}, {}, {}.",
+                "WARN:".yellow(),
+                at_risk.len().to_string().bold(),
+                scanned,
+                plural(commits, "commit", "commits"),
+                plural(files, "file", "files"),
+                plural(stashes, "stash", "stashes")
+            );
+        }
+        Commands::Journal {
+            since,
+            query,
+            tag,
+            output,
+        } => {
+            let targets = filter_projects(load_projects(&workspace)?, query, tag);
+
+            let mut repos = Vec::new();
+            if workspace.root.join(".gitmodules").exists()
+                && !targets.iter().any(|p| p.path == workspace.root)
+            {
+                repos.push(("Hub Root".to_string(), workspace.root.clone()));
+            }
+            for p in targets {
+                repos.push((p.name.clone(), p.path.clone()));
+                for sub in p.submodules {
+                    repos.push((
+                        format!("{} > {}", p.name, sub.name),
+                        workspace.root.join(&sub.path),
+                    ));
+                }
+            }
+            repos.retain(|(_, path)| path.join(".git").exists());
+            let Some((_, first)) = repos.first() else {
+                println!("No repositories found matching filters.");
+                return Ok(());
+            };
+            let since_ts = journal::resolve_since(first, since)?;
+
+            let sections: Vec<String> = repos
+                .par_iter()
+                .filter_map(
+                    |(name, path)| match journal::collect(path, since, since_ts) {
+                        Ok(entry) if !entry.is_empty() => Some(journal::render(name, &entry)),
+                        Ok(_) => None,
+                        Err(e) => {
+                            eprintln!("{} {}: {}", "WARN:".yellow(), name, e);
+                            None
+                        }
+                    },
+                )
+                .collect();
+
+            let mut md = format!("# Journal since {}\n\n", since);
+            if sections.is_empty() {
+                md.push_str("_No activity._\n");
+            } else {
+                md.push_str(sections.join("").trim_end());
+                md.push('\n');
+            }
+
+            match output {
+                Some(path) => {
+                    fs::write(path, &md)?;
+                    println!(
+                        "{} Journal for {} repositories written to {:?}",
+                        "SUCCESS:".green().bold(),
+                        sections.len(),
+                        path
+                    );
+                }
+                None => print!("{}", md),
+            }
+        }
+        Commands::Lock {
+            subcommand,
+            output,
+            query,
+            tag,
+        } => match subcommand {
+            None => {
+                println!("{}", "--- ECOSYSTEM LOCK ---".blue().bold());
+                let targets = filter_projects(load_projects(&workspace)?, query, tag);
+                let repos = hub_and_submodule_repos(&workspace, targets);
+                let snapshot = lock::snapshot(&workspace.root, &repos);
+                if snapshot.repos.is_empty() {
+                    println!("No git repositories found matching filters.");
+                    return Ok(());
+                }
+
+                println!("{:<40} {:<10} BRANCH", "REPOSITORY", "COMMIT");
+                println!("{:-<40} {:-<10} {:-<20}", "", "", "");
+                for entry in &snapshot.repos {
+                    let branch = entry.branch.as_deref().unwrap_or("(detached)");
+                    println!(
+                        "{:<40} {:<10} {}",
+                        entry.name.bold(),
+                        git::short(&entry.commit).yellow(),
+                        branch.cyan()
+                    );
+                    for warning in lock::warnings(&workspace.root, entry) {
+                        println!("  {} {}", "WARN:".yellow(), warning);
+                    }
+                }
+
+                let path = output
+                    .clone()
+                    .unwrap_or_else(|| workspace.root.join(lock::LOCK_FILE));
+                snapshot.save(&path)?;
+                println!(
+                    "\n{} Locked {} repositories to {:?}",
+                    "SUCCESS:".green().bold(),
+                    snapshot.repos.len(),
+                    path
+                );
+            }
+            Some(LockCommand::Restore { file, yes, dry_run }) => {
+                let path = file
+                    .clone()
+                    .unwrap_or_else(|| workspace.root.join(lock::LOCK_FILE));
+                let lockfile = lock::Lockfile::load(&path)?;
+                println!("{}", "--- LOCK RESTORE PREFLIGHT ---".blue().bold());
+                println!("Lockfile: {:?}\n", path);
+
+                let mut pending = Vec::new();
+                let mut any_issues = false;
+                for (entry, state) in lock::plan_restore(&workspace.root, &lockfile) {
+                    if state == lock::RestoreState::Current {
+                        println!(
+                            "{:<40} {} {}",
+                            entry.name.bold(),
+                            git::short(&entry.commit).dimmed(),
+                            "up-to-date".dimmed()
+                        );
+                        continue;
+                    }
+                    println!(
+                        "{:<40} {} {}",
+                        entry.name.bold(),
+                        git::short(&entry.commit).yellow(),
+                        entry.branch.as_deref().unwrap_or("(detached)").cyan()
+                    );
+                    match state {
+                        lock::RestoreState::Blocked(issues) => {
+                            any_issues = true;
+                            for issue in issues {
+                                println!("  {} {}", "└─".dimmed(), issue.yellow());
+                            }
+                        }
+                        _ => pending.push(entry),
+                    }
+                }
+
+                if any_issues {
+                    println!(
+                        "\n{} Safety checks failed. Resolve the issues above and retry.",
+                        "ERROR:".red().bold()
+                    );
+                    std::process::exit(1);
+                }
+                if pending.is_empty() {
+                    println!("\nAll repositories already match the lockfile.");
+                    return Ok(());
+                }
+                if *dry_run {
+                    println!("\n{}", "--- DRY RUN COMPLETE ---".green().bold());
+                    return Ok(());
+                }
+                if !*yes
+                    && !confirm(&format!(
+                        "\nCheck out {} repositories to the locked commits?",
+                        pending.len()
+                    ))?
+                {
+                    return Ok(());
+                }
+
+                let mut results = Vec::new();
+                for entry in pending {
+                    println!("Restoring {}...", entry.name.cyan());
+                    results.push(lock::restore(&workspace.root, entry)?);
+                }
+                if print_summary("RESTORE", &results) {
+                    std::process::exit(1);
+                }
+            }
+            Some(LockCommand::Diff { old, new }) => {
+                let old_lock = lock::Lockfile::load(old)?;
+                let (new_lock, new_label) = match new {
+                    Some(path) => (lock::Lockfile::load(path)?, format!("{:?}", path)),
+                    None => {
+                        let repos: Vec<_> = old_lock
+                            .repos
+                            .iter()
+                            .map(|r| (r.name.clone(), r.abs_path(&workspace.root)))
+                            .collect();
+                        (
+                            lock::snapshot(&workspace.root, &repos),
+                            "current checkouts".to_string(),
+                        )
+                    }
+                };
+
+                println!("{}", "--- LOCK DIFF ---".blue().bold());
+                println!("{:?} → {}\n", old, new_label);
+
+                let changes = lock::diff(&old_lock, &new_lock);
+                if changes.is_empty() {
+                    println!("No differences.");
+                    return Ok(());
+                }
+
+                for change in &changes {
+                    match (&change.old, &change.new) {
+                        (None, Some(n)) => println!(
+                            "{} {:<38} {}",
+                            "+".green(),
+                            n.name.bold(),
+                            git::short(&n.commit).green()
+                        ),
+                        (Some(o), None) => println!(
+                            "{} {:<38} {}",
+                            "-".red(),
+                            o.name.bold(),
+                            git::short(&o.commit).red()
+                        ),
+                        (Some(o), Some(n)) => {
+                            println!(
+                                "{} {:<38} {} → {}",
+                                "~".yellow(),
+                                n.name.bold(),
+                                git::short(&o.commit).dimmed(),
+                                git::short(&n.commit).yellow()
+                            );
+                            if o.branch != n.branch {
+                                println!(
+                                    "  {} branch {} → {}",
+                                    "└─".dimmed(),
+                                    o.branch.as_deref().unwrap_or("(detached)"),
+                                    n.branch.as_deref().unwrap_or("(detached)")
+                                );
+                            }
+                            if o.remote != n.remote {
+                                println!(
+                                    "  {} remote {} → {}",
+                                    "└─".dimmed(),
+                                    o.remote.as_deref().unwrap_or("(none)"),
+                                    n.remote.as_deref().unwrap_or("(none)")
+                                );
+                            }
+                            if o.commit != n.commit {
+                                match change.log(&workspace.root) {
+                                    Some(log) if log.is_empty() => println!(
+                                        "  {} {}",
+                                        "└─".dimmed(),
+                                        "moves back to an earlier commit".dimmed()
+                                    ),
+                                    Some(log) => {
+                                        for line in log {
+                                            println!("  {} {}", "└─".dimmed(), line);
+                                        }
+                                    }
+                                    None => println!(
+                                        "  {} {}",
+                                        "└─".dimmed(),
+                                        "history not available locally".dimmed()
+                                    ),
+                                }
+                            }
+                        }
+                        (None, None) => {}
+                    }
+                }
+                println!("\n{} repositories differ.", changes.len());
+            }
+        },
+        Commands::Hooks {
+            subcommand,
+            query,
+            tag,
+            from,
+        } => {
+            let template_dir = match from {
+                Some(dir) => fs::canonicalize(dir).unwrap_or_else(|_| dir.clone()),
+                None => hooks::default_template_dir(&workspace)?,
+            };
+            let set = match hooks::templates(&template_dir) {
+                Ok(set) => set,
+                Err(e) => {
+                    println!("{} {}", "ERROR:".red().bold(), e);
+                    println!(
+                        "Add hook scripts there, or pass --from <dir> (e.g. scripts/git-hooks)."
+                    );
+                    std::process::exit(1);
+                }
+            };
+            let targets = filter_projects(load_projects(&workspace)?, query, tag);
+            let mut repos = hub_and_submodule_repos(&workspace, targets);
+            repos.retain(|(_, path)| path.join(".git").exists());
+            if repos.is_empty() {
+                println!("No repositories found matching filters.");
+                return Ok(());
+            }
+            let names: Vec<&str> = set.iter().map(|t| t.name.as_str()).collect();
+            let outcome = |name: &str, res: Result<()>| toad_core::GitOpResult {
+                project_name: name.to_string(),
+                success: res.is_ok(),
+                stdout: String::new(),
+                stderr: res.err().map(|e| e.to_string()).unwrap_or_default(),
+            };
+
+            match subcommand {
+                HooksCommand::Install { link, dry_run } => {
+                    println!("{}", "--- HOOK INSTALL ---".blue().bold());
+                    println!("Templates: {:?} ({})", template_dir, names.join(", "));
+
+                    if *dry_run {
+                        for (name, path) in &repos {
+                            let plan = match hooks::plan_install(path, &template_dir, &set, *link)?
+                            {
+                                hooks::InstallPlan::Linked => "already linked".dimmed().to_string(),
+                                hooks::InstallPlan::Link => "set core.hooksPath".to_string(),
+                                hooks::InstallPlan::CopyInactive(p) => {
+                                    format!("copy hooks (inactive: core.hooksPath is {})", p)
+                                        .yellow()
+                                        .to_string()
+                                }
+                                hooks::InstallPlan::Copy(todo) if todo.is_empty() => {
+                                    "up to date".dimmed().to_string()
+                                }
+                                hooks::InstallPlan::Copy(todo) => {
+                                    let todo: Vec<String> = todo
+                                        .into_iter()
+                                        .map(|(hook, backup)| match backup {
+                                            true => format!("{} (backup)", hook),
+                                            false => hook,
+                                        })
+                                        .collect();
+                                    format!("install {}", todo.join(", "))
+                                }
+                            };
+                            println!("  {} {:<30} {}", "»".blue(), name.bold(), plan);
+                        }
+                        println!("\n{}", "--- DRY RUN COMPLETE ---".yellow().bold());
+                        return Ok(());
+                    }
+
+                    let ts = std::time::SystemTime::now()
+                        .duration_since(std::time::UNIX_EPOCH)?
+                        .as_secs();
+                    let mut results = Vec::new();
+                    for (name, path) in &repos {
+                        println!("\n{} {}", "»".blue(), name.bold());
+                        let previous = hooks::hooks_path(path);
+                        if *link {
+                            if let Some(previous) = previous {
+                                println!(
+                                    "  {} replaces core.hooksPath {}",
+                                    "└─".dimmed(),
+                                    previous
+                                );
+                            }
+                            let res = hooks::link(path, name, &template_dir)?;
+                            if res.success {
+                                println!(
+                                    "  {} core.hooksPath -> {}",
+                                    "└─".dimmed(),
+                                    template_dir.to_string_lossy().cyan()
+                                );
+                            }
+                            results.push(res);
+                            continue;
+                        }
+                        if let Some(p) = previous {
+                            println!(
+                                "  {} core.hooksPath is {}; copied hooks will not run until it is unset",
+                                "WARN:".yellow(),
+                                p
+                            );
+                        }
+                        let installed = hooks::install(path, &set, ts);
+                        match &installed {
+                            Ok(installed) if installed.is_empty() => {
+                                println!("  {} {}", "└─".dimmed(), "up to date".dimmed())
+                            }
+                            Ok(installed) => {
+                                for i in installed {
+                                    match &i.backup {
+                                        Some(b) => println!(
+                                            "  {} {} (previous hook kept as {})",
+                                            "└─".dimmed(),
+                                            i.hook.green(),
+                                            b.file_name().unwrap_or_default().to_string_lossy()
+                                        ),
+                                        None => println!("  {} {}", "└─".dimmed(), i.hook.green()),
+                                    }
+                                }
+                            }
+                            Err(_) => {}
+                        }
+                        results.push(outcome(name, installed.map(|_| ())));
+                    }
+
+                    if print_summary("HOOK", &results) {
+                        std::process::exit(1);
+                    }
+                }
+                HooksCommand::Status => {
+                    println!("{}", "--- HOOK STATUS ---".blue().bold());
+                    println!("Templates: {:?} ({})", template_dir, names.join(", "));
+                    let mut drifted = 0;
+                    for (name, path) in &repos {
+                        let mode = hooks::status(path, &template_dir, &set)?;
+                        let label = match &mode {
+                            hooks::Mode::Linked => "linked".green().to_string(),
+                            hooks::Mode::Overridden(p) => {
+                                format!("core.hooksPath overridden ({})", p)
+                                    .yellow()
+                                    .to_string()
+                            }
+                            hooks::Mode::Copied(_) if mode.is_current() => {
+                                "up to date".green().to_string()
+                            }
+                            hooks::Mode::Copied(_) => {
+                                let parts: Vec<String> = [
+                                    ("drifted", hooks::HookState::Drifted),
+                                    ("outdated", hooks::HookState::Outdated),
+                                    ("missing", hooks::HookState::Missing),
+                                ]
+                                .iter()
+                                .filter_map(|(label, state)| {
+                                    let hooks = mode.hooks_in(state);
+                                    (!hooks.is_empty()).then(|| {
+                                        let text = format!("{}: {}", label, hooks.join(", "));
+                                        match state {
+                                            hooks::HookState::Drifted => text.red().to_string(),
+                                            _ => text.yellow().to_string(),
+                                        }
+                                    })
+                                })
+                                .collect();
+                                parts.join("; ")
+                            }
+                        };
+                        if !mode.is_current() {
+                            drifted += 1;
+                        }
+                        println!("{:<40} {}", name.bold(), label);
+                    }
+                    if drifted == 0 {
+                        println!(
+                            "\n{} All {} repositories match the hook templates.",
+                            "SUCCESS:".green().bold(),
+                            repos.len()
+                        );
+                    } else {
+                        println!(
+                            "\n{} {} of {} repositories differ from the hook templates. Run 'toad hooks install' to update them.",
+                            "WARN:".yellow(),
+                            drifted,
+                            repos.len()
+                        );
+                    }
+                }
+                HooksCommand::Remove { force, dry_run } => {
+                    println!("{}", "--- HOOK REMOVE ---".blue().bold());
+                    let mut results = Vec::new();
+                    for (name, path) in &repos {
+                        let plan = hooks::plan_remove(path, &template_dir, &set, *force)?;
+                        println!("\n{} {}", "»".blue(), name.bold());
+                        match plan {
+                            hooks::RemovePlan::Unlink => {
+                                println!("  {} unset core.hooksPath", "└─".dimmed());
+                                if !*dry_run {
+                                    results.push(hooks::unlink(path, name)?);
+                                }
+                            }
+                            hooks::RemovePlan::Copied(hooks) if *dry_run => {
+                                for (hook, removed) in hooks {
+                                    let action = match removed {
+                                        true => "remove".normal(),
+                                        false => "kept (modified)".yellow(),
+                                    };
+                                    println!("  {} {} {}", "└─".dimmed(), hook, action);
+                                }
+                            }
+                            hooks::RemovePlan::Overridden(p) if *dry_run => println!(
+                                "  {} core.hooksPath is {}; copied hooks only",
+                                "└─".dimmed(),
+                                p
+                            ),
+                            _ => {
+                                let removed = hooks::remove(path, &set, *force);
+                                for r in removed.iter().flatten() {
+                                    match r {
+                                        hooks::Removed::Restored(h) => println!(
+                                            "  {} {} (previous hook restored)",
+                                            "└─".dimmed(),
+                                            h
+                                        ),
+                                        hooks::Removed::Deleted(h) => {
+                                            println!("  {} {} removed", "└─".dimmed(), h)
+                                        }
+                                        hooks::Removed::KeptDrifted(h) => println!(
+                                            "  {} {} kept: modified since install (use --force)",
+                                            "WARN:".yellow(),
+                                            h
+                                        ),
+                                    }
+                                }
+                                results.push(outcome(name, removed.map(|_| ())));
+                            }
+                        }
+                    }
+
+                    if *dry_run {
+                        println!("\n{}", "--- DRY RUN COMPLETE ---".yellow().bold());
+                        return Ok(());
+                    }
+                    if print_summary("HOOK", &results) {
+                        std::process::exit(1);
+                    }
+                }
+            }
+        }
+        Commands::AuditIgnore {
+            query,
+            tag,
+            fix,
+            yes,
+            dry_run,
+        } => {
+            let targets = filter_projects(load_projects(&workspace)?, query, tag);
+
+            // Submodules inherit their parent project's artifact dirs.
+            let mut repos = Vec::new();
+            for p in &targets {
+                repos.push((p.name.clone(), p.path.clone(), p.artifact_dirs.clone()));
+                for sub in &p.submodules {
+                    repos.push((
+                        format!("{} > {}", p.name, sub.name),
+                        workspace.root.join(&sub.path),
+                        p.artifact_dirs.clone(),
+                    ));
+                }
+            }
+            repos
+                .retain(|(_, path, artifacts)| path.join(".git").exists() && !artifacts.is_empty());
+            if repos.is_empty() {
+                println!("No repositories with known artifact directories match the filters.");
+                return Ok(());
+            }
+
+            let scanned = repos.len();
+            let mut flagged: Vec<audit::RepoAudit> = repos
+                .par_iter()
+                .map(|(name, path, artifacts)| audit::scan(name, path, artifacts))
+                .filter(|a| !a.is_empty())
+                .collect();
+            flagged.sort_by(|a, b| a.name.cmp(&b.name));
+
+            println!("{}", "--- IGNORE AUDIT ---".blue().bold());
+            if flagged.is_empty() {
+                println!(
+                    "{} Artifacts are ignored and untracked in all {} repositories.",
+                    "SUCCESS:".green().bold(),
+                    scanned
+                );
+                return Ok(());
+            }
+
+            for a in &flagged {
+                println!("\n{} {}", "»".blue(), a.name.bold());
+                for f in &a.findings {
+                    let mut issues = Vec::new();
+                    if !f.tracked.is_empty() {
+                        issues.push(
+                            format!(
+                                "{} tracked {}",
+                                f.tracked.len(),
+                                if f.tracked.len() == 1 {
+                                    "file"
+                                } else {
+                                    "files"
+                                }
+                            )
+                            .red()
+                            .to_string(),
+                        );
+                    }
+                    if !f.unignored.is_empty() {
+                        issues.push(
+                            format!("{} unignored file(s)", f.unignored.len())
+                                .yellow()
+                                .to_string(),
+                        );
+                    } else if !f.ignored {
+                        issues.push("not in .gitignore".yellow().to_string());
+                    }
+                    println!(
+                        "  {} {:<20} {}",
+                        "└─".dimmed(),
+                        f.entry(),
+                        issues.join(", ")
+                    );
+                }
+                if *fix {
+                    let missing = a.missing();
+                    if !missing.is_empty() {
+                        println!("    {}", "--- a/.gitignore".dimmed());
+                        println!("    {}", "+++ b/.gitignore".dimmed());
+                        for entry in &missing {
+                            println!("    {}", format!("+{}", entry).green());
+                        }
+                    }
+                    for f in a.findings.iter().filter(|f| !f.tracked.is_empty()) {
+                        for file in f.tracked.iter().take(5) {
+                            println!("    {}", format!("-{} (untrack)", file).red());
+                        }
+                        if f.tracked.len() > 5 {
+                            println!(
+                                "    {}",
+                                format!("... {} more under {}", f.tracked.len() - 5, f.entry())
+                                    .dimmed()
+                            );
+                        }
+                    }
+                }
+            }
+
+            let missing: usize = flagged.iter().map(|a| a.missing().len()).sum();
+            let tracked: usize = flagged.iter().map(|a| a.tracked()).sum();
+            if !*fix {
+                println!(
+                    "\n{} {} of {} repositories need attention ({} missing entries, {} tracked artifact files). Run 'toad audit-ignore --fix' to repair them.",
+                    "WARN:".yellow(),
+                    flagged.len(),
+                    scanned,
+                    missing,
+                    tracked
+                );
+                return Ok(());
+            }
+            if *dry_run {
+                println!("\n{}", "--- DRY RUN COMPLETE ---".yellow().bold());
+                return Ok(());
+            }
+
+            let confirm = |prompt: String| -> Result<bool> {
+                if *yes {
+                    return Ok(true);
+                }
+                print!("\n{} [y/N]: ", prompt);
+                io::stdout().flush()?;
+                let mut input = String::new();
+                io::stdin().read_line(&mut input)?;
+                Ok(input.trim().to_lowercase().starts_with('y'))
+            };
+            let add_entries =
+                missing > 0 && confirm(format!("Append {} .gitignore entries?", missing))?;
+            // Untracking stages deletions, so it is offered separately.
+            let untrack = tracked > 0
+                && confirm(format!(
+                    "Untrack {} artifact files with 'git rm --cached' (files stay on disk)?",
+                    tracked
+                ))?;
+            if !add_entries && !untrack {
+                println!("Aborted.");
+                return Ok(());
+            }
+
+            let mut failures = Vec::new();
+            for a in &flagged {
+                if add_entries {
+                    if let Err(e) = audit::append_entries(&a.path, &a.missing()) {
+                        failures.push((a.name.clone(), e.to_string()));
+                        continue;
+                    }
+                }
+                if untrack && a.tracked() > 0 {
+                    match audit::untrack(a) {
+                        Ok(res) if res.success => {}
+                        Ok(res) => failures.push((a.name.clone(), res.stderr.trim().to_string())),
+                        Err(e) => failures.push((a.name.clone(), e.to_string())),
+                    }
+                }
+            }
+
+            println!("\n--- IGNORE SUMMARY ---");
+            for a in &flagged {
+                let failure = failures.iter().find(|(n, _)| *n == a.name);
+                let status = match failure {
+                    Some(_) => "FAIL".red(),
+                    None => "OK".green(),
+                };
+                println!("{:<40} {}", a.name.bold(), status);
+                if let Some((_, e)) = failure {
+                    println!("  Error: {}", e.dimmed());
+                }
+            }
+            if !failures.is_empty() {
+                std::process::exit(1);
+            }
+            println!("\nReview and commit the changes in each repository.");
+        }
+        Commands::Mirror { subcommand } => {
+            let root = mirror::mirrors_root()?;
+            match subcommand {
+                MirrorCommand::Sync { query, tag } => {
+                    let targets = filter_projects(load_projects(&workspace)?, query, tag);
+
+                    // One mirror per remote URL, however many checkouts share it.
+                    let mut urls: std::collections::BTreeMap<String, Vec<String>> =
+                        std::collections::BTreeMap::new();
+                    let mut add = |name: String, url: Option<String>| {
+                        if let Some(url) = url.filter(|u| mirror::is_absolute(u)) {
+                            urls.entry(url).or_default().push(name);
+                        }
+                    };
+                    if workspace.root.join(".gitmodules").exists()
+                        && !targets.iter().any(|p| p.path == workspace.root)
+                    {
+                        add("Hub Root".to_string(), mirror::remote_url(&workspace.root));
+                    }
+                    for p in &targets {
+                        add(p.name.clone(), mirror::remote_url(&p.path));
+                        for sub in &p.submodules {
+                            let path = workspace.root.join(&sub.path);
+                            let url = mirror::remote_url(&path).or_else(|| Some(sub.url.clone()));
+                            add(format!("{} > {}", p.name, sub.name), url);
+                        }
+                    }
+                    if urls.is_empty() {
+                        println!("No remotes found for the matching projects.");
+                        return Ok(());
+                    }
+
+                    println!("{}", "--- MIRROR SYNC ---".blue().bold());
+                    println!("Mirrors: {:?}", root);
+                    let pb = ProgressBar::new(urls.len() as u64);
+                    pb.set_style(
+                        ProgressStyle::default_bar()
+                            .template(
+                                "{spinner:.green} [{elapsed_precise}] [{bar:40.green/black}] {pos}/{len}",
+                            )?
+                            .progress_chars("■-"),
+                    );
+                    let results: Vec<_> = urls
+                        .par_iter()
+                        .map(|(url, names)| {
+                            let res = mirror::sync(&root, url);
+                            pb.inc(1);
+                            (url, names, res)
+                        })
+                        .collect();
+                    pb.finish_and_clear();
+
+                    println!("\n--- MIRROR SUMMARY ---");
+                    let mut any_fail = false;
+                    for (url, names, res) in &results {
+                        let status = match res {
+                            Ok(mirror::SyncOutcome::Created) => "CREATED".green(),
+                            Ok(mirror::SyncOutcome::Updated) => "OK".green(),
+                            Err(_) => {
+                                any_fail = true;
+                                "FAIL".red()
+                            }
+                        };
+                        println!("{:<40} {}", names.join(", ").bold(), status);
+                        println!("  {} {}", "└─".dimmed(), url.dimmed());
+                        if let Err(e) = res {
+                            println!("  Error: {}", e.to_string().dimmed());
+                        }
+                    }
+                    if any_fail {
+                        std::process::exit(1);
+                    }
+                }
+                MirrorCommand::List => {
+                    let mirrors = mirror::list(&root);
+                    if mirrors.is_empty() {
+                        println!("No mirrors yet. Run 'toad mirror sync' to create them.");
+                        return Ok(());
+                    }
+                    println!("{}", "--- MIRRORS ---".blue().bold());
+                    let mut total = 0;
+                    for m in &mirrors {
+                        let size = maintain::git_dir_size(&m.path);
+                        total += size;
+                        let synced = m
+                            .synced_at()
+                            .map(|t| {
+                                chrono::DateTime::<chrono::Local>::from(t)
+                                    .format("%Y-%m-%d %H:%M")
+                                    .to_string()
+                            })
+                            .unwrap_or_else(|| "unknown".to_string());
+                        println!(
+                            "{:<50} {:>10}  synced {}",
+                            m.url.bold(),
+                            format_size(size),
+                            synced.dimmed()
+                        );
+                    }
+                    println!(
+                        "\n{} mirror(s), {} in {:?}",
+                        mirrors.len(),
+                        format_size(total).bold(),
+                        root
+                    );
+                }
             }
         }
         Commands::Cw { subcommand } => {
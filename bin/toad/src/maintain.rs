@@ -0,0 +1,170 @@
+//! `.git` storage analytics for `toad stats` and `toad ggit maintain`.
+//!
+//! The footprint of a repository is measured on its git directory (which for
+//! a submodule lives under the Hub's `.git/modules/`), so the Hub's own size
+//! leaves its submodules' directories out. Maintenance runs gc, prune, a
+//! full repack and a commit-graph write, and is judged by the bytes the git
+//! directory shrank.
+
+use crate::git;
+use anyhow::{Context, Result};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use toad_core::GitOpResult;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Blob {
+    pub path: String,
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Footprint {
+    /// On-disk size of the git directory.
+    pub git_bytes: u64,
+    pub loose_objects: u64,
+    pub loose_bytes: u64,
+    pub packs: u64,
+}
+
+/// Absolute git directory of a working tree.
+pub fn git_dir(repo: &Path) -> Option<PathBuf> {
+    git::probe(repo, &["rev-parse", "--absolute-git-dir"]).map(PathBuf::from)
+}
+
+/// Size of a git directory, leaving out nested submodule repositories.
+pub fn git_dir_size(dir: &Path) -> u64 {
+    fn walk(path: &Path, skip: Option<&Path>) -> u64 {
+        let Ok(entries) = fs::read_dir(path) else {
+            return 0;
+        };
+        entries
+            .filter_map(|e| e.ok())
+            .map(|e| {
+                let path = e.path();
+                match e.file_type() {
+                    Ok(t) if t.is_dir() && Some(path.as_path()) != skip => walk(&path, None),
+                    Ok(t) if t.is_file() => e.metadata().map(|m| m.len()).unwrap_or(0),
+                    _ => 0,
+                }
+            })
+            .sum()
+    }
+    walk(dir, Some(&dir.join("modules")))
+}
+
+/// Parses `git count-objects -v` into (loose count, loose bytes, packs).
+pub fn count_objects(repo: &Path) -> (u64, u64, u64) {
+    let out = git::probe(repo, &["count-objects", "-v"]).unwrap_or_default();
+    let field = |name: &str| {
+        out.lines()
+            .find_map(|l| l.strip_prefix(name)?.strip_prefix(": "))
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .unwrap_or(0)
+    };
+    // Sizes are reported in KiB.
+    (field("count"), field("size") * 1024, field("packs"))
+}
+
+/// The `limit` largest blobs reachable from any ref, with the path they were
+/// first seen at.
+pub fn largest_blobs(repo: &Path, limit: usize) -> Result<Vec<Blob>> {
+    let objects = git::output(repo, &["rev-list", "--objects", "--all"])?;
+    let mut child = Command::new("git")
+        .args([
+            "cat-file",
+            "--batch-check=%(objecttype) %(objectsize) %(rest)",
+        ])
+        .current_dir(repo)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()?;
+    let mut stdin = child.stdin.take().context("cat-file stdin")?;
+    // Written from a thread so a full stdout pipe cannot stall the writer.
+    let writer = std::thread::spawn(move || stdin.write_all(format!("{}\n", objects).as_bytes()));
+    let out = child.wait_with_output()?;
+    writer.join().ok();
+
+    let mut blobs: Vec<Blob> = String::from_utf8_lossy(&out.stdout)
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.splitn(3, ' ');
+            if parts.next()? != "blob" {
+                return None;
+            }
+            let size = parts.next()?.parse().ok()?;
+            Some(Blob {
+                path: parts.next().unwrap_or_default().to_string(),
+                size,
+            })
+        })
+        .collect();
+    blobs.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.path.cmp(&b.path)));
+    blobs.truncate(limit);
+    Ok(blobs)
+}
+
+/// Measures a repository's git directory. History is not walked; see
+/// [`largest_blobs`] for that.
+pub fn footprint(repo: &Path) -> Option<Footprint> {
+    let dir = git_dir(repo)?;
+    let (loose_objects, loose_bytes, packs) = count_objects(repo);
+    Some(Footprint {
+        git_bytes: git_dir_size(&dir),
+        loose_objects,
+        loose_bytes,
+        packs,
+    })
+}
+
+#[derive(Debug)]
+pub struct Report {
+    pub before: u64,
+    pub after: u64,
+    pub results: Vec<GitOpResult>,
+}
+
+impl Report {
+    pub fn reclaimed(&self) -> u64 {
+        self.before.saturating_sub(self.after)
+    }
+
+    pub fn success(&self) -> bool {
+        self.results.iter().all(|r| r.success)
+    }
+}
+
+/// Runs the maintenance steps in order, stopping at the first failure.
+pub fn run(repo: &Path, name: &str) -> Result<Report> {
+    let dir = git_dir(repo).with_context(|| format!("{} is not a git repository", name))?;
+    let before = git_dir_size(&dir);
+    // gc prunes and repacks on its own expiry schedule; the explicit prune and
+    // repack do the same work now, so the space shows up in this report.
+    let steps: [&[&str]; 4] = [
+        &["gc", "--quiet"],
+        &["prune"],
+        &["repack", "-a", "-d", "-q"],
+        &["commit-graph", "write", "--reachable"],
+    ];
+
+    let mut results = Vec::new();
+    for args in steps {
+        let res = toad_git::run_git(repo, args, &format!("{} ({})", name, args[0]))?;
+        let ok = res.success;
+        results.push(res);
+        if !ok {
+            break;
+        }
+    }
+    Ok(Report {
+        before,
+        after: git_dir_size(&dir),
+        results,
+    })
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,62 @@
+use super::*;
+use crate::test_support::{commit_with, git};
+use tempfile::tempdir;
+
+fn commit(dir: &Path, file: &str, bytes: usize) {
+    commit_with(dir, file, &"x".repeat(bytes), file);
+}
+
+#[test]
+fn test_footprint_counts_loose_objects_and_largest_blobs() {
+    let dir = tempdir().unwrap();
+    let repo = dir.path();
+    git(repo, &["init", "-q", "-b", "main"]);
+    commit(repo, "small.txt", 10);
+    commit(repo, "big.bin", 50_000);
+    // Deleted files still count: they live on in history.
+    fs::remove_file(repo.join("big.bin")).unwrap();
+    git(repo, &["commit", "-q", "-am", "drop big"]);
+
+    let fp = footprint(repo).unwrap();
+    assert!(fp.loose_objects > 0);
+    assert!(fp.git_bytes > 0);
+    assert_eq!(
+        largest_blobs(repo, 2).unwrap(),
+        vec![
+            Blob {
+                path: "big.bin".to_string(),
+                size: 50_000,
+            },
+            Blob {
+                path: "small.txt".to_string(),
+                size: 10,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_run_packs_loose_objects() {
+    let dir = tempdir().unwrap();
+    let repo = dir.path();
+    git(repo, &["init", "-q", "-b", "main"]);
+    for i in 0..5 {
+        commit(repo, &format!("file{}.txt", i), 2_000);
+    }
+    // A fresh unreachable object survives gc's expiry; prune drops it.
+    fs::write(repo.join("orphan.txt"), "orphan").unwrap();
+    git(repo, &["hash-object", "-w", "orphan.txt"]);
+
+    let report = run(repo, "repo").unwrap();
+    assert!(report.success(), "{:?}", report.results);
+    assert_eq!(report.results.len(), 4);
+    assert_eq!(count_objects(repo).0, 0);
+    assert!(footprint(repo).unwrap().packs >= 1);
+}
+
+#[test]
+fn test_not_a_repository() {
+    let dir = tempdir().unwrap();
+    assert!(footprint(dir.path()).is_none());
+    assert!(run(dir.path(), "plain").is_err());
+}
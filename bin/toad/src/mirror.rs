@@ -0,0 +1,225 @@
+//! Local bare mirrors for `toad mirror`, clones and offline pulls.
+//!
+//! Every remote gets a `git clone --mirror` under `mirrors/<host>/<path>.git`
+//! in the global config directory, shared by all contexts. Clones and
+//! submodule inits fetch through `url.<mirror>.insteadOf=<remote>` rewrites,
+//! so objects are copied locally while the real remote stays recorded in the
+//! new repository. When a remote cannot be reached, `ggit pull` reads from
+//! the mirror instead; the next online pull reconciles with the remote.
+
+use crate::git;
+use anyhow::Result;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use toad_core::{GitOpResult, GlobalConfig};
+
+const MIRRORS_DIR: &str = "mirrors";
+
+/// Messages git prints when the remote itself is unreachable, as opposed to a
+/// merge or authentication failure.
+const OFFLINE_MARKERS: &[&str] = &[
+    "Could not resolve host",
+    "Could not resolve hostname",
+    "Network is unreachable",
+    "Connection timed out",
+    "Connection refused",
+    "Operation timed out",
+    "No route to host",
+    "Temporary failure in name resolution",
+];
+
+pub fn mirrors_root() -> Result<PathBuf> {
+    Ok(GlobalConfig::config_dir(None)?.join(MIRRORS_DIR))
+}
+
+/// `git@github.com:org/lily` and `https://github.com/org/lily.git` both map
+/// to `github.com/org/lily.git`.
+pub fn key(url: &str) -> PathBuf {
+    let rest = url.split_once("://").map(|(_, r)| r).unwrap_or(url);
+    let rest = match rest.split_once('@') {
+        Some((user, host)) if !user.contains('/') => host,
+        _ => rest,
+    };
+    let rest = rest.replace(':', "/");
+    let parts: Vec<&str> = rest
+        .split('/')
+        .filter(|c| !c.is_empty() && *c != "." && *c != "..")
+        .collect();
+    let mut key = PathBuf::new();
+    for (i, part) in parts.iter().enumerate() {
+        if i + 1 == parts.len() {
+            key.push(format!("{}.git", part.trim_end_matches(".git")));
+        } else {
+            key.push(part);
+        }
+    }
+    key
+}
+
+pub fn mirror_path(root: &Path, url: &str) -> PathBuf {
+    root.join(key(url))
+}
+
+fn is_mirror(path: &Path) -> bool {
+    path.join("HEAD").is_file() && path.join("objects").is_dir()
+}
+
+/// URL of the repository's default remote.
+pub fn remote_url(repo: &Path) -> Option<String> {
+    let remote = git::default_remote(repo)?;
+    git::probe(repo, &["remote", "get-url", &remote]).filter(|u| !u.is_empty())
+}
+
+/// Submodule URLs may be relative to the superproject's remote; those are
+/// only mirrored once the submodule is initialized.
+pub fn is_absolute(url: &str) -> bool {
+    !url.starts_with("./") && !url.starts_with("../")
+}
+
+#[derive(Debug, Clone)]
+pub struct Mirror {
+    pub url: String,
+    pub path: PathBuf,
+}
+
+impl Mirror {
+    /// The last fetch into the mirror, or its creation for a fresh clone.
+    pub fn synced_at(&self) -> Option<SystemTime> {
+        ["FETCH_HEAD", "HEAD"]
+            .iter()
+            .find_map(|f| fs::metadata(self.path.join(f)).ok()?.modified().ok())
+    }
+}
+
+/// Every mirror under `root`, sorted by path.
+pub fn list(root: &Path) -> Vec<Mirror> {
+    fn walk(dir: &Path, found: &mut Vec<Mirror>) {
+        if is_mirror(dir) {
+            if let Some(url) = git::probe(dir, &["config", "remote.origin.url"]) {
+                found.push(Mirror {
+                    url,
+                    path: dir.to_path_buf(),
+                });
+            }
+            return;
+        }
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.filter_map(|e| e.ok()) {
+            if entry.file_type().is_ok_and(|t| t.is_dir()) {
+                walk(&entry.path(), found);
+            }
+        }
+    }
+    let mut found = Vec::new();
+    walk(root, &mut found);
+    found.sort_by(|a, b| a.path.cmp(&b.path));
+    found
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyncOutcome {
+    Created,
+    Updated,
+}
+
+/// Creates the mirror for `url` or fetches everything new into it.
+pub fn sync(root: &Path, url: &str) -> Result<SyncOutcome> {
+    let path = mirror_path(root, url);
+    if is_mirror(&path) {
+        git::output(&path, &["remote", "update", "--prune"])?;
+        return Ok(SyncOutcome::Updated);
+    }
+    let parent = path.parent().unwrap_or(root);
+    fs::create_dir_all(parent)?;
+    git::output(
+        parent,
+        &["clone", "--mirror", "--quiet", url, &path.to_string_lossy()],
+    )?;
+    Ok(SyncOutcome::Created)
+}
+
+/// The ways a remote may be written that lead to the same mirror, without a
+/// trailing `.git` or `/`: the recorded form plus its scp, ssh and https
+/// spellings for network remotes.
+fn url_bases(url: &str) -> Vec<String> {
+    let trimmed = url.trim_end_matches('/');
+    let mut bases = vec![trimmed.strip_suffix(".git").unwrap_or(trimmed).to_string()];
+    let network = match url.split_once("://") {
+        Some((scheme, _)) => scheme != "file",
+        None => url
+            .split_once(':')
+            .is_some_and(|(host, _)| !host.contains('/')),
+    };
+    if network {
+        let key = key(url).to_string_lossy().to_string();
+        if let Some((host, path)) = key.trim_end_matches(".git").split_once('/') {
+            for base in [
+                format!("git@{}:{}", host, path),
+                format!("ssh://git@{}/{}", host, path),
+                format!("https://{}/{}", host, path),
+            ] {
+                if !bases.contains(&base) {
+                    bases.push(base);
+                }
+            }
+        }
+    }
+    bases
+}
+
+/// `-c url.<mirror>.insteadOf=<url>` arguments for every mirror under `root`,
+/// to put in front of a git subcommand. Each form of a mirror's remote is
+/// rewritten with a trailing `.git` and with a trailing `/`, so `org/lily`
+/// never captures `org/lily-extra`.
+pub fn rewrite_args(root: &Path) -> Vec<String> {
+    list(root)
+        .into_iter()
+        .flat_map(|m| {
+            let mirror = m.path.to_string_lossy().to_string();
+            url_bases(&m.url).into_iter().flat_map(move |base| {
+                [
+                    "-c".to_string(),
+                    format!("url.{}.insteadOf={}.git", mirror, base),
+                    "-c".to_string(),
+                    format!("url.{}/.insteadOf={}/", mirror, base),
+                ]
+            })
+        })
+        .collect()
+}
+
+/// Runs `git <args>` through the mirror rewrites, then straight against the
+/// remotes if that fails (a stale mirror can lack a recorded commit).
+pub fn run_via(rewrites: &[String], dir: &Path, args: &[&str], name: &str) -> Result<GitOpResult> {
+    if !rewrites.is_empty() {
+        let mut via: Vec<&str> = rewrites.iter().map(|s| s.as_str()).collect();
+        via.extend(args);
+        let res = toad_git::run_git(dir, &via, name)?;
+        if res.success {
+            return Ok(res);
+        }
+    }
+    toad_git::run_git(dir, args, name)
+}
+
+pub fn is_offline(stderr: &str) -> bool {
+    OFFLINE_MARKERS.iter().any(|m| stderr.contains(m))
+}
+
+/// Pulls from the mirror of the repository's remote, for when the remote
+/// cannot be reached. Returns `None` if there is no mirror for it.
+pub fn pull_from_mirror(root: &Path, repo: &Path, name: &str) -> Option<Result<GitOpResult>> {
+    let url = remote_url(repo)?;
+    let path = mirror_path(root, &url);
+    if !is_mirror(&path) {
+        return None;
+    }
+    let rewrite = format!("url.{}.insteadOf={}", path.to_string_lossy(), url);
+    Some(toad_git::run_git(repo, &["-c", &rewrite, "pull"], name))
+}
+
+#[cfg(test)]
+mod tests;
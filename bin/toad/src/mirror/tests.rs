@@ -0,0 +1,83 @@
+use super::*;
+use crate::test_support::git;
+use tempfile::tempdir;
+
+#[test]
+fn test_key_normalizes_remote_forms() {
+    let expected = PathBuf::from("github.com/org/lily.git");
+    assert_eq!(key("git@github.com:org/lily.git"), expected);
+    assert_eq!(key("https://github.com/org/lily"), expected);
+    assert_eq!(key("ssh://git@github.com/org/lily.git/"), expected);
+    assert_eq!(key("/srv/git/../lily"), PathBuf::from("srv/git/lily.git"));
+    assert!(!is_absolute("../lily.git"));
+    assert!(is_offline(
+        "fatal: unable to access 'https://x/': Could not resolve host: x"
+    ));
+    assert!(!is_offline("CONFLICT (content): Merge conflict in a.rs"));
+}
+
+#[test]
+fn test_url_bases_cover_every_form_of_a_remote() {
+    let expected = vec![
+        "git@github.com:org/lily".to_string(),
+        "ssh://git@github.com/org/lily".to_string(),
+        "https://github.com/org/lily".to_string(),
+    ];
+    assert_eq!(url_bases("git@github.com:org/lily.git"), expected);
+    let mut https = url_bases("https://github.com/org/lily/");
+    https.sort();
+    let mut sorted = expected.clone();
+    sorted.sort();
+    assert_eq!(https, sorted);
+    assert_eq!(url_bases("/srv/git/lily.git"), vec!["/srv/git/lily"]);
+}
+
+#[test]
+fn test_sync_list_and_pull_from_mirror() {
+    let dir = tempdir().unwrap();
+    let origin = dir.path().join("origin");
+    let root = dir.path().join("mirrors");
+    fs::create_dir(&origin).unwrap();
+    git(&origin, &["init", "-q", "-b", "main"]);
+    git(&origin, &["commit", "-q", "--allow-empty", "-m", "init"]);
+    let url = origin.to_string_lossy().to_string();
+
+    assert_eq!(sync(&root, &url).unwrap(), SyncOutcome::Created);
+    git(&origin, &["commit", "-q", "--allow-empty", "-m", "two"]);
+    assert_eq!(sync(&root, &url).unwrap(), SyncOutcome::Updated);
+    let mirrors = list(&root);
+    assert_eq!(mirrors.len(), 1);
+    assert_eq!(mirrors[0].url, url);
+    assert!(mirrors[0].synced_at().is_some());
+
+    // A clone whose remote is gone can still pull what the mirror has.
+    let rewrites = rewrite_args(&root);
+    assert!(rewrites.contains(&format!(
+        "url.{}.insteadOf={}.git",
+        mirrors[0].path.to_string_lossy(),
+        url
+    )));
+    let clone = dir.path().join("clone");
+    let res = run_via(
+        &rewrites,
+        dir.path(),
+        &["clone", "--quiet", &url, &clone.to_string_lossy()],
+        "lily",
+    )
+    .unwrap();
+    assert!(res.success);
+    git(&clone, &["reset", "-q", "--hard", "HEAD~1"]);
+    fs::rename(&origin, dir.path().join("moved")).unwrap();
+    assert!(
+        !toad_git::run_git(&clone, &["pull"], "lily")
+            .unwrap()
+            .success
+    );
+    let res = pull_from_mirror(&root, &clone, "lily").unwrap().unwrap();
+    assert!(res.success, "{}", res.stderr);
+    assert_eq!(remote_url(&clone).as_deref(), Some(url.as_str()));
+    assert_eq!(
+        git::output(&clone, &["log", "-1", "--format=%s"]).unwrap(),
+        "two"
+    );
+}
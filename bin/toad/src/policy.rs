@@ -0,0 +1,190 @@
+//! Per-context git policy, read from `~/.toad/contexts/<name>/policy.toml`.
+//!
+//! ```toml
+//! [commit]
+//! conventional = true
+//! ticket_prefix = "TOAD"
+//!
+//! [push]
+//! protected = ["main", "release/*"]
+//!
+//! [prune]
+//! keep = ["develop", "support/*"]
+//! ```
+//!
+//! The commit policy covers messages the user writes (`ggit commit`,
+//! `ggit release`). Merge commits created by `ggit merge` and
+//! `ggit change finish` keep git's generated subject and are exempt, the same
+//! way commit linters skip merges.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use toad_core::{GlobalConfig, Workspace};
+
+const DEFAULT_TYPES: [&str; 11] = [
+    "build", "chore", "ci", "docs", "feat", "fix", "perf", "refactor", "revert", "style", "test",
+];
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ContextPolicy {
+    #[serde(default)]
+    pub commit: CommitPolicy,
+    #[serde(default)]
+    pub push: PushPolicy,
+    #[serde(default)]
+    pub prune: PrunePolicy,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct CommitPolicy {
+    /// Require Conventional Commit subjects (`type(scope)!: description`).
+    #[serde(default)]
+    pub conventional: bool,
+    /// Overrides the allowed Conventional Commit types.
+    #[serde(default)]
+    pub types: Vec<String>,
+    /// Require a ticket reference such as `TOAD-123` somewhere in the message.
+    pub ticket_prefix: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct PushPolicy {
+    /// Branches that must never be pushed to directly. A trailing `*` matches
+    /// any suffix (`release/*`).
+    #[serde(default)]
+    pub protected: Vec<String>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct PrunePolicy {
+    /// Branches `ggit prune-branches` must never delete, in addition to the
+    /// protected push branches.
+    #[serde(default)]
+    pub keep: Vec<String>,
+}
+
+impl ContextPolicy {
+    /// Location of the policy file for the active context, if there is one.
+    pub fn path(workspace: &Workspace) -> Result<Option<PathBuf>> {
+        match &workspace.active_context {
+            Some(name) => Ok(Some(
+                GlobalConfig::context_dir(name, None)?.join("policy.toml"),
+            )),
+            None => Ok(None),
+        }
+    }
+
+    /// Loads the active context's policy; a missing file means no policy.
+    pub fn load(workspace: &Workspace) -> Result<Self> {
+        match Self::path(workspace)? {
+            Some(path) if path.exists() => Ok(toml::from_str(&fs::read_to_string(path)?)?),
+            _ => Ok(Self::default()),
+        }
+    }
+
+    /// True if pruning must leave `branch` alone.
+    pub fn keeps_branch(&self, branch: &str) -> bool {
+        self.push.is_protected(branch) || self.prune.keep.iter().any(|p| matches_branch(p, branch))
+    }
+}
+
+impl CommitPolicy {
+    /// Returns the policy violations for a commit message (empty when valid).
+    /// Not applied to merge commits; see the module docs.
+    pub fn check(&self, message: &str) -> Vec<String> {
+        let mut issues = Vec::new();
+        let subject = message.lines().next().unwrap_or_default().trim();
+
+        if self.conventional {
+            if let Err(issue) = self.check_conventional(subject) {
+                issues.push(issue);
+            }
+        }
+
+        if let Some(prefix) = &self.ticket_prefix {
+            if !contains_ticket(message, prefix) {
+                issues.push(format!(
+                    "Missing ticket reference (expected '{}-<number>').",
+                    prefix
+                ));
+            }
+        }
+
+        issues
+    }
+
+    fn check_conventional(&self, subject: &str) -> Result<(), String> {
+        let Some((header, description)) = subject.split_once(": ") else {
+            return Err(format!(
+                "'{}' is not a Conventional Commit (expected 'type(scope): description').",
+                subject
+            ));
+        };
+        if description.trim().is_empty() {
+            return Err("Conventional Commit description is empty.".to_string());
+        }
+
+        let header = header.strip_suffix('!').unwrap_or(header);
+        let commit_type = match header.split_once('(') {
+            Some((t, scope)) => {
+                if !scope.ends_with(')') || scope.len() < 2 {
+                    return Err(format!("Malformed scope in '{}'.", header));
+                }
+                t
+            }
+            None => header,
+        };
+
+        let allowed = self.allowed_types();
+        if !allowed.iter().any(|t| t == commit_type) {
+            return Err(format!(
+                "Commit type '{}' is not allowed (expected one of: {}).",
+                commit_type,
+                allowed.join(", ")
+            ));
+        }
+        Ok(())
+    }
+
+    fn allowed_types(&self) -> Vec<String> {
+        if self.types.is_empty() {
+            DEFAULT_TYPES.iter().map(|t| t.to_string()).collect()
+        } else {
+            self.types.clone()
+        }
+    }
+}
+
+impl PushPolicy {
+    pub fn is_protected(&self, branch: &str) -> bool {
+        self.protected.iter().any(|p| matches_branch(p, branch))
+    }
+}
+
+/// Matches a branch against a name or a `prefix*` pattern.
+fn matches_branch(pattern: &str, branch: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => branch.starts_with(prefix),
+        None => branch == pattern,
+    }
+}
+
+fn contains_ticket(message: &str, prefix: &str) -> bool {
+    let needle = format!("{}-", prefix);
+    message.match_indices(&needle).any(|(idx, _)| {
+        let boundary = message[..idx]
+            .chars()
+            .next_back()
+            .is_none_or(|c| !c.is_ascii_alphanumeric());
+        let digits = message[idx + needle.len()..]
+            .chars()
+            .take_while(|c| c.is_ascii_digit())
+            .count();
+        boundary && digits > 0
+    })
+}
+
+#[cfg(test)]
+mod tests;
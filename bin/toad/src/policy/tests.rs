@@ -0,0 +1,40 @@
+use super::*;
+
+#[test]
+fn test_conventional_commit_policy() {
+    let policy = CommitPolicy {
+        conventional: true,
+        ..Default::default()
+    };
+    assert!(policy.check("feat(git): add release command").is_empty());
+    assert!(policy.check("fix!: drop legacy home pointer").is_empty());
+    assert_eq!(policy.check("Added a thing").len(), 1);
+    assert_eq!(policy.check("feature: add a thing").len(), 1);
+    assert_eq!(policy.check("feat(git: add a thing").len(), 1);
+}
+
+#[test]
+fn test_ticket_prefix_policy() {
+    let policy = CommitPolicy {
+        ticket_prefix: Some("TOAD".to_string()),
+        ..Default::default()
+    };
+    assert!(policy.check("TOAD-42 fix stale registry").is_empty());
+    assert!(policy
+        .check("fix: stale registry\n\nRefs: TOAD-42")
+        .is_empty());
+    assert_eq!(policy.check("fix stale registry").len(), 1);
+    assert_eq!(policy.check("TOAD- fix").len(), 1);
+    assert_eq!(policy.check("XTOAD-42 fix").len(), 1);
+}
+
+#[test]
+fn test_protected_branch_patterns() {
+    let policy = PushPolicy {
+        protected: vec!["main".to_string(), "release/*".to_string()],
+    };
+    assert!(policy.is_protected("main"));
+    assert!(policy.is_protected("release/1.2"));
+    assert!(!policy.is_protected("maintenance"));
+    assert!(!policy.is_protected("feature/release"));
+}
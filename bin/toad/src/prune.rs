@@ -0,0 +1,248 @@
+//! Branch cleanup for `toad ggit prune-branches`.
+//!
+//! A branch is a candidate when it is fully merged into the repository's
+//! default branch or, with `--stale <days>`, when its last commit is older than
+//! the cutoff. The default branch, the current branch and any branch kept by
+//! the context policy are never candidates. Stale branches may hold unmerged
+//! work, so their tips are kept under `refs/toad/prune-backup/` before they
+//! are deleted.
+
+use crate::git;
+use crate::policy::ContextPolicy;
+use anyhow::Result;
+use std::path::Path;
+use toad_core::GitOpResult;
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Where the tips of deleted stale branches are kept.
+pub const BACKUP_PREFIX: &str = "refs/toad/prune-backup";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Reason {
+    Merged,
+    Stale(u64),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Candidate {
+    /// Branch name without the remote prefix.
+    pub branch: String,
+    /// Remote name for remote-tracking branches.
+    pub remote: Option<String>,
+    pub reason: Reason,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PrunePlan {
+    pub default_branch: Option<String>,
+    pub candidates: Vec<Candidate>,
+}
+
+impl Candidate {
+    pub fn display_name(&self) -> String {
+        match &self.remote {
+            Some(remote) => format!("{}/{}", remote, self.branch),
+            None => self.branch.clone(),
+        }
+    }
+
+    /// The ref holding the branch tip after [`apply`]; `None` for merged
+    /// branches, which are not backed up.
+    pub fn backup_ref(&self) -> Option<String> {
+        match self.reason {
+            Reason::Merged => None,
+            Reason::Stale(_) => Some(format!("{}/{}", BACKUP_PREFIX, self.display_name())),
+        }
+    }
+
+    /// The git command that brings a backed-up branch back.
+    pub fn restore_command(&self, path: &Path) -> Option<String> {
+        let backup = self.backup_ref()?;
+        Some(match &self.remote {
+            Some(remote) => format!(
+                "git -C {:?} push {} {}:refs/heads/{}",
+                path, remote, backup, self.branch
+            ),
+            None => format!("git -C {:?} branch {} {}", path, self.branch, backup),
+        })
+    }
+}
+
+/// The branch the remote's HEAD points at, falling back to a local
+/// `main` or `master`.
+pub fn default_branch(path: &Path) -> Option<String> {
+    let remote = git::default_remote(path).unwrap_or_else(|| "origin".to_string());
+    if let Some(head) = git::probe(
+        path,
+        &[
+            "symbolic-ref",
+            "--short",
+            "-q",
+            &format!("refs/remotes/{}/HEAD", remote),
+        ],
+    ) {
+        if let Some(branch) = head.strip_prefix(&format!("{}/", remote)) {
+            return Some(branch.to_string());
+        }
+    }
+    ["main", "master"]
+        .into_iter()
+        .find(|b| git::ref_exists(path, &format!("refs/heads/{}", b)))
+        .map(String::from)
+}
+
+/// Finds prunable branches. `now` is a unix timestamp so callers and tests
+/// agree on the staleness cutoff.
+pub fn plan(
+    path: &Path,
+    policy: &ContextPolicy,
+    stale_days: Option<u64>,
+    include_remote: bool,
+    now: u64,
+) -> PrunePlan {
+    let Some(default) = default_branch(path) else {
+        return PrunePlan::default();
+    };
+    let current = git::probe(path, &["symbolic-ref", "--short", "-q", "HEAD"]);
+    let merge_base = if git::ref_exists(path, &format!("refs/heads/{}", default)) {
+        format!("refs/heads/{}", default)
+    } else {
+        let remote = git::default_remote(path).unwrap_or_else(|| "origin".to_string());
+        format!("refs/remotes/{}/{}", remote, default)
+    };
+
+    let mut namespaces = vec!["refs/heads"];
+    if include_remote {
+        namespaces.push("refs/remotes");
+    }
+    let list = |extra: &[&str]| -> Vec<(String, u64)> {
+        let mut args = vec![
+            "for-each-ref",
+            "--format=%(refname)%09%(committerdate:unix)",
+        ];
+        args.extend(extra);
+        args.extend(&namespaces);
+        git::probe(path, &args)
+            .map(|out| {
+                out.lines()
+                    .filter_map(|l| {
+                        let (name, date) = l.split_once('\t')?;
+                        Some((name.to_string(), date.parse().unwrap_or(0)))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    let merged_flag = format!("--merged={}", merge_base);
+    let merged: Vec<String> = list(&[&merged_flag])
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect();
+
+    let mut candidates = Vec::new();
+    for (refname, date) in list(&[]) {
+        let (branch, remote) = match refname.strip_prefix("refs/heads/") {
+            Some(b) => (b.to_string(), None),
+            None => {
+                let rest = refname.trim_start_matches("refs/remotes/");
+                let Some((remote, b)) = rest.split_once('/') else {
+                    continue;
+                };
+                (b.to_string(), Some(remote.to_string()))
+            }
+        };
+        if branch == "HEAD"
+            || branch == default
+            || current.as_deref() == Some(branch.as_str())
+            || policy.keeps_branch(&branch)
+        {
+            continue;
+        }
+
+        let age_days = now.saturating_sub(date) / SECONDS_PER_DAY;
+        let reason = if merged.contains(&refname) {
+            Reason::Merged
+        } else if stale_days.is_some_and(|days| age_days >= days) {
+            Reason::Stale(age_days)
+        } else {
+            continue;
+        };
+        candidates.push(Candidate {
+            branch,
+            remote,
+            reason,
+        });
+    }
+
+    PrunePlan {
+        default_branch: Some(default),
+        candidates,
+    }
+}
+
+/// Deletes the planned branches: local ones with `branch -D`, remote ones on
+/// the remote itself so they do not come back on the next fetch. Stale
+/// branches are first backed up to [`Candidate::backup_ref`]; if that fails
+/// nothing is deleted.
+pub fn apply(path: &Path, name: &str, plan: &PrunePlan) -> Result<Vec<GitOpResult>> {
+    let mut results = Vec::new();
+
+    for c in &plan.candidates {
+        let Some(backup) = c.backup_ref() else {
+            continue;
+        };
+        let tip = match &c.remote {
+            Some(remote) => format!("refs/remotes/{}/{}", remote, c.branch),
+            None => format!("refs/heads/{}", c.branch),
+        };
+        let res = toad_git::run_git(
+            path,
+            &["update-ref", &backup, &tip],
+            &format!("{} (backup {})", name, c.display_name()),
+        )?;
+        let ok = res.success;
+        results.push(res);
+        if !ok {
+            return Ok(results);
+        }
+    }
+
+    let local: Vec<&str> = plan
+        .candidates
+        .iter()
+        .filter(|c| c.remote.is_none())
+        .map(|c| c.branch.as_str())
+        .collect();
+    if !local.is_empty() {
+        let mut args = vec!["branch", "-D"];
+        args.extend(&local);
+        results.push(toad_git::run_git(path, &args, name)?);
+    }
+
+    let mut remotes: Vec<&str> = plan
+        .candidates
+        .iter()
+        .filter_map(|c| c.remote.as_deref())
+        .collect();
+    remotes.sort();
+    remotes.dedup();
+    for remote in remotes {
+        let mut args = vec!["push", remote, "--delete"];
+        args.extend(
+            plan.candidates
+                .iter()
+                .filter(|c| c.remote.as_deref() == Some(remote))
+                .map(|c| c.branch.as_str()),
+        );
+        results.push(toad_git::run_git(
+            path,
+            &args,
+            &format!("{} ({})", name, remote),
+        )?);
+    }
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,82 @@
+use super::*;
+use crate::policy::PrunePolicy;
+use crate::test_support::{commit_with, git};
+use std::fs;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tempfile::tempdir;
+
+fn branch_with_commit(dir: &Path, branch: &str) {
+    git(dir, &["checkout", "-q", "-b", branch, "main"]);
+    let file = format!("{}.txt", branch.replace('/', "-"));
+    commit_with(dir, &file, branch, branch);
+}
+
+#[test]
+fn test_prune_plan_merged_stale_and_protected() {
+    let dir = tempdir().unwrap();
+    let repo = dir.path();
+    git(repo, &["init", "-q", "-b", "main"]);
+    fs::write(repo.join("README.md"), "pond").unwrap();
+    git(repo, &["add", "-A"]);
+    git(repo, &["commit", "-q", "-m", "init"]);
+
+    branch_with_commit(repo, "done");
+    branch_with_commit(repo, "open");
+    branch_with_commit(repo, "keep/this");
+    branch_with_commit(repo, "wip");
+    git(repo, &["checkout", "-q", "main"]);
+    git(repo, &["merge", "-q", "--no-edit", "done"]);
+    git(repo, &["checkout", "-q", "wip"]);
+
+    let policy = ContextPolicy {
+        prune: PrunePolicy {
+            keep: vec!["keep/*".to_string()],
+        },
+        ..Default::default()
+    };
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+
+    let fresh = plan(repo, &policy, None, false, now);
+    assert_eq!(fresh.default_branch.as_deref(), Some("main"));
+    assert_eq!(
+        fresh.candidates,
+        vec![Candidate {
+            branch: "done".to_string(),
+            remote: None,
+            reason: Reason::Merged,
+        }]
+    );
+
+    // A year later `open` is stale; `wip` is checked out and `keep/this` is kept.
+    let later = plan(repo, &policy, Some(90), false, now + 365 * SECONDS_PER_DAY);
+    let names: Vec<_> = later.candidates.iter().map(|c| c.display_name()).collect();
+    assert_eq!(names, vec!["done", "open"]);
+    assert!(matches!(later.candidates[1].reason, Reason::Stale(days) if days >= 365));
+
+    assert_eq!(later.candidates[0].backup_ref(), None);
+    assert_eq!(
+        later.candidates[1].backup_ref().as_deref(),
+        Some("refs/toad/prune-backup/open")
+    );
+    let open_tip = git::probe(repo, &["rev-parse", "refs/heads/open"]);
+
+    let results = apply(repo, "pond", &later).unwrap();
+    assert!(results.iter().all(|r| r.success));
+    assert!(!git::ref_exists(repo, "refs/heads/done"));
+    assert!(!git::ref_exists(repo, "refs/heads/open"));
+    assert!(git::ref_exists(repo, "refs/heads/keep/this"));
+    // Only the unmerged branch is kept, and it can be brought back.
+    assert!(!git::ref_exists(repo, "refs/toad/prune-backup/done"));
+    assert_eq!(
+        git::probe(repo, &["rev-parse", "refs/toad/prune-backup/open"]),
+        open_tip
+    );
+    git(repo, &["branch", "open", "refs/toad/prune-backup/open"]);
+    assert_eq!(
+        git::probe(repo, &["rev-parse", "refs/heads/open"]),
+        open_tip
+    );
+}
@@ -0,0 +1,136 @@
+//! Push preflight for `toad ggit push`.
+//!
+//! Every repository goes through the same safety checks as `ggit sync`, plus
+//! push-specific ones, before anything is published: protected branches,
+//! diverged upstreams and forced push refspecs block the push, missing
+//! upstreams only warn, and the outgoing commits are listed.
+
+use crate::git;
+use crate::policy::PushPolicy;
+use anyhow::Result;
+use std::path::Path;
+use toad_core::PreflightResult;
+
+/// What a push would publish from one repository.
+#[derive(Debug, Clone, Default)]
+pub struct Outgoing {
+    pub branch: Option<String>,
+    /// Upstream such as `origin/main`; `None` if the branch does not track one.
+    pub upstream: Option<String>,
+    /// `%h %s` lines for the commits the push would publish.
+    pub commits: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+impl Outgoing {
+    /// Nothing to publish: the branch tracks an upstream that already has every commit.
+    pub fn is_up_to_date(&self) -> bool {
+        self.upstream.is_some() && self.commits.is_empty()
+    }
+}
+
+/// Runs `toad_git`'s sync preflight for a repository and adds the push
+/// checks to its issues. Refreshes the upstream's remote-tracking refs first
+/// so divergence is judged against the remote.
+pub fn preflight(
+    path: &Path,
+    name: &str,
+    parent: Option<&Path>,
+    submodule: Option<&Path>,
+    policy: &PushPolicy,
+) -> Result<(PreflightResult, Outgoing)> {
+    let mut result = toad_git::sync::preflight_check(path, name, parent, submodule)?;
+    let outgoing = inspect(path, policy, &mut result.issues);
+    Ok((result, outgoing))
+}
+
+fn inspect(path: &Path, policy: &PushPolicy, issues: &mut Vec<String>) -> Outgoing {
+    let mut outgoing = Outgoing::default();
+
+    let Some(branch) = git::probe(path, &["symbolic-ref", "--short", "-q", "HEAD"]) else {
+        issues.push("Detached HEAD — check out a branch before pushing".to_string());
+        return outgoing;
+    };
+
+    let upstream = git::probe(
+        path,
+        &["rev-parse", "--abbrev-ref", "--symbolic-full-name", "@{u}"],
+    );
+    let remote = git::probe(path, &["config", &format!("branch.{}.remote", branch)])
+        .or_else(|| git::default_remote(path));
+
+    if let Some(remote) = &remote {
+        let _ = git::probe(path, &["fetch", "--quiet", remote]);
+        if git::probe(
+            path,
+            &["config", "--bool", &format!("remote.{}.mirror", remote)],
+        )
+        .as_deref()
+            == Some("true")
+        {
+            issues.push(format!(
+                "Remote '{}' is a mirror; pushing would force-overwrite every ref",
+                remote
+            ));
+        }
+        let forced = git::probe(
+            path,
+            &["config", "--get-all", &format!("remote.{}.push", remote)],
+        )
+        .map(|out| out.lines().any(|refspec| refspec.starts_with('+')))
+        .unwrap_or(false);
+        if forced {
+            issues.push(format!(
+                "Remote '{}' has a forced push refspec ('+'); force pushes are not allowed",
+                remote
+            ));
+        }
+    } else {
+        issues.push("No remote configured".to_string());
+    }
+
+    let log_range = match &upstream {
+        Some(up) => {
+            let behind = git::probe(path, &["rev-list", "--count", &format!("HEAD..{}", up)])
+                .and_then(|n| n.parse::<usize>().ok())
+                .unwrap_or(0);
+            if behind > 0 {
+                issues.push(format!(
+                    "Non-fast-forward — {} is {} commits ahead; pull or rebase first",
+                    up, behind
+                ));
+            }
+            vec![format!("{}..HEAD", up)]
+        }
+        None => {
+            outgoing
+                .warnings
+                .push(format!("Branch '{}' has no upstream branch set", branch));
+            vec![
+                "HEAD".to_string(),
+                "--not".to_string(),
+                "--remotes".to_string(),
+            ]
+        }
+    };
+
+    let mut args = vec!["log", "--format=%h %s"];
+    args.extend(log_range.iter().map(String::as_str));
+    outgoing.commits = git::probe(path, &args)
+        .map(|out| out.lines().map(String::from).collect())
+        .unwrap_or_default();
+
+    if policy.is_protected(&branch) && !outgoing.commits.is_empty() {
+        issues.push(format!(
+            "'{}' is a protected branch — push a feature branch and open a pull request",
+            branch
+        ));
+    }
+
+    outgoing.branch = Some(branch);
+    outgoing.upstream = upstream;
+    outgoing
+}
+
+#[cfg(test)]
+mod tests;
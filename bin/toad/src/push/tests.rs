@@ -0,0 +1,77 @@
+use super::*;
+use crate::test_support::{commit_with, git};
+use tempfile::tempdir;
+
+fn commit(dir: &Path, file: &str, msg: &str) {
+    commit_with(dir, file, msg, msg);
+}
+
+fn check(path: &Path, policy: &PushPolicy) -> (PreflightResult, Outgoing) {
+    preflight(path, "work", None, None, policy).unwrap()
+}
+
+#[test]
+fn test_push_preflight_outgoing_protected_and_diverged() {
+    let dir = tempdir().unwrap();
+    let remote = dir.path().join("remote.git");
+    let work = dir.path().join("work");
+    let other = dir.path().join("other");
+    git(
+        dir.path(),
+        &["init", "-q", "--bare", "-b", "main", "remote.git"],
+    );
+    git(
+        dir.path(),
+        &["clone", "-q", remote.to_str().unwrap(), "work"],
+    );
+    commit(&work, "a.txt", "init");
+    git(&work, &["push", "-q", "-u", "origin", "main"]);
+
+    let open = PushPolicy::default();
+    let protected = PushPolicy {
+        protected: vec!["main".to_string()],
+    };
+
+    // Up to date: nothing to publish, even on a protected branch.
+    let (res, out) = check(&work, &protected);
+    assert!(res.issues.is_empty());
+    assert!(out.is_up_to_date());
+
+    commit(&work, "b.txt", "add b");
+    let (res, out) = check(&work, &open);
+    assert!(res.issues.is_empty());
+    assert_eq!(out.commits.len(), 1);
+    assert!(out.commits[0].ends_with("add b"));
+    assert_eq!(check(&work, &protected).0.issues.len(), 1);
+
+    // Someone else pushes first: our push is no longer a fast-forward.
+    git(
+        dir.path(),
+        &["clone", "-q", remote.to_str().unwrap(), "other"],
+    );
+    commit(&other, "c.txt", "add c");
+    git(&other, &["push", "-q", "origin", "main"]);
+    let (res, _) = check(&work, &open);
+    assert!(res.issues.iter().any(|i| i.contains("Non-fast-forward")));
+
+    // New branches have no upstream yet and only warn.
+    git(&work, &["checkout", "-q", "-b", "feature"]);
+    let (_, out) = check(&work, &open);
+    assert!(out.upstream.is_none());
+    assert_eq!(out.warnings.len(), 1);
+    assert_eq!(out.commits.len(), 1);
+}
+
+#[test]
+fn test_push_preflight_detached_head() {
+    let dir = tempdir().unwrap();
+    git(dir.path(), &["init", "-q", "-b", "main"]);
+    commit(dir.path(), "a.txt", "init");
+    git(dir.path(), &["checkout", "-q", "--detach"]);
+
+    let (res, out) = check(dir.path(), &PushPolicy::default());
+    assert!(res
+        .issues
+        .contains(&"Detached HEAD — check out a branch before pushing".to_string()));
+    assert!(out.branch.is_none());
+}
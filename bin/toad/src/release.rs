@@ -0,0 +1,567 @@
+//! Coordinated version bumps for `toad ggit release`.
+//!
+//! Manifests are rewritten line by line rather than re-serialized so that
+//! comments, key order, formatting and line endings survive the bump.
+
+use crate::git;
+use crate::txn;
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const MANIFEST_NAMES: [&str; 3] = ["Cargo.toml", "package.json", "pyproject.toml"];
+const JSON_DEP_BLOCKS: [&str; 4] = [
+    "dependencies",
+    "devDependencies",
+    "peerDependencies",
+    "optionalDependencies",
+];
+
+/// A single version string that will change.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VersionChange {
+    /// `package` for the manifest's own version, otherwise the dependency name.
+    pub item: String,
+    pub from: String,
+    pub to: String,
+}
+
+/// The rewritten content of one manifest file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ManifestEdit {
+    /// Path relative to the repository root.
+    pub file: PathBuf,
+    pub changes: Vec<VersionChange>,
+    pub content: String,
+}
+
+/// Everything the release will do to one repository.
+#[derive(Debug, Clone)]
+pub struct RepoRelease {
+    pub name: String,
+    pub path: PathBuf,
+    pub edits: Vec<ManifestEdit>,
+    pub tag_exists: bool,
+}
+
+/// Strips a leading `v` and validates the semantic version.
+pub fn normalize_version(input: &str) -> Result<String> {
+    let version = input.trim().trim_start_matches('v');
+    let core = version.split(['-', '+']).next().unwrap_or_default();
+    let parts: Vec<_> = core.split('.').collect();
+    if parts.len() != 3
+        || parts
+            .iter()
+            .any(|p| p.is_empty() || !p.chars().all(|c| c.is_ascii_digit()))
+    {
+        bail!(
+            "Invalid version '{}'. Expected a semantic version such as 1.2.0.",
+            input
+        );
+    }
+    Ok(version.to_string())
+}
+
+pub fn tag_name(version: &str) -> String {
+    format!("v{}", version)
+}
+
+/// Tracked manifests in the repository (submodule contents are excluded by git).
+pub fn manifest_files(repo: &Path) -> Vec<PathBuf> {
+    let mut specs = Vec::new();
+    for name in MANIFEST_NAMES {
+        specs.push(name.to_string());
+        specs.push(format!("*/{}", name));
+    }
+    let mut args = vec!["ls-files", "--"];
+    args.extend(specs.iter().map(|s| s.as_str()));
+
+    match git::probe(repo, &args) {
+        Some(out) => out
+            .lines()
+            .filter(|l| !l.contains("node_modules/"))
+            .map(PathBuf::from)
+            .collect(),
+        None => MANIFEST_NAMES
+            .iter()
+            .map(PathBuf::from)
+            .filter(|f| repo.join(f).exists())
+            .collect(),
+    }
+}
+
+/// Package names declared by the repository's manifests.
+pub fn package_names(repo: &Path) -> Vec<String> {
+    let mut names = Vec::new();
+    for file in manifest_files(repo) {
+        let Ok(content) = fs::read_to_string(repo.join(&file)) else {
+            continue;
+        };
+        let name = match file.file_name().and_then(|n| n.to_str()) {
+            Some("package.json") => serde_json::from_str::<serde_json::Value>(&content)
+                .ok()
+                .and_then(|v| v.get("name")?.as_str().map(String::from)),
+            Some(_) => content.parse::<toml::Table>().ok().and_then(|t| {
+                ["package", "project"]
+                    .iter()
+                    .find_map(|s| t.get(*s)?.get("name")?.as_str().map(String::from))
+                    .or_else(|| {
+                        t.get("tool")?
+                            .get("poetry")?
+                            .get("name")?
+                            .as_str()
+                            .map(String::from)
+                    })
+            }),
+            None => None,
+        };
+        names.extend(name);
+    }
+    names
+}
+
+/// Computes the manifest rewrites for one repository without touching disk.
+pub fn plan_repo(
+    name: &str,
+    path: &Path,
+    version: &str,
+    ecosystem: &HashSet<String>,
+) -> Result<RepoRelease> {
+    let mut edits = Vec::new();
+    for file in manifest_files(path) {
+        let content = fs::read_to_string(path.join(&file))?;
+        let (new_content, changes) = match file.file_name().and_then(|n| n.to_str()) {
+            Some("Cargo.toml") => bump_toml(&content, version, ecosystem, &CARGO_SECTIONS),
+            Some("pyproject.toml") => bump_toml(&content, version, ecosystem, &PYPROJECT_SECTIONS),
+            Some("package.json") => bump_package_json(&content, version, ecosystem),
+            _ => continue,
+        };
+        if !changes.is_empty() {
+            edits.push(ManifestEdit {
+                file,
+                changes,
+                content: new_content,
+            });
+        }
+    }
+
+    Ok(RepoRelease {
+        name: name.to_string(),
+        path: path.to_path_buf(),
+        edits,
+        tag_exists: git::ref_exists(path, &format!("refs/tags/{}", tag_name(version))),
+    })
+}
+
+/// Plans the release of every repository, dependencies first. Ecosystem
+/// packages are those any of `repos` publishes. Returns the plans and the
+/// reasons the release cannot go ahead.
+pub fn plan(repos: &[(String, PathBuf)], version: &str) -> Result<(Vec<RepoRelease>, Vec<String>)> {
+    let ecosystem: HashSet<String> = repos
+        .iter()
+        .flat_map(|(_, path)| package_names(path))
+        .collect();
+    let mut plans = Vec::new();
+    let mut blockers = Vec::new();
+    for (name, path) in repos {
+        let plan = plan_repo(name, path, version, &ecosystem)?;
+        if plan.tag_exists {
+            blockers.push(format!(
+                "{}: tag {} already exists",
+                name,
+                tag_name(version)
+            ));
+        }
+        if toad_git::commit::is_dirty(path)? {
+            blockers.push(format!("{}: uncommitted changes", name));
+        }
+        plans.push(plan);
+    }
+    Ok((plans, blockers))
+}
+
+/// The journaled release: a commit and a tag per repository, in plan order.
+/// Each release commit waits for every repository before it, so a failed
+/// child is never captured by its parent's pointer.
+pub fn journal(plans: &[RepoRelease], tag: &str, message: &str) -> txn::Journal {
+    let mut journal =
+        txn::Journal::new(&format!("ggit release {}", tag), txn::ResetMode::Keep, true);
+    let mut released = Vec::new();
+    for plan in plans {
+        let step = journal.push_step(
+            &plan.name,
+            &plan.path,
+            txn::Action::Release {
+                message: message.to_string(),
+                edits: plan.edits.clone(),
+            },
+            released.clone(),
+        );
+        journal.push_step(
+            &plan.name,
+            &plan.path,
+            txn::Action::Tag {
+                name: tag.to_string(),
+                message: message.to_string(),
+            },
+            vec![step],
+        );
+        released.push(step);
+    }
+    journal
+}
+
+/// Writes planned manifest rewrites into the repository at `repo`.
+pub fn apply(repo: &Path, edits: &[ManifestEdit]) -> Result<()> {
+    for edit in edits {
+        fs::write(repo.join(&edit.file), &edit.content)?;
+    }
+    Ok(())
+}
+
+/// Which TOML sections hold the package version and which hold dependencies.
+struct TomlSections {
+    package: &'static [&'static str],
+    dependencies: fn(&str) -> bool,
+    /// `(section, key)` of arrays of PEP 508 requirement strings.
+    requirement_arrays: fn(&str, &str) -> bool,
+}
+
+const CARGO_SECTIONS: TomlSections = TomlSections {
+    package: &["package", "workspace.package"],
+    dependencies: |s| s.ends_with("dependencies"),
+    requirement_arrays: |_, _| false,
+};
+
+const PYPROJECT_SECTIONS: TomlSections = TomlSections {
+    package: &["project", "tool.poetry"],
+    dependencies: |s| s.starts_with("tool.poetry") && s.ends_with("dependencies"),
+    // PEP 621: `[project] dependencies = [...]` and its optional groups.
+    requirement_arrays: |s, key| {
+        (s == "project" && key == "dependencies") || s == "project.optional-dependencies"
+    },
+};
+
+fn bump_toml(
+    content: &str,
+    version: &str,
+    ecosystem: &HashSet<String>,
+    sections: &TomlSections,
+) -> (String, Vec<VersionChange>) {
+    let (bodies, endings) = split_lines(content);
+    let mut section = String::new();
+    let mut lines = Vec::new();
+    let mut changes = Vec::new();
+    let mut in_array = false;
+
+    for line in bodies {
+        let trimmed = line.trim();
+        if in_array {
+            let (rewritten, closed) = bump_requirements(line, version, ecosystem, &mut changes);
+            in_array = !closed;
+            lines.push(rewritten);
+            continue;
+        }
+        if trimmed.starts_with('[') {
+            section = trimmed
+                .trim_matches(|c| c == '[' || c == ']')
+                .trim()
+                .to_string();
+            lines.push(line.to_string());
+            continue;
+        }
+
+        let Some((key, _)) = trimmed.split_once('=') else {
+            lines.push(line.to_string());
+            continue;
+        };
+        let key = key.trim().trim_matches('"');
+
+        if (sections.requirement_arrays)(&section, key) {
+            let at = line.find('=').map_or(0, |i| i + 1);
+            let (value, closed) = bump_requirements(&line[at..], version, ecosystem, &mut changes);
+            in_array = !closed;
+            lines.push(format!("{}{}", &line[..at], value));
+            continue;
+        }
+
+        // `[dependencies.toad-core]` style tables carry the dependency in the header.
+        let dotted_dep = section
+            .rsplit_once("dependencies.")
+            .map(|(_, dep)| dep.trim_matches('"'))
+            .filter(|dep| ecosystem.contains(*dep) && key == "version");
+
+        let target = if sections.package.contains(&section.as_str()) && key == "version" {
+            Some(("package".to_string(), false))
+        } else if let Some(dep) = dotted_dep {
+            Some((dep.to_string(), true))
+        } else if (sections.dependencies)(&section) && ecosystem.contains(key) {
+            Some((key.to_string(), true))
+        } else {
+            None
+        };
+
+        let rewritten = target.and_then(|(item, is_dep)| {
+            let (start, end) = locate_toml_version(line, is_dep && key != "version")?;
+            let old = &line[start..end];
+            let new_value = if is_dep {
+                rewrite_requirement(old, version)?
+            } else {
+                version.to_string()
+            };
+            if new_value == old {
+                return None;
+            }
+            changes.push(VersionChange {
+                item,
+                from: old.to_string(),
+                to: new_value.clone(),
+            });
+            Some(format!("{}{}{}", &line[..start], new_value, &line[end..]))
+        });
+        lines.push(rewritten.unwrap_or_else(|| line.to_string()));
+    }
+
+    (join_lines(lines, &endings), changes)
+}
+
+/// Rewrites the ecosystem requirements among the strings of one line of a
+/// requirement array. Also reports whether the array closes on this line.
+fn bump_requirements(
+    line: &str,
+    version: &str,
+    ecosystem: &HashSet<String>,
+    changes: &mut Vec<VersionChange>,
+) -> (String, bool) {
+    let mut out = String::new();
+    let mut rest = line;
+    while let Some(open) = rest.find(['"', ']']) {
+        out.push_str(&rest[..open]);
+        if rest[open..].starts_with(']') {
+            out.push_str(&rest[open..]);
+            return (out, true);
+        }
+        let body = &rest[open + 1..];
+        let Some(len) = body.find('"') else {
+            break;
+        };
+        let requirement = &body[..len];
+        let new = match bump_pep508(requirement, version, ecosystem) {
+            Some((item, from, to)) => {
+                let rewritten = requirement.replacen(&from, &to, 1);
+                changes.push(VersionChange { item, from, to });
+                rewritten
+            }
+            None => requirement.to_string(),
+        };
+        out.push('"');
+        out.push_str(&new);
+        out.push('"');
+        rest = &body[len + 1..];
+    }
+    out.push_str(rest);
+    (out, false)
+}
+
+/// `(name, old specifier, new specifier)` for a PEP 508 requirement such as
+/// `lily[cli]==1.0.0; python_version >= "3.9"` on an ecosystem package.
+fn bump_pep508(
+    requirement: &str,
+    version: &str,
+    ecosystem: &HashSet<String>,
+) -> Option<(String, String, String)> {
+    let normalize = |n: &str| n.to_lowercase().replace('_', "-");
+    let name: String = requirement
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        .collect();
+    if !ecosystem.iter().any(|e| normalize(e) == normalize(&name)) {
+        return None;
+    }
+    let rest = requirement[name.len()..].trim_start();
+    let rest = match rest.strip_prefix('[') {
+        Some(extras) => &extras[extras.find(']')? + 1..],
+        None => rest,
+    };
+    let spec = rest.split(';').next().unwrap_or_default().trim();
+    if spec.is_empty() || spec.contains(',') {
+        return None;
+    }
+    let new = rewrite_requirement(spec, version)?;
+    (new != spec).then(|| (name, spec.to_string(), new))
+}
+
+/// Byte range of the version string on a `key = "x"` or
+/// `key = { version = "x" }` line.
+fn locate_toml_version(line: &str, inline_dep: bool) -> Option<(usize, usize)> {
+    let (key, value) = line.split_once('=')?;
+    let value_offset = key.len() + 1;
+
+    let quote = if inline_dep && value.trim_start().starts_with('{') {
+        value_offset + find_version_key(value)?
+    } else {
+        value_offset + value.find('"')?
+    };
+    let start = quote + 1;
+    let end = start + line[start..].find('"')?;
+    Some((start, end))
+}
+
+/// Finds the opening quote of `version = "..."` inside an inline table.
+fn find_version_key(value: &str) -> Option<usize> {
+    let mut search = 0;
+    while let Some(pos) = value[search..].find("version") {
+        let after = search + pos + "version".len();
+        let rest = value[after..].trim_start();
+        if let Some(rest) = rest.strip_prefix('=') {
+            let quote = rest.trim_start();
+            if quote.starts_with('"') {
+                return Some(value.len() - quote.len());
+            }
+        }
+        search = after;
+    }
+    None
+}
+
+fn bump_package_json(
+    content: &str,
+    version: &str,
+    ecosystem: &HashSet<String>,
+) -> (String, Vec<VersionChange>) {
+    let (bodies, endings) = split_lines(content);
+    let mut depth = 0usize;
+    let mut in_dep_block = false;
+    let mut lines = Vec::new();
+    let mut changes = Vec::new();
+
+    for line in bodies {
+        let mut new_line = line.to_string();
+        if let Some((key, old, start, end)) = json_string_pair(line) {
+            let item = if depth == 1 && key == "version" {
+                Some(("package".to_string(), version.to_string()))
+            } else if depth == 2 && in_dep_block && ecosystem.contains(&key) {
+                rewrite_requirement(&old, version).map(|req| (key.clone(), req))
+            } else {
+                None
+            };
+            if let Some((item, new_value)) = item {
+                if new_value != old {
+                    new_line = format!("{}{}{}", &line[..start], new_value, &line[end..]);
+                    changes.push(VersionChange {
+                        item,
+                        from: old,
+                        to: new_value,
+                    });
+                }
+            }
+        }
+
+        let opens_dep_block = depth == 1
+            && json_object_key(line).is_some_and(|k| JSON_DEP_BLOCKS.contains(&k.as_str()));
+        depth = apply_brace_delta(depth, line);
+        if depth <= 1 {
+            in_dep_block = false;
+        } else if opens_dep_block && depth == 2 {
+            in_dep_block = true;
+        }
+        lines.push(new_line);
+    }
+
+    (join_lines(lines, &endings), changes)
+}
+
+/// Parses `"key": "value"` and returns the key, the value and the value's byte range.
+fn json_string_pair(line: &str) -> Option<(String, String, usize, usize)> {
+    let key_start = line.find('"')? + 1;
+    let key_end = key_start + line[key_start..].find('"')?;
+    let rest = &line[key_end + 1..];
+    let colon = rest.find(':')?;
+    let after_colon = &rest[colon + 1..];
+    let value_rel = after_colon.find('"')?;
+    if !after_colon[..value_rel].trim().is_empty() {
+        return None;
+    }
+    let start = key_end + 1 + colon + 1 + value_rel + 1;
+    let end = start + line[start..].find('"')?;
+    Some((
+        line[key_start..key_end].to_string(),
+        line[start..end].to_string(),
+        start,
+        end,
+    ))
+}
+
+/// Returns the key of a `"key": {` line.
+fn json_object_key(line: &str) -> Option<String> {
+    let trimmed = line.trim();
+    let rest = trimmed.strip_prefix('"')?;
+    let (key, after) = rest.split_once('"')?;
+    after
+        .trim_start()
+        .strip_prefix(':')?
+        .trim_start()
+        .starts_with('{')
+        .then(|| key.to_string())
+}
+
+fn apply_brace_delta(depth: usize, line: &str) -> usize {
+    let mut depth = depth as isize;
+    let mut in_string = false;
+    let mut escaped = false;
+    for c in line.chars() {
+        match c {
+            '\\' if in_string => {
+                escaped = !escaped;
+                continue;
+            }
+            '"' if !escaped => in_string = !in_string,
+            '{' if !in_string => depth += 1,
+            '}' if !in_string => depth -= 1,
+            _ => {}
+        }
+        escaped = false;
+    }
+    depth.max(0) as usize
+}
+
+/// Keeps the requirement operator (`^`, `~`, `=`, `>=`) and swaps the version.
+/// Complex requirements and non-version specifiers (`workspace:*`) are left alone.
+fn rewrite_requirement(old: &str, version: &str) -> Option<String> {
+    let operator: String = old
+        .chars()
+        .take_while(|c| matches!(c, '^' | '~' | '=' | '>' | '<' | '!' | ' '))
+        .collect();
+    let rest = &old[operator.len()..];
+    let is_plain = rest.starts_with(|c: char| c.is_ascii_digit())
+        && rest
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '+'));
+    is_plain.then(|| format!("{}{}", operator, version))
+}
+
+/// Splits `content` into lines and the ending each one had (`\n`, `\r\n`, or
+/// nothing on an unterminated last line).
+fn split_lines(content: &str) -> (Vec<&str>, Vec<&str>) {
+    content
+        .split_inclusive('\n')
+        .map(|line| {
+            let body = line.strip_suffix('\n').unwrap_or(line);
+            let body = body.strip_suffix('\r').unwrap_or(body);
+            (body, &line[body.len()..])
+        })
+        .unzip()
+}
+
+fn join_lines(lines: Vec<String>, endings: &[&str]) -> String {
+    lines
+        .iter()
+        .zip(endings)
+        .map(|(line, ending)| format!("{}{}", line, ending))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,162 @@
+use super::*;
+
+fn eco(names: &[&str]) -> HashSet<String> {
+    names.iter().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn test_normalize_version() {
+    assert_eq!(normalize_version("v1.2.0").unwrap(), "1.2.0");
+    assert_eq!(normalize_version("1.2.0-rc.1").unwrap(), "1.2.0-rc.1");
+    assert!(normalize_version("1.2").is_err());
+    assert!(normalize_version("one.two.three").is_err());
+}
+
+#[test]
+fn test_bump_cargo_package_and_ecosystem_deps() {
+    let content = r#"[package]
+name = "toad"
+version = "1.0.2"
+rust-version = "1.75"
+
+[dependencies]
+anyhow = "1.0.100"
+toad-core = { version = "1.0.2", path = "../../crates/toad-core" }
+toad-git = "^1.0.2"
+
+[dependencies.toad-ops]
+version = "1.0.2"
+path = "../../crates/toad-ops"
+"#;
+    let (out, changes) = bump_toml(
+        content,
+        "1.1.0",
+        &eco(&["toad-core", "toad-git", "toad-ops"]),
+        &CARGO_SECTIONS,
+    );
+
+    assert!(out.contains("version = \"1.1.0\"\nrust-version = \"1.75\""));
+    assert!(out.contains("toad-core = { version = \"1.1.0\", path = \"../../crates/toad-core\" }"));
+    assert!(out.contains("toad-git = \"^1.1.0\""));
+    assert!(out.contains("anyhow = \"1.0.100\""));
+    assert!(out.contains("[dependencies.toad-ops]\nversion = \"1.1.0\""));
+    assert!(out.ends_with('\n'));
+    assert_eq!(changes.len(), 4);
+    assert_eq!(changes[0].item, "package");
+}
+
+#[test]
+fn test_bump_package_json_keeps_operators() {
+    let content = r#"{
+  "name": "@pond/web",
+  "version": "0.3.0",
+  "scripts": { "build": "vite build" },
+  "dependencies": {
+    "@pond/ui": "^0.3.0",
+    "react": "^18.2.0",
+    "@pond/shared": "workspace:*"
+  }
+}
+"#;
+    let (out, changes) = bump_package_json(content, "0.4.0", &eco(&["@pond/ui", "@pond/shared"]));
+
+    assert!(out.contains("\"version\": \"0.4.0\""));
+    assert!(out.contains("\"@pond/ui\": \"^0.4.0\""));
+    assert!(out.contains("\"react\": \"^18.2.0\""));
+    assert!(out.contains("\"@pond/shared\": \"workspace:*\""));
+    assert_eq!(changes.len(), 2);
+}
+
+#[test]
+fn test_bump_pyproject_project_version() {
+    let content =
+        "[project]\nname = \"lily\"\nversion = \"2.0.0\"\n\n[tool.black]\nversion = \"x\"\n";
+    let (out, changes) = bump_toml(content, "2.1.0", &eco(&[]), &PYPROJECT_SECTIONS);
+
+    assert!(out.contains("[project]\nname = \"lily\"\nversion = \"2.1.0\""));
+    assert!(out.contains("[tool.black]\nversion = \"x\""));
+    assert_eq!(changes.len(), 1);
+}
+
+#[test]
+fn test_bump_pyproject_pep621_requirement_arrays() {
+    let content = r#"[project]
+name = "lily"
+version = "2.0.0"
+dependencies = [
+    "toad_core==2.0.0",
+    "requests>=2.31",
+    "pond-ui[cli]~=2.0.0; python_version >= '3.9'",
+]
+
+[project.optional-dependencies]
+dev = ["pond-test>=2.0.0,<3", "pond-lint>=2.0.0"]
+"#;
+    let ecosystem = eco(&["toad-core", "pond-ui", "pond-test", "pond-lint"]);
+    let (out, changes) = bump_toml(content, "2.1.0", &ecosystem, &PYPROJECT_SECTIONS);
+
+    assert!(out.contains("version = \"2.1.0\""));
+    assert!(out.contains("\"toad_core==2.1.0\","));
+    assert!(out.contains("\"requests>=2.31\","));
+    assert!(out.contains("\"pond-ui[cli]~=2.1.0; python_version >= '3.9'\","));
+    // Ranges are left for a human to adjust.
+    assert!(out.contains("dev = [\"pond-test>=2.0.0,<3\", \"pond-lint>=2.1.0\"]"));
+    assert_eq!(changes.len(), 4);
+}
+
+#[test]
+fn test_bump_preserves_line_endings() {
+    let content = "[package]\r\nname = \"lily\"\r\nversion = \"1.0.0\"\r\n";
+    let (out, _) = bump_toml(content, "1.1.0", &eco(&[]), &CARGO_SECTIONS);
+    assert_eq!(
+        out,
+        "[package]\r\nname = \"lily\"\r\nversion = \"1.1.0\"\r\n"
+    );
+
+    let content = "{\n  \"version\": \"1.0.0\"\n}";
+    let (out, _) = bump_package_json(content, "1.1.0", &eco(&[]));
+    assert_eq!(out, "{\n  \"version\": \"1.1.0\"\n}");
+}
+
+#[test]
+fn test_plan_blocks_and_journals_dependencies_first() {
+    let dir = tempfile::tempdir().unwrap();
+    let core = crate::test_support::repo(dir.path(), "core");
+    fs::write(
+        core.join("Cargo.toml"),
+        "[package]\nname = \"core\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+    crate::test_support::git(&core, &["add", "-A"]);
+    crate::test_support::git(&core, &["commit", "-q", "-m", "manifest"]);
+    let app = crate::test_support::repo(dir.path(), "app");
+    fs::write(
+        app.join("Cargo.toml"),
+        "[package]\nname = \"app\"\nversion = \"0.1.0\"\n\n[dependencies]\ncore = \"0.1.0\"\n",
+    )
+    .unwrap();
+    crate::test_support::git(&app, &["add", "-A"]);
+    crate::test_support::git(&app, &["commit", "-q", "-m", "manifest"]);
+    fs::write(app.join("Cargo.toml"), "[package]\nname = \"app\"\nversion = \"0.1.0\"\n\n[dependencies]\ncore = \"0.1.0\"\nserde = \"1\"\n").unwrap();
+
+    let repos = vec![("core".to_string(), core), ("app".to_string(), app)];
+    let (plans, blockers) = plan(&repos, "0.2.0").unwrap();
+    assert_eq!(blockers, vec!["app: uncommitted changes".to_string()]);
+    assert_eq!(plans[1].edits[0].changes.len(), 2);
+
+    let journal = journal(&plans, "v0.2.0", "chore(release): v0.2.0");
+    let steps: Vec<_> = journal
+        .steps
+        .iter()
+        .map(|s| (s.name.as_str(), s.depends_on.clone()))
+        .collect();
+    assert_eq!(
+        steps,
+        vec![
+            ("core", vec![]),
+            ("core", vec![0]),
+            ("app", vec![0]),
+            ("app", vec![2])
+        ]
+    );
+}
@@ -0,0 +1,169 @@
+//! Bulk remote management for `toad ggit remote`.
+//!
+//! URL rewrites are planned first so they can be shown as a table, then
+//! applied to `git remote` entries and to `.gitmodules` URLs, after which the
+//! submodule configuration is re-synced.
+
+use crate::git;
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+use toad_core::GitOpResult;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RemoteEntry {
+    pub name: String,
+    pub url: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum UrlTarget {
+    /// A configured `git remote`.
+    Remote(String),
+    /// A `submodule.<name>.url` entry in `.gitmodules`.
+    Submodule(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct UrlChange {
+    pub repo: String,
+    pub path: PathBuf,
+    pub target: UrlTarget,
+    pub old: String,
+    pub new: String,
+}
+
+impl UrlChange {
+    pub fn target_label(&self) -> String {
+        match &self.target {
+            UrlTarget::Remote(name) => name.clone(),
+            UrlTarget::Submodule(name) => format!(".gitmodules:{}", name),
+        }
+    }
+}
+
+/// Remotes configured in a repository, in `git remote` order.
+pub fn list(path: &Path) -> Vec<RemoteEntry> {
+    git::probe(path, &["remote"])
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|name| {
+            let url = git::probe(path, &["remote", "get-url", name])?;
+            Some(RemoteEntry {
+                name: name.to_string(),
+                url,
+            })
+        })
+        .collect()
+}
+
+/// Submodule names and URLs declared in `.gitmodules`.
+pub fn gitmodules_urls(path: &Path) -> Vec<(String, String)> {
+    if !path.join(".gitmodules").exists() {
+        return Vec::new();
+    }
+    git::probe(
+        path,
+        &[
+            "config",
+            "-f",
+            ".gitmodules",
+            "--get-regexp",
+            r"^submodule\..*\.url$",
+        ],
+    )
+    .unwrap_or_default()
+    .lines()
+    .filter_map(|line| {
+        let (key, url) = line.split_once(' ')?;
+        let name = key.strip_prefix("submodule.")?.strip_suffix(".url")?;
+        Some((name.to_string(), url.to_string()))
+    })
+    .collect()
+}
+
+/// Swaps a leading `from` for `to`, like git's `url.<to>.insteadOf <from>`.
+/// Returns `None` if `url` does not start with `from`. When `to` extends
+/// `from`, a URL that already starts with `to` is left alone so that
+/// re-running a rewrite changes nothing.
+pub fn rewrite(url: &str, from: &str, to: &str) -> Option<String> {
+    if from.is_empty() {
+        return None;
+    }
+    let rest = url.strip_prefix(from)?;
+    if to.len() > from.len() && url.starts_with(to) {
+        return None;
+    }
+    Some(format!("{}{}", to, rest))
+}
+
+/// Plans the URL rewrites for one repository. `only_remote` limits the
+/// rewrite to a single remote name; `.gitmodules` entries are always included.
+pub fn plan_set_url(
+    repo: &str,
+    path: &Path,
+    from: &str,
+    to: &str,
+    only_remote: Option<&str>,
+) -> Vec<UrlChange> {
+    let mut changes = Vec::new();
+    for remote in list(path) {
+        if only_remote.is_some_and(|r| r != remote.name) {
+            continue;
+        }
+        if let Some(new) = rewrite(&remote.url, from, to) {
+            changes.push(UrlChange {
+                repo: repo.to_string(),
+                path: path.to_path_buf(),
+                target: UrlTarget::Remote(remote.name),
+                old: remote.url,
+                new,
+            });
+        }
+    }
+    for (name, url) in gitmodules_urls(path) {
+        if let Some(new) = rewrite(&url, from, to) {
+            changes.push(UrlChange {
+                repo: repo.to_string(),
+                path: path.to_path_buf(),
+                target: UrlTarget::Submodule(name),
+                old: url,
+                new,
+            });
+        }
+    }
+    changes
+}
+
+pub fn apply(change: &UrlChange) -> Result<GitOpResult> {
+    match &change.target {
+        UrlTarget::Remote(name) => toad_git::run_git(
+            &change.path,
+            &["remote", "set-url", name, &change.new],
+            &change.repo,
+        ),
+        UrlTarget::Submodule(name) => toad_git::run_git(
+            &change.path,
+            &[
+                "config",
+                "-f",
+                ".gitmodules",
+                &format!("submodule.{}.url", name),
+                &change.new,
+            ],
+            &change.repo,
+        ),
+    }
+}
+
+/// Copies `.gitmodules` URLs into `.git/config` and the submodules' remotes.
+pub fn sync_submodules(path: &Path, repo: &str) -> Result<GitOpResult> {
+    toad_git::run_git(path, &["submodule", "sync", "--recursive", "--quiet"], repo)
+}
+
+/// Expands `{name}` in an `add` URL template with the repository name.
+pub fn expand_url(template: &str, repo: &str) -> String {
+    template.replace("{name}", repo)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,106 @@
+use super::*;
+use crate::test_support::git;
+use tempfile::tempdir;
+
+#[test]
+fn test_rewrite_url() {
+    assert_eq!(
+        rewrite(
+            "git@old-host:org/lily.git",
+            "git@old-host:org/",
+            "git@new-host:neworg/"
+        )
+        .as_deref(),
+        Some("git@new-host:neworg/lily.git")
+    );
+    assert_eq!(
+        rewrite("git@other:org/lily.git", "git@old-host:org/", "x"),
+        None
+    );
+    assert_eq!(rewrite("git@old-host:org/lily.git", "", "x"), None);
+    // Only a leading match counts.
+    assert_eq!(
+        rewrite(
+            "https://mirror/git@old-host:org/lily.git",
+            "git@old-host:org/",
+            "x/"
+        ),
+        None
+    );
+    // Re-running a rewrite whose target extends the source is a no-op.
+    let once = rewrite(
+        "https://host/org/lily.git",
+        "https://host/org/",
+        "https://host/org/archive/",
+    );
+    assert_eq!(once.as_deref(), Some("https://host/org/archive/lily.git"));
+    assert_eq!(
+        rewrite(
+            &once.unwrap(),
+            "https://host/org/",
+            "https://host/org/archive/"
+        ),
+        None
+    );
+    // A target that is a prefix of the source still rewrites.
+    assert_eq!(
+        rewrite(
+            "https://host/org/archive/lily.git",
+            "https://host/org/archive/",
+            "https://host/org/"
+        )
+        .as_deref(),
+        Some("https://host/org/lily.git")
+    );
+    assert_eq!(
+        rewrite(
+            "https://host/org/lily.git",
+            "https://host/org/archive/",
+            "https://host/org/"
+        ),
+        None
+    );
+    assert_eq!(
+        expand_url("git@host:org/{name}.git", "lily"),
+        "git@host:org/lily.git"
+    );
+}
+
+#[test]
+fn test_plan_set_url_covers_remotes_and_gitmodules() {
+    let dir = tempdir().unwrap();
+    let repo = dir.path();
+    git(repo, &["init", "-q"]);
+    git(
+        repo,
+        &["remote", "add", "origin", "git@old-host:org/hub.git"],
+    );
+    git(repo, &["remote", "add", "fork", "git@old-host:me/hub.git"]);
+    std::fs::write(
+        repo.join(".gitmodules"),
+        "[submodule \"core\"]\n\tpath = core\n\turl = git@old-host:org/core.git\n",
+    )
+    .unwrap();
+
+    let changes = plan_set_url(
+        "hub",
+        repo,
+        "git@old-host:org/",
+        "git@new-host:neworg/",
+        None,
+    );
+    let labels: Vec<_> = changes.iter().map(|c| c.target_label()).collect();
+    assert_eq!(labels, vec!["origin", ".gitmodules:core"]);
+
+    for change in &changes {
+        assert!(apply(change).unwrap().success);
+    }
+    assert_eq!(list(repo)[1].url, "git@new-host:neworg/hub.git");
+    assert_eq!(
+        gitmodules_urls(repo),
+        vec![(
+            "core".to_string(),
+            "git@new-host:neworg/core.git".to_string()
+        )]
+    );
+}
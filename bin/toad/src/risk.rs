@@ -0,0 +1,135 @@
+//! At-risk work report for `toad risk`.
+//!
+//! Finds work that exists only on this machine: uncommitted and untracked
+//! files (build artifact directories excluded), stashes, commits that no
+//! upstream or remote branch contains, and commits reachable only from a
+//! detached HEAD.
+
+use crate::git;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BranchRisk {
+    pub branch: String,
+    /// Upstream the commits are counted against; `None` means they are on no remote at all.
+    pub upstream: Option<String>,
+    pub commits: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RepoRisk {
+    pub name: String,
+    pub path: PathBuf,
+    /// Modified, staged or deleted tracked files.
+    pub modified: usize,
+    pub untracked: Vec<String>,
+    pub stashes: usize,
+    pub branches: Vec<BranchRisk>,
+    /// Commits reachable only from a detached HEAD.
+    pub detached: usize,
+}
+
+impl RepoRisk {
+    pub fn commits(&self) -> usize {
+        self.branches.iter().map(|b| b.commits).sum::<usize>() + self.detached
+    }
+
+    pub fn files(&self) -> usize {
+        self.modified + self.untracked.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.commits() == 0 && self.files() == 0 && self.stashes == 0
+    }
+}
+
+fn count(path: &Path, args: &[&str]) -> usize {
+    git::probe(path, args)
+        .and_then(|out| out.trim().parse().ok())
+        .unwrap_or(0)
+}
+
+fn lines(path: &Path, args: &[&str]) -> Vec<String> {
+    git::probe(path, args)
+        .map(|out| {
+            out.lines()
+                .filter(|l| !l.is_empty())
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+pub fn scan(name: &str, path: &Path, artifact_dirs: &[String]) -> RepoRisk {
+    let mut untracked_args = vec![
+        "ls-files".to_string(),
+        "--others".to_string(),
+        "--exclude-standard".to_string(),
+        "--".to_string(),
+        ".".to_string(),
+    ];
+    for dir in artifact_dirs {
+        untracked_args.push(format!(":(exclude,glob)**/{}/**", dir));
+    }
+    let untracked_args: Vec<&str> = untracked_args.iter().map(|s| s.as_str()).collect();
+
+    let mut branches = Vec::new();
+    for line in lines(
+        path,
+        &[
+            "for-each-ref",
+            "--format=%(refname:short)%00%(upstream:short)",
+            "refs/heads",
+        ],
+    ) {
+        let (branch, upstream) = line.split_once('\0').unwrap_or((&line, ""));
+        // An upstream whose remote branch is gone protects nothing.
+        let upstream = Some(upstream.to_string())
+            .filter(|u| !u.is_empty() && git::ref_exists(path, &format!("refs/remotes/{}", u)));
+        let commits = match &upstream {
+            Some(u) => count(
+                path,
+                &["rev-list", "--count", &format!("{}..{}", u, branch)],
+            ),
+            None => count(path, &["rev-list", "--count", branch, "--not", "--remotes"]),
+        };
+        if commits > 0 {
+            branches.push(BranchRisk {
+                branch: branch.to_string(),
+                upstream,
+                commits,
+            });
+        }
+    }
+
+    let detached = if git::probe(path, &["symbolic-ref", "-q", "HEAD"]).is_none() {
+        count(
+            path,
+            &[
+                "rev-list",
+                "--count",
+                "HEAD",
+                "--not",
+                "--branches",
+                "--tags",
+                "--remotes",
+            ],
+        )
+    } else {
+        0
+    };
+
+    RepoRisk {
+        name: name.to_string(),
+        path: path.to_path_buf(),
+        modified: git::tracked_changes(path),
+        untracked: lines(path, &untracked_args),
+        stashes: lines(path, &["stash", "list"]).len(),
+        branches,
+        detached,
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,63 @@
+use super::*;
+use crate::test_support::{cloned_remote, commit, git};
+use std::fs;
+use tempfile::tempdir;
+
+#[test]
+fn test_clean_pushed_repo_has_no_risk() {
+    let dir = tempdir().unwrap();
+    let work = cloned_remote(dir.path());
+    let risk = scan("work", &work, &[]);
+    assert!(risk.is_empty(), "{:?}", risk);
+}
+
+#[test]
+fn test_scan_finds_local_only_work() {
+    let dir = tempdir().unwrap();
+    let work = cloned_remote(dir.path());
+
+    commit(&work, "ahead.rs");
+    git(&work, &["checkout", "-q", "-b", "feat"]);
+    commit(&work, "feat.rs");
+    git(&work, &["checkout", "-q", "main"]);
+    fs::write(work.join("README.md"), "changed").unwrap();
+    git(&work, &["stash", "-q"]);
+    fs::write(work.join("README.md"), "changed again").unwrap();
+    fs::write(work.join("notes.txt"), "todo").unwrap();
+    fs::create_dir_all(work.join("target/debug")).unwrap();
+    fs::write(work.join("target/debug/out"), "bin").unwrap();
+
+    let risk = scan("work", &work, &["target".to_string()]);
+    assert_eq!(risk.modified, 1);
+    assert_eq!(risk.untracked, vec!["notes.txt".to_string()]);
+    assert_eq!(risk.stashes, 1);
+    assert_eq!(
+        risk.branches,
+        vec![
+            BranchRisk {
+                branch: "feat".to_string(),
+                upstream: None,
+                commits: 2,
+            },
+            BranchRisk {
+                branch: "main".to_string(),
+                upstream: Some("origin/main".to_string()),
+                commits: 1,
+            },
+        ]
+    );
+    assert_eq!(risk.detached, 0);
+    assert_eq!((risk.commits(), risk.files()), (3, 2));
+}
+
+#[test]
+fn test_scan_counts_detached_commits() {
+    let dir = tempdir().unwrap();
+    let work = cloned_remote(dir.path());
+    git(&work, &["checkout", "-q", "--detach"]);
+    commit(&work, "orphan.rs");
+
+    let risk = scan("work", &work, &[]);
+    assert_eq!(risk.detached, 1);
+    assert!(risk.branches.is_empty());
+}
@@ -0,0 +1,122 @@
+//! Recursive submodule trees and drift explanations for `toad status`.
+//!
+//! Discovery records a single level of submodules. The tree here walks every
+//! level through the read backend, and a drifted submodule is explained by the
+//! commits on each side of the recorded pointer, which shows whether
+//! `ggit align` would leave local work behind.
+
+use crate::backend::{GitBackend, SubmoduleState};
+use crate::git;
+use std::path::{Path, PathBuf};
+use toad_core::VcsStatus;
+
+/// Guards against pathological or cyclic submodule layouts.
+pub const MAX_DEPTH: usize = 8;
+
+/// Log lines shown for each side of a drift.
+pub const LOG_LIMIT: usize = 5;
+
+#[derive(Debug, Clone)]
+pub struct SubmoduleNode {
+    pub state: SubmoduleState,
+    /// Absolute path of the submodule's working tree.
+    pub path: PathBuf,
+    pub children: Vec<SubmoduleNode>,
+}
+
+impl SubmoduleNode {
+    pub fn initialized(&self) -> bool {
+        self.state.actual.is_some()
+    }
+
+    pub fn is_drifted(&self) -> bool {
+        self.initialized() && self.state.expected != self.state.actual
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Drift {
+    /// Commits checked out in the submodule but not in the recorded commit.
+    pub ahead: usize,
+    /// Commits in the recorded commit but not checked out.
+    pub behind: usize,
+    /// `%h %s` lines for the newest `ahead` commits.
+    pub ahead_log: Vec<String>,
+    /// `%h %s` lines for the newest `behind` commits.
+    pub behind_log: Vec<String>,
+}
+
+/// Walks the submodules of `repo` and of every initialized submodule below it.
+pub fn tree(backend: &dyn GitBackend, repo: &Path) -> Vec<SubmoduleNode> {
+    walk(backend, repo, 0)
+}
+
+fn walk(backend: &dyn GitBackend, repo: &Path, depth: usize) -> Vec<SubmoduleNode> {
+    if depth >= MAX_DEPTH {
+        return Vec::new();
+    }
+    backend
+        .submodules(repo)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|state| {
+            let path = repo.join(&state.path);
+            let children = if state.actual.is_some() {
+                walk(backend, &path, depth + 1)
+            } else {
+                Vec::new()
+            };
+            SubmoduleNode {
+                state,
+                path,
+                children,
+            }
+        })
+        .collect()
+}
+
+/// Classifies a working tree the way discovery does for top-level projects:
+/// tracked changes make it dirty, untracked files alone only untracked.
+pub fn vcs_status(backend: &dyn GitBackend, path: &Path) -> VcsStatus {
+    match backend.dirty_files(path) {
+        Ok(files) if files.changed > 0 => VcsStatus::Dirty,
+        Ok(files) if files.untracked > 0 => VcsStatus::Untracked,
+        Ok(_) => VcsStatus::Clean,
+        Err(_) => VcsStatus::None,
+    }
+}
+
+/// Compares the recorded commit with the checked-out one inside the
+/// submodule. `None` when either commit is not available locally.
+pub fn explain(
+    backend: &dyn GitBackend,
+    path: &Path,
+    expected: &str,
+    actual: &str,
+) -> Option<Drift> {
+    let (behind, ahead) = backend.left_right(path, expected, actual).ok()??;
+
+    let log = |range: String| -> Vec<String> {
+        git::probe(
+            path,
+            &[
+                "log",
+                "--format=%h %s",
+                &format!("--max-count={}", LOG_LIMIT),
+                &range,
+            ],
+        )
+        .map(|out| out.lines().map(String::from).collect())
+        .unwrap_or_default()
+    };
+
+    Some(Drift {
+        ahead,
+        behind,
+        ahead_log: log(format!("{}..{}", expected, actual)),
+        behind_log: log(format!("{}..{}", actual, expected)),
+    })
+}
+
+#[cfg(test)]
+mod tests;
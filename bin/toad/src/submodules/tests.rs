@@ -0,0 +1,94 @@
+use super::*;
+use crate::backend::CliBackend;
+use crate::test_support::{commit, git, init};
+use std::fs;
+use tempfile::tempdir;
+
+/// hub -> app -> lib, with `hub/app` moved back one commit and given a
+/// local commit of its own.
+fn fixture(root: &Path) -> PathBuf {
+    let lib = root.join("lib");
+    let app = root.join("app");
+    let hub = root.join("hub");
+    init(&lib);
+    commit(&lib, "lib.rs");
+    init(&app);
+    commit(&app, "one.rs");
+    git(
+        &app,
+        &["submodule", "add", "-q", lib.to_str().unwrap(), "lib"],
+    );
+    git(&app, &["commit", "-q", "-m", "add lib"]);
+    commit(&app, "two.rs");
+    init(&hub);
+    git(
+        &hub,
+        &["submodule", "add", "-q", app.to_str().unwrap(), "app"],
+    );
+    git(&hub, &["commit", "-q", "-m", "add app"]);
+    git(
+        &hub,
+        &["submodule", "update", "-q", "--init", "--recursive"],
+    );
+
+    let checkout = hub.join("app");
+    git(&checkout, &["checkout", "-q", "HEAD~1"]);
+    commit(&checkout, "local.rs");
+    hub
+}
+
+#[test]
+fn test_tree_is_recursive() {
+    let dir = tempdir().unwrap();
+    let hub = fixture(dir.path());
+
+    let nodes = tree(&CliBackend, &hub);
+    assert_eq!(nodes.len(), 1);
+    assert_eq!(nodes[0].state.path, "app");
+    assert!(nodes[0].is_drifted());
+    assert_eq!(nodes[0].children.len(), 1);
+    assert_eq!(nodes[0].children[0].state.path, "lib");
+    assert_eq!(nodes[0].children[0].path, hub.join("app").join("lib"));
+}
+
+#[test]
+fn test_vcs_status_keeps_untracked_apart() {
+    let dir = tempdir().unwrap();
+    let hub = fixture(dir.path());
+    let lib = hub.join("app").join("lib");
+    assert!(matches!(vcs_status(&CliBackend, &lib), VcsStatus::Clean));
+
+    fs::write(lib.join("scratch.txt"), "").unwrap();
+    assert!(matches!(
+        vcs_status(&CliBackend, &lib),
+        VcsStatus::Untracked
+    ));
+
+    fs::write(lib.join("lib.rs"), "changed").unwrap();
+    assert!(matches!(vcs_status(&CliBackend, &lib), VcsStatus::Dirty));
+
+    assert!(matches!(
+        vcs_status(&CliBackend, &dir.path().join("missing")),
+        VcsStatus::None
+    ));
+}
+
+#[test]
+fn test_explain_counts_both_directions() {
+    let dir = tempdir().unwrap();
+    let hub = fixture(dir.path());
+    let node = &tree(&CliBackend, &hub)[0];
+
+    let drift = explain(
+        &CliBackend,
+        &node.path,
+        node.state.expected.as_deref().unwrap(),
+        node.state.actual.as_deref().unwrap(),
+    )
+    .unwrap();
+    assert_eq!((drift.ahead, drift.behind), (1, 1));
+    assert!(drift.ahead_log[0].ends_with("local.rs"));
+    assert!(drift.behind_log[0].ends_with("two.rs"));
+
+    assert!(explain(&CliBackend, &node.path, &"0".repeat(40), "HEAD").is_none());
+}
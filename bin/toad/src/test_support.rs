@@ -0,0 +1,92 @@
+//! Git fixtures shared by the unit tests.
+//!
+//! Every helper runs git with a fixed identity and with `file://` transport
+//! allowed, so submodules can be added from sibling directories.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+fn run(dir: &Path, args: &[&str], date: Option<&str>) {
+    let mut cmd = Command::new("git");
+    cmd.args(["-c", "user.name=Toad", "-c", "user.email=toad@example.com"])
+        .args(["-c", "protocol.file.allow=always"])
+        .args(args)
+        .current_dir(dir);
+    if let Some(date) = date {
+        cmd.env("GIT_COMMITTER_DATE", date)
+            .env("GIT_AUTHOR_DATE", date);
+    }
+    let out = cmd.output().expect("failed to run git");
+    assert!(
+        out.status.success(),
+        "git {:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&out.stderr)
+    );
+}
+
+pub fn git(dir: &Path, args: &[&str]) {
+    run(dir, args, None);
+}
+
+/// Like [`git`], with the author and committer dates pinned.
+pub fn git_at(dir: &Path, args: &[&str], date: &str) {
+    run(dir, args, Some(date));
+}
+
+fn head(dir: &Path) -> String {
+    crate::git::output(dir, &["rev-parse", "HEAD"]).unwrap()
+}
+
+/// Writes `content` to `file`, commits everything with `message` and returns
+/// the new HEAD.
+pub fn commit_with(dir: &Path, file: &str, content: &str, message: &str) -> String {
+    fs::write(dir.join(file), content).unwrap();
+    git(dir, &["add", "-A"]);
+    git(dir, &["commit", "-q", "-m", message]);
+    head(dir)
+}
+
+/// Commits `file`, using its name as both content and message.
+pub fn commit(dir: &Path, file: &str) -> String {
+    commit_with(dir, file, file, file)
+}
+
+/// Like [`commit`], dated `date`.
+pub fn commit_at(dir: &Path, file: &str, date: &str) -> String {
+    fs::write(dir.join(file), file).unwrap();
+    git(dir, &["add", "-A"]);
+    git_at(dir, &["commit", "-q", "-m", file], date);
+    head(dir)
+}
+
+/// An empty repository on `main`.
+pub fn init(dir: &Path) {
+    fs::create_dir_all(dir).unwrap();
+    git(dir, &["init", "-q", "-b", "main"]);
+}
+
+/// `root/name` with a committed README and the test identity configured, for
+/// code under test that commits or merges itself.
+pub fn repo(root: &Path, name: &str) -> PathBuf {
+    let path = root.join(name);
+    init(&path);
+    git(&path, &["config", "user.name", "Toad"]);
+    git(&path, &["config", "user.email", "toad@example.com"]);
+    commit_with(&path, "README.md", name, "init");
+    path
+}
+
+/// `root/work`, a clone of the bare `root/remote.git` with one pushed commit
+/// on `main`.
+pub fn cloned_remote(root: &Path) -> PathBuf {
+    git(root, &["init", "-q", "--bare", "-b", "main", "remote.git"]);
+    git(root, &["clone", "-q", "remote.git", "work"]);
+    let work = root.join("work");
+    git(&work, &["config", "user.name", "Toad"]);
+    git(&work, &["config", "user.email", "toad@example.com"]);
+    commit(&work, "README.md");
+    git(&work, &["push", "-q", "-u", "origin", "main"]);
+    work
+}
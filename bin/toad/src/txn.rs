@@ -0,0 +1,305 @@
+//! Journaled multi-repository operations for `ggit commit`, `ggit sync` and
+//! `ggit release`.
+//!
+//! Before the first repository is touched, the planned steps and the HEAD of
+//! every repository involved are written to `txn.json` in the active
+//! context's directory. Each step's outcome is saved as soon as it finishes,
+//! so an interrupted run can be continued with `ggit resume` or undone with
+//! `ggit rollback`. The journal is removed once every step has completed.
+
+use crate::{backend, cascade, git, release};
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use toad_core::{GitOpResult, GlobalConfig, Workspace};
+
+const JOURNAL_FILE: &str = "txn.json";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Action {
+    /// Stage everything and commit with `message`.
+    Commit {
+        message: String,
+    },
+    /// Commit the Hub root with a message generated from its pointer moves.
+    CascadeCommit {
+        subject: String,
+    },
+    Pull,
+    SubmoduleUpdate,
+    /// Write the bumped manifests and commit them with `message`.
+    Release {
+        message: String,
+        edits: Vec<release::ManifestEdit>,
+    },
+    /// Create the annotated release tag `name`.
+    Tag {
+        name: String,
+        message: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "state", content = "detail", rename_all = "snake_case")]
+pub enum StepState {
+    Pending,
+    Done,
+    Failed(String),
+    /// Not run because a step it depends on did not complete.
+    Skipped(String),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Step {
+    pub name: String,
+    pub path: PathBuf,
+    pub action: Action,
+    /// Indexes of steps that must be done before this one may run.
+    #[serde(default)]
+    pub depends_on: Vec<usize>,
+    pub state: StepState,
+}
+
+/// How `rollback` moves a repository back to its recorded HEAD.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResetMode {
+    /// Undo commits but keep their changes staged.
+    Soft,
+    /// Move the working tree back, refusing to overwrite local changes.
+    Keep,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedHead {
+    pub name: String,
+    pub path: PathBuf,
+    pub head: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Journal {
+    /// The command that started the transaction, e.g. `ggit commit --cascade`.
+    pub operation: String,
+    pub started_at: u64,
+    pub fail_fast: bool,
+    pub rollback: ResetMode,
+    pub heads: Vec<RecordedHead>,
+    pub steps: Vec<Step>,
+}
+
+impl Journal {
+    pub fn new(operation: &str, rollback: ResetMode, fail_fast: bool) -> Self {
+        Self {
+            operation: operation.to_string(),
+            started_at: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            fail_fast,
+            rollback,
+            heads: Vec::new(),
+            steps: Vec::new(),
+        }
+    }
+
+    /// Adds a step and records its repository's HEAD; returns the step index.
+    pub fn push_step(
+        &mut self,
+        name: &str,
+        path: &Path,
+        action: Action,
+        depends_on: Vec<usize>,
+    ) -> usize {
+        self.record_head(name, path);
+        self.steps.push(Step {
+            name: name.to_string(),
+            path: path.to_path_buf(),
+            action,
+            depends_on,
+            state: StepState::Pending,
+        });
+        self.steps.len() - 1
+    }
+
+    /// Records a repository's current HEAD, once, so rollback can restore it.
+    pub fn record_head(&mut self, name: &str, path: &Path) {
+        if self.heads.iter().any(|h| h.path == path) {
+            return;
+        }
+        self.heads.push(RecordedHead {
+            name: name.to_string(),
+            path: path.to_path_buf(),
+            head: git::probe(path, &["rev-parse", "HEAD"]),
+        });
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.steps.iter().all(|s| s.state == StepState::Done)
+    }
+
+    pub fn path(workspace: &Workspace) -> Result<PathBuf> {
+        let dir = match &workspace.active_context {
+            Some(name) => GlobalConfig::context_dir(name, None)?,
+            None => GlobalConfig::config_dir(None)?,
+        };
+        Ok(dir.join(JOURNAL_FILE))
+    }
+
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_str(&fs::read_to_string(path)?)?))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        // Write then rename so a crash never leaves a truncated journal.
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_string_pretty(self)?)?;
+        fs::rename(tmp, path)?;
+        Ok(())
+    }
+}
+
+/// Runs every step that is not yet done, saving the journal after each one.
+/// `announce` is called before a step executes. Returns the results of the
+/// git operations that ran.
+pub fn run(
+    journal: &mut Journal,
+    journal_path: &Path,
+    mut announce: impl FnMut(&Step),
+) -> Result<Vec<GitOpResult>> {
+    journal.save(journal_path)?;
+    let mut results = Vec::new();
+
+    for i in 0..journal.steps.len() {
+        if journal.steps[i].state == StepState::Done {
+            continue;
+        }
+        let blocked = journal.steps[i]
+            .depends_on
+            .iter()
+            .find(|&&d| journal.steps[d].state != StepState::Done)
+            .map(|&d| journal.steps[d].name.clone());
+        if let Some(blocker) = blocked {
+            journal.steps[i].state = StepState::Skipped(format!("{} did not complete", blocker));
+            journal.save(journal_path)?;
+            continue;
+        }
+
+        announce(&journal.steps[i]);
+        let outcome = execute(&journal.steps[i])?;
+        journal.steps[i].state = match &outcome {
+            Some(res) if !res.success => StepState::Failed(res.stderr.trim().to_string()),
+            _ => StepState::Done,
+        };
+        journal.save(journal_path)?;
+
+        let failed = matches!(journal.steps[i].state, StepState::Failed(_));
+        results.extend(outcome);
+        if failed && journal.fail_fast {
+            break;
+        }
+    }
+
+    if journal.is_complete() {
+        fs::remove_file(journal_path)?;
+    }
+    Ok(results)
+}
+
+/// Executes one step. `None` means there was nothing to do.
+fn execute(step: &Step) -> Result<Option<GitOpResult>> {
+    match &step.action {
+        Action::Commit { message } => {
+            if !toad_git::commit::is_dirty(&step.path)? {
+                return Ok(None);
+            }
+            Ok(Some(toad_git::commit::commit(
+                &step.path, message, &step.name,
+            )?))
+        }
+        Action::CascadeCommit { subject } => {
+            if !toad_git::commit::is_dirty(&step.path)? {
+                return Ok(None);
+            }
+            let moves = cascade::pointer_moves(backend::select().as_ref(), &step.path);
+            let message = cascade::hub_message(subject, &moves);
+            Ok(Some(toad_git::commit::commit(
+                &step.path, &message, &step.name,
+            )?))
+        }
+        Action::Pull => Ok(Some(toad_git::remote::pull(&step.path, &step.name)?)),
+        Action::SubmoduleUpdate => Ok(Some(toad_git::run_git(
+            &step.path,
+            &["submodule", "update", "--init", "--recursive"],
+            &step.name,
+        )?)),
+        Action::Release { message, edits } => {
+            release::apply(&step.path, edits)?;
+            if !toad_git::commit::is_dirty(&step.path)? {
+                return Ok(None);
+            }
+            Ok(Some(toad_git::commit::commit(
+                &step.path, message, &step.name,
+            )?))
+        }
+        Action::Tag { name, message } => {
+            // Re-running after an interruption must not fail on our own tag.
+            if git::ref_exists(&step.path, &format!("refs/tags/{}", name)) {
+                return Ok(None);
+            }
+            Ok(Some(toad_git::run_git(
+                &step.path,
+                &["tag", "-a", name, "-m", message],
+                &step.name,
+            )?))
+        }
+    }
+}
+
+/// Repositories whose HEAD has moved since the journal recorded it.
+pub fn moved_heads(journal: &Journal) -> Vec<(&RecordedHead, Option<String>)> {
+    journal
+        .heads
+        .iter()
+        .filter_map(|h| {
+            let current = git::probe(&h.path, &["rev-parse", "HEAD"]);
+            (h.head.is_some() && current != h.head).then_some((h, current))
+        })
+        .collect()
+}
+
+/// Tags created by completed `Tag` steps, as (step, tag name).
+pub fn created_tags(journal: &Journal) -> Vec<(&Step, &str)> {
+    journal
+        .steps
+        .iter()
+        .filter(|s| s.state == StepState::Done)
+        .filter_map(|s| match &s.action {
+            Action::Tag { name, .. } => Some((s, name.as_str())),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Resets one repository to its recorded HEAD.
+pub fn reset(head: &RecordedHead, mode: ResetMode) -> Result<GitOpResult> {
+    let Some(sha) = &head.head else {
+        bail!("no HEAD was recorded for {}", head.name);
+    };
+    let flag = match mode {
+        ResetMode::Soft => "--soft",
+        ResetMode::Keep => "--keep",
+    };
+    toad_git::run_git(&head.path, &["reset", "--quiet", flag, sha], &head.name)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,124 @@
+use super::*;
+use crate::test_support::{git, repo};
+use tempfile::tempdir;
+
+#[test]
+fn test_failed_step_skips_dependents_and_keeps_journal() {
+    let dir = tempdir().unwrap();
+    let journal_path = dir.path().join("ctx").join(JOURNAL_FILE);
+    // No upstream, so the pull fails.
+    let app = repo(dir.path(), "app");
+    fs::write(app.join("change.txt"), "x").unwrap();
+
+    let mut journal = Journal::new("ggit sync", ResetMode::Keep, false);
+    let pull = journal.push_step("app", &app, Action::Pull, Vec::new());
+    journal.push_step(
+        "app commit",
+        &app,
+        Action::Commit {
+            message: "wip".to_string(),
+        },
+        vec![pull],
+    );
+    assert_eq!(journal.heads.len(), 1);
+
+    let results = run(&mut journal, &journal_path, |_| {}).unwrap();
+    assert_eq!(results.len(), 1);
+    assert!(!results[0].success);
+    assert!(matches!(journal.steps[0].state, StepState::Failed(_)));
+    assert!(matches!(journal.steps[1].state, StepState::Skipped(_)));
+
+    let saved = Journal::load(&journal_path).unwrap().unwrap();
+    assert_eq!(saved.operation, "ggit sync");
+    assert!(matches!(saved.steps[1].state, StepState::Skipped(_)));
+}
+
+#[test]
+fn test_completed_run_removes_journal() {
+    let dir = tempdir().unwrap();
+    let journal_path = dir.path().join(JOURNAL_FILE);
+    let app = repo(dir.path(), "app");
+    fs::write(app.join("change.txt"), "x").unwrap();
+
+    let mut journal = Journal::new("ggit commit", ResetMode::Soft, false);
+    journal.push_step(
+        "app",
+        &app,
+        Action::Commit {
+            message: "feat: change".to_string(),
+        },
+        Vec::new(),
+    );
+    run(&mut journal, &journal_path, |_| {}).unwrap();
+    assert!(journal.is_complete());
+    assert!(!journal_path.exists());
+}
+
+#[test]
+fn test_rollback_restores_recorded_head() {
+    let dir = tempdir().unwrap();
+    let app = repo(dir.path(), "app");
+    let mut journal = Journal::new("ggit commit", ResetMode::Soft, false);
+    journal.record_head("app", &app);
+    let before = journal.heads[0].head.clone();
+
+    fs::write(app.join("change.txt"), "x").unwrap();
+    git(&app, &["add", "-A"]);
+    git(&app, &["commit", "-q", "-m", "change"]);
+
+    let moved = moved_heads(&journal);
+    assert_eq!(moved.len(), 1);
+    assert!(reset(moved[0].0, journal.rollback).unwrap().success);
+    assert_eq!(git::probe(&app, &["rev-parse", "HEAD"]), before);
+    // A soft rollback leaves the committed change staged.
+    assert_eq!(
+        git::probe(&app, &["status", "--porcelain"]).as_deref(),
+        Some("A  change.txt")
+    );
+    assert!(moved_heads(&journal).is_empty());
+}
+
+#[test]
+fn test_release_steps_are_resumable_and_report_tags() {
+    let dir = tempdir().unwrap();
+    let journal_path = dir.path().join(JOURNAL_FILE);
+    let app = repo(dir.path(), "app");
+    fs::write(app.join("Cargo.toml"), "version = \"1.0.0\"\n").unwrap();
+    git(&app, &["add", "-A"]);
+    git(&app, &["commit", "-q", "-m", "manifest"]);
+
+    let mut journal = Journal::new("ggit release v1.1.0", ResetMode::Keep, true);
+    let commit = journal.push_step(
+        "app",
+        &app,
+        Action::Release {
+            message: "chore(release): v1.1.0".to_string(),
+            edits: vec![release::ManifestEdit {
+                file: PathBuf::from("Cargo.toml"),
+                changes: Vec::new(),
+                content: "version = \"1.1.0\"\n".to_string(),
+            }],
+        },
+        Vec::new(),
+    );
+    journal.push_step(
+        "app",
+        &app,
+        Action::Tag {
+            name: "v1.1.0".to_string(),
+            message: "chore(release): v1.1.0".to_string(),
+        },
+        vec![commit],
+    );
+    run(&mut journal, &journal_path, |_| {}).unwrap();
+    assert!(journal.is_complete());
+    assert!(git::ref_exists(&app, "refs/tags/v1.1.0"));
+    assert_eq!(created_tags(&journal)[0].1, "v1.1.0");
+
+    // Replaying both steps, as a resume after a crash would, is a no-op.
+    for step in &mut journal.steps {
+        step.state = StepState::Pending;
+    }
+    assert!(run(&mut journal, &journal_path, |_| {}).unwrap().is_empty());
+    assert!(journal.is_complete());
+}
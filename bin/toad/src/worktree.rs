@@ -0,0 +1,234 @@
+//! Linked worktrees across repositories for `toad ggit worktree`.
+//!
+//! `add <branch>` checks the branch out in a linked worktree of every targeted
+//! repository, laid out as a pond under `<root>/.worktrees/<branch>/projects/`
+//! so the tree can be registered as its own context. The main checkouts are
+//! never touched. `remove` refuses to discard uncommitted work unless forced,
+//! and leaves worktrees locked with `git worktree lock` alone unless told to
+//! unlock them.
+
+use crate::git;
+use crate::mirror;
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use toad_core::GitOpResult;
+
+pub const WORKTREES_DIR: &str = ".worktrees";
+
+/// Directory-safe form of a branch name (`release/1.2` -> `release-1.2`).
+pub fn slug(branch: &str) -> String {
+    branch
+        .chars()
+        .map(|c| {
+            if c == '/' || c.is_whitespace() {
+                '-'
+            } else {
+                c
+            }
+        })
+        .collect()
+}
+
+/// Root of the worktree pond for a branch.
+pub fn tree_root(root: &Path, branch: &str) -> PathBuf {
+    root.join(WORKTREES_DIR).join(slug(branch))
+}
+
+/// Name of the temporary context registered for a branch's worktrees.
+pub fn context_name(base: &str, branch: &str) -> String {
+    format!("{}-wt-{}", base, slug(branch))
+}
+
+/// Keeps the worktree ponds out of `git status` when the root is itself a
+/// repository (a Hub).
+pub fn exclude_from(root: &Path) -> Result<()> {
+    if !root.join(".git").exists() {
+        return Ok(());
+    }
+    let Some(git_dir) = git::probe(root, &["rev-parse", "--path-format=absolute", "--git-dir"])
+    else {
+        return Ok(());
+    };
+    let exclude = PathBuf::from(git_dir).join("info").join("exclude");
+    let pattern = format!("/{}/", WORKTREES_DIR);
+    let current = fs::read_to_string(&exclude).unwrap_or_default();
+    if current.lines().any(|l| l.trim() == pattern) {
+        return Ok(());
+    }
+    if let Some(parent) = exclude.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let separator = if current.is_empty() || current.ends_with('\n') {
+        ""
+    } else {
+        "\n"
+    };
+    fs::write(&exclude, format!("{}{}{}\n", current, separator, pattern))?;
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Source {
+    /// The local branch exists.
+    Local,
+    /// Created to track `<remote>/<branch>`.
+    Remote(String),
+    /// Created from the repository's current HEAD.
+    NewFromHead,
+}
+
+/// Works out where a repository's worktree would come from. `None` when the
+/// branch exists nowhere and `create` is off.
+pub fn source(repo: &Path, branch: &str, create: bool) -> Option<Source> {
+    if git::ref_exists(repo, &format!("refs/heads/{}", branch)) {
+        return Some(Source::Local);
+    }
+    if let Some(remote) = git::default_remote(repo) {
+        if git::ref_exists(repo, &format!("refs/remotes/{}/{}", remote, branch)) {
+            return Some(Source::Remote(remote));
+        }
+    }
+    create.then_some(Source::NewFromHead)
+}
+
+/// Adds the linked worktree and initializes its submodules, through the
+/// local mirrors when `rewrites` are given.
+pub fn add(
+    repo: &Path,
+    name: &str,
+    target: &Path,
+    branch: &str,
+    source: &Source,
+    rewrites: &[String],
+) -> Result<Vec<GitOpResult>> {
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let target_str = target.to_string_lossy().to_string();
+    let upstream;
+    let mut args = vec!["worktree", "add", "--quiet"];
+    match source {
+        Source::Local => args.extend([target_str.as_str(), branch]),
+        Source::Remote(remote) => {
+            upstream = format!("{}/{}", remote, branch);
+            args.extend(["--track", "-b", branch, target_str.as_str(), &upstream]);
+        }
+        Source::NewFromHead => args.extend(["-b", branch, target_str.as_str()]),
+    }
+
+    let mut results = vec![toad_git::run_git(repo, &args, name)?];
+    if results[0].success && target.join(".gitmodules").exists() {
+        results.push(mirror::run_via(
+            rewrites,
+            target,
+            &["submodule", "update", "--init", "--recursive", "--quiet"],
+            &format!("{} (submodules)", name),
+        )?);
+    }
+    Ok(results)
+}
+
+/// A linked worktree found under a worktree pond.
+#[derive(Debug, Clone)]
+pub struct LinkedWorktree {
+    pub name: String,
+    pub path: PathBuf,
+    /// Working tree of the repository the worktree belongs to.
+    pub main: PathBuf,
+    /// `status --porcelain` lines, untracked files included.
+    pub changes: Vec<String>,
+    /// Reason given to `git worktree lock` (empty if none); `None` if unlocked.
+    pub locked: Option<String>,
+}
+
+/// Lists the linked worktrees in a pond created by [`add`].
+pub fn list(tree: &Path) -> Result<Vec<LinkedWorktree>> {
+    let projects = tree.join("projects");
+    if !projects.exists() {
+        return Ok(Vec::new());
+    }
+    let mut entries: Vec<_> = fs::read_dir(&projects)?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.join(".git").is_file())
+        .collect();
+    entries.sort();
+
+    Ok(entries
+        .into_iter()
+        .filter_map(|path| {
+            // The first entry of `worktree list` is always the main working tree.
+            let listing = git::probe(&path, &["worktree", "list", "--porcelain"])?;
+            let main = PathBuf::from(listing.lines().next()?.strip_prefix("worktree ")?);
+            Some(LinkedWorktree {
+                name: path.file_name()?.to_string_lossy().to_string(),
+                changes: git::probe(&path, &["status", "--porcelain"])
+                    .map(|out| out.lines().map(String::from).collect())
+                    .unwrap_or_default(),
+                locked: lock_reason(&listing, &path),
+                main,
+                path,
+            })
+        })
+        .collect())
+}
+
+/// The `locked` attribute of `path`'s entry in `worktree list --porcelain`.
+fn lock_reason(listing: &str, path: &Path) -> Option<String> {
+    let path = path.canonicalize().ok()?;
+    let entry = listing.split("\n\n").find(|entry| {
+        entry
+            .lines()
+            .next()
+            .and_then(|line| line.strip_prefix("worktree "))
+            .and_then(|p| Path::new(p).canonicalize().ok())
+            .is_some_and(|p| p == path)
+    })?;
+    entry.lines().find_map(|line| match line {
+        "locked" => Some(String::new()),
+        _ => line.strip_prefix("locked ").map(String::from),
+    })
+}
+
+/// Removes a linked worktree through its main repository. Callers check
+/// `changes` first: git's own check is bypassed because it also refuses any
+/// worktree with initialized submodules. A locked worktree is only removed
+/// with `unlock`.
+pub fn remove(wt: &LinkedWorktree, unlock: bool) -> Result<GitOpResult> {
+    if wt.locked.is_some() && !unlock {
+        bail!("worktree {:?} is locked", wt.path);
+    }
+    let path = wt.path.to_string_lossy().to_string();
+    // A second --force is what overrides the lock.
+    let mut args = vec!["worktree", "remove", "--force"];
+    if unlock {
+        args.push("--force");
+    }
+    args.push(&path);
+    let res = toad_git::run_git(&wt.main, &args, &wt.name)?;
+    if res.success {
+        let _ = git::probe(&wt.main, &["worktree", "prune"]);
+    }
+    Ok(res)
+}
+
+/// Deletes a pond once its worktrees are removed: the root marker, the empty
+/// `projects/` directory and the tree itself. Anything else left inside was
+/// not created by [`add`], so it is kept and an error is returned.
+pub fn remove_tree(tree: &Path) -> Result<()> {
+    let marker = tree.join(crate::ROOT_MARKER);
+    if marker.is_file() {
+        fs::remove_file(marker)?;
+    }
+    for dir in [tree.join("projects"), tree.to_path_buf()] {
+        if dir.exists() {
+            fs::remove_dir(&dir)
+                .with_context(|| format!("{:?} is not empty; remove what is left by hand", dir))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests;
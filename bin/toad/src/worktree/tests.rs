@@ -0,0 +1,97 @@
+use super::*;
+use crate::test_support::{self, git};
+use tempfile::tempdir;
+
+fn repo(root: &Path) -> PathBuf {
+    let path = test_support::repo(root, "newt");
+    git(&path, &["branch", "hotfix/1.2"]);
+    path
+}
+
+#[test]
+fn test_slug_and_paths() {
+    assert_eq!(slug("release/1.2"), "release-1.2");
+    assert_eq!(
+        tree_root(Path::new("/pond"), "release/1.2"),
+        PathBuf::from("/pond/.worktrees/release-1.2")
+    );
+    assert_eq!(context_name("pond", "release/1.2"), "pond-wt-release-1.2");
+}
+
+#[test]
+fn test_add_list_and_remove() {
+    let dir = tempdir().unwrap();
+    let main = repo(dir.path());
+    assert_eq!(source(&main, "hotfix/1.2", false), Some(Source::Local));
+    assert_eq!(source(&main, "missing", false), None);
+    assert_eq!(source(&main, "missing", true), Some(Source::NewFromHead));
+
+    let tree = tree_root(dir.path(), "hotfix/1.2");
+    let target = tree.join("projects").join("newt");
+    let results = add(&main, "newt", &target, "hotfix/1.2", &Source::Local, &[]).unwrap();
+    assert!(results.iter().all(|r| r.success));
+    assert_eq!(
+        git::probe(&target, &["symbolic-ref", "--short", "HEAD"]).as_deref(),
+        Some("hotfix/1.2")
+    );
+    // The main checkout stays where it was.
+    assert_eq!(
+        git::probe(&main, &["symbolic-ref", "--short", "HEAD"]).as_deref(),
+        Some("main")
+    );
+
+    fs::write(target.join("scratch.txt"), "wip").unwrap();
+    let linked = list(&tree).unwrap();
+    assert_eq!(linked.len(), 1);
+    assert_eq!(linked[0].name, "newt");
+    assert_eq!(
+        linked[0].main.canonicalize().unwrap(),
+        main.canonicalize().unwrap()
+    );
+    assert_eq!(linked[0].changes, vec!["?? scratch.txt".to_string()]);
+    assert_eq!(linked[0].locked, None);
+
+    git(
+        &main,
+        &[
+            "worktree",
+            "lock",
+            "--reason",
+            "on a usb stick",
+            target.to_str().unwrap(),
+        ],
+    );
+    let linked = list(&tree).unwrap();
+    assert_eq!(linked[0].locked.as_deref(), Some("on a usb stick"));
+    assert!(remove(&linked[0], false).is_err());
+    assert!(target.exists());
+
+    assert!(remove(&linked[0], true).unwrap().success);
+    assert!(!target.exists());
+    assert!(list(&tree).unwrap().is_empty());
+
+    // Files that no worktree owns keep the tree in place.
+    fs::write(tree.join(crate::ROOT_MARKER), "").unwrap();
+    fs::write(tree.join("notes.txt"), "keep").unwrap();
+    assert!(remove_tree(&tree).is_err());
+    assert!(tree.join("notes.txt").exists());
+    fs::remove_file(tree.join("notes.txt")).unwrap();
+    remove_tree(&tree).unwrap();
+    assert!(!tree.exists());
+}
+
+#[test]
+fn test_exclude_from_is_idempotent() {
+    let dir = tempdir().unwrap();
+    let main = repo(dir.path());
+    exclude_from(&main).unwrap();
+    exclude_from(&main).unwrap();
+    fs::create_dir_all(main.join(WORKTREES_DIR).join("x")).unwrap();
+    fs::write(main.join(WORKTREES_DIR).join("x").join("f"), "").unwrap();
+    assert_eq!(
+        git::probe(&main, &["status", "--porcelain"]).as_deref(),
+        Some("")
+    );
+    let exclude = fs::read_to_string(main.join(".git/info/exclude")).unwrap();
+    assert_eq!(exclude.matches("/.worktrees/").count(), 1);
+}
@@ -789,3 +789,853 @@ fn test_do_fail_fast() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+fn git(dir: &std::path::Path, args: &[&str]) {
+    StdCommand::new("git")
+        .args(["-c", "user.name=Toad", "-c", "user.email=toad@example.com"])
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .expect("failed to run git");
+}
+
+fn init_committed_repo(dir: &std::path::Path) {
+    git(dir, &["init", "-q", "-b", "main"]);
+    git(dir, &["config", "user.name", "Toad"]);
+    git(dir, &["config", "user.email", "toad@example.com"]);
+    git(dir, &["add", "-A"]);
+    git(dir, &["commit", "-q", "-m", "init"]);
+}
+
+#[test]
+fn test_ggit_release_dry_run() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    fs::write(dir.path().join(".toad-root"), "")?;
+    let proj_path = dir.path().join("projects/lily");
+    fs::create_dir_all(&proj_path)?;
+    let manifest = "[package]\nname = \"lily\"\nversion = \"0.1.0\"\n";
+    fs::write(proj_path.join("Cargo.toml"), manifest)?;
+    init_committed_repo(&proj_path);
+
+    let mut cmd = cargo_bin_cmd!("toad");
+    cmd.current_dir(dir.path())
+        .args(["ggit", "release", "v0.2.0", "-q", "lily", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("0.1.0 → 0.2.0"))
+        .stdout(predicate::str::contains("tag: v0.2.0"))
+        .stdout(predicate::str::contains("DRY RUN COMPLETE"));
+
+    assert_eq!(fs::read_to_string(proj_path.join("Cargo.toml"))?, manifest);
+    Ok(())
+}
+
+#[test]
+fn test_ggit_merge_conflict_report_and_abort() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    fs::write(dir.path().join(".toad-root"), "")?;
+    let clean_path = dir.path().join("projects/pond-clean");
+    let clash_path = dir.path().join("projects/pond-clash");
+    for path in [&clean_path, &clash_path] {
+        fs::create_dir_all(path)?;
+        fs::write(path.join("README.md"), "base\n")?;
+        init_committed_repo(path);
+        git(path, &["checkout", "-q", "-b", "feature"]);
+        fs::write(path.join("README.md"), "feature\n")?;
+        git(path, &["commit", "-q", "-am", "feature change"]);
+        git(path, &["checkout", "-q", "main"]);
+    }
+    fs::write(clash_path.join("README.md"), "main\n")?;
+    git(&clash_path, &["commit", "-q", "-am", "main change"]);
+
+    let mut cmd = cargo_bin_cmd!("toad");
+    cmd.current_dir(dir.path())
+        .args(["ggit", "merge", "feature", "-q", "pond"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("CONFLICT"))
+        .stdout(predicate::str::contains(
+            "1 repositories stopped on conflicts",
+        ))
+        .stdout(predicate::str::contains("README.md"));
+
+    let mut cmd = cargo_bin_cmd!("toad");
+    cmd.current_dir(dir.path())
+        .args(["ggit", "merge", "--abort", "-q", "pond"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Aborting merge in pond-clash"));
+
+    assert_eq!(fs::read_to_string(clash_path.join("README.md"))?, "main\n");
+    assert_eq!(
+        fs::read_to_string(clean_path.join("README.md"))?,
+        "feature\n"
+    );
+    Ok(())
+}
+
+fn git_output(dir: &std::path::Path, args: &[&str]) -> String {
+    let out = StdCommand::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .expect("failed to run git");
+    String::from_utf8_lossy(&out.stdout).trim().to_string()
+}
+
+fn head_commit(dir: &std::path::Path) -> String {
+    git_output(dir, &["rev-parse", "HEAD"])
+}
+
+#[test]
+fn test_lock_diff_and_restore() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    fs::write(dir.path().join(".toad-root"), "")?;
+    let proj_path = dir.path().join("projects/newt");
+    fs::create_dir_all(&proj_path)?;
+    fs::write(proj_path.join("README.md"), "v1\n")?;
+    init_committed_repo(&proj_path);
+    let locked = head_commit(&proj_path);
+
+    let mut cmd = cargo_bin_cmd!("toad");
+    cmd.current_dir(dir.path())
+        .args(["lock"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Locked 1 repositories"));
+    let lockfile = fs::read_to_string(dir.path().join("toad.lock"))?;
+    assert!(lockfile.contains(&locked));
+    assert!(lockfile.contains("path = \"projects/newt\""));
+
+    fs::write(proj_path.join("README.md"), "v2\n")?;
+    git(&proj_path, &["commit", "-q", "-am", "second change"]);
+
+    let mut cmd = cargo_bin_cmd!("toad");
+    cmd.current_dir(dir.path())
+        .args(["lock", "diff", "toad.lock"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("second change"))
+        .stdout(predicate::str::contains("1 repositories differ"));
+
+    let mut cmd = cargo_bin_cmd!("toad");
+    cmd.current_dir(dir.path())
+        .args(["lock", "restore", "-y"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("RESTORE SUMMARY"));
+    assert_eq!(head_commit(&proj_path), locked);
+    assert_eq!(fs::read_to_string(proj_path.join("README.md"))?, "v1\n");
+    Ok(())
+}
+
+#[test]
+fn test_clone_from_manifest_with_local_remotes() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let home = dir.path().join("home");
+    fs::create_dir_all(&home)?;
+
+    let upstream = dir.path().join("upstream");
+    let lily_src = upstream.join("lily");
+    fs::create_dir_all(&lily_src)?;
+    fs::write(lily_src.join("README.md"), "lily\n")?;
+    init_committed_repo(&lily_src);
+    git(&upstream, &["clone", "-q", "--bare", "lily", "newt.git"]);
+
+    let manifest = "[context]\nname = \"pond\"\n\n\
+        [[project]]\nname = \"lily\"\nremote = \"upstream/lily\"\nbranch = \"main\"\ntags = [\"backend\"]\n\n\
+        [[project]]\nname = \"newt\"\nremote = \"upstream/newt.git\"\npath = \"tools/newt\"\n";
+    fs::write(dir.path().join("pond.toml"), manifest)?;
+
+    let dest = dir.path().join("pond");
+    let mut cmd = cargo_bin_cmd!("toad");
+    cmd.current_dir(dir.path())
+        .env("HOME", &home)
+        .args(["clone", "pond.toml", "--dest"])
+        .arg(&dest)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("CLONE SUMMARY"))
+        .stdout(predicate::str::contains("Context 'pond' registered"));
+
+    assert!(dest.join(".toad-root").exists());
+    assert_eq!(
+        fs::read_to_string(dest.join("projects/lily/README.md"))?,
+        "lily\n"
+    );
+    assert!(dest.join("projects/tools/newt/README.md").exists());
+
+    // Re-running is safe: existing clones are left alone.
+    let mut cmd = cargo_bin_cmd!("toad");
+    cmd.current_dir(dir.path())
+        .env("HOME", &home)
+        .args(["clone", "pond.toml", "--dest"])
+        .arg(&dest)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("EXISTS"));
+    Ok(())
+}
+
+#[test]
+fn test_ggit_push_preflight_blocks_non_fast_forward() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    fs::write(dir.path().join(".toad-root"), "")?;
+    git(
+        dir.path(),
+        &["init", "-q", "--bare", "-b", "main", "remote.git"],
+    );
+    let remote = dir.path().join("remote.git");
+    let proj_path = dir.path().join("projects/frog");
+    fs::create_dir_all(&proj_path)?;
+    fs::write(proj_path.join("README.md"), "frog\n")?;
+    init_committed_repo(&proj_path);
+    git(
+        &proj_path,
+        &["remote", "add", "origin", remote.to_str().unwrap()],
+    );
+    git(&proj_path, &["push", "-q", "-u", "origin", "main"]);
+
+    fs::write(proj_path.join("README.md"), "frog v2\n")?;
+    git(&proj_path, &["commit", "-q", "-am", "ribbit louder"]);
+
+    let mut cmd = cargo_bin_cmd!("toad");
+    cmd.current_dir(dir.path())
+        .args(["ggit", "push", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("ribbit louder"))
+        .stdout(predicate::str::contains("DRY RUN COMPLETE"));
+
+    let other = dir.path().join("other");
+    git(
+        dir.path(),
+        &["clone", "-q", remote.to_str().unwrap(), "other"],
+    );
+    fs::write(other.join("NEW.md"), "other\n")?;
+    git(&other, &["add", "-A"]);
+    git(&other, &["commit", "-q", "-m", "upstream change"]);
+    git(&other, &["push", "-q", "origin", "main"]);
+
+    let mut cmd = cargo_bin_cmd!("toad");
+    cmd.current_dir(dir.path())
+        .args(["ggit", "push"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("Non-fast-forward"));
+    Ok(())
+}
+
+#[test]
+fn test_ggit_prune_branches_deletes_merged() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    fs::write(dir.path().join(".toad-root"), "")?;
+    let proj_path = dir.path().join("projects/heron");
+    fs::create_dir_all(&proj_path)?;
+    fs::write(proj_path.join("README.md"), "heron\n")?;
+    init_committed_repo(&proj_path);
+    git(&proj_path, &["branch", "shipped"]);
+    git(&proj_path, &["checkout", "-q", "-b", "unmerged"]);
+    fs::write(proj_path.join("WIP.md"), "wip\n")?;
+    git(&proj_path, &["add", "-A"]);
+    git(&proj_path, &["commit", "-q", "-m", "wip"]);
+    git(&proj_path, &["checkout", "-q", "main"]);
+
+    let mut cmd = cargo_bin_cmd!("toad");
+    cmd.current_dir(dir.path())
+        .args(["ggit", "prune-branches", "-y"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("shipped"))
+        .stdout(predicate::str::contains("PRUNE SUMMARY"));
+
+    let branches = StdCommand::new("git")
+        .args(["branch", "--format=%(refname:short)"])
+        .current_dir(&proj_path)
+        .output()?;
+    let branches = String::from_utf8_lossy(&branches.stdout);
+    assert!(!branches.contains("shipped"));
+    assert!(branches.contains("unmerged"));
+    assert!(branches.contains("main"));
+    Ok(())
+}
+
+#[test]
+fn test_ggit_remote_set_url_rewrites_prefix() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    fs::write(dir.path().join(".toad-root"), "")?;
+    for name in ["lily", "newt"] {
+        let proj_path = dir.path().join("projects").join(name);
+        fs::create_dir_all(&proj_path)?;
+        git(&proj_path, &["init", "-q"]);
+        let url = format!("git@old-host:org/{}.git", name);
+        git(&proj_path, &["remote", "add", "origin", &url]);
+    }
+
+    let mut cmd = cargo_bin_cmd!("toad");
+    cmd.current_dir(dir.path())
+        .args([
+            "ggit",
+            "remote",
+            "set-url",
+            "git@old-host:org/",
+            "git@new-host:neworg/",
+            "-y",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("git@new-host:neworg/lily.git"))
+        .stdout(predicate::str::contains("REMOTE SUMMARY"));
+
+    let mut cmd = cargo_bin_cmd!("toad");
+    cmd.current_dir(dir.path())
+        .args(["ggit", "remote", "list", "-q", "newt"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("git@new-host:neworg/newt.git"))
+        .stdout(predicate::str::contains("lily").not());
+    Ok(())
+}
+
+#[test]
+fn test_grep_tracked_files_across_projects() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    fs::write(dir.path().join(".toad-root"), "")?;
+    for name in ["lily", "newt"] {
+        let proj_path = dir.path().join("projects").join(name);
+        fs::create_dir_all(&proj_path)?;
+        fs::write(proj_path.join("notes.txt"), "one\nfind the lilypad\n")?;
+        fs::write(proj_path.join(".gitignore"), "ignored.txt\n")?;
+        init_committed_repo(&proj_path);
+        fs::write(proj_path.join("ignored.txt"), "lilypad\n")?;
+    }
+
+    let mut cmd = cargo_bin_cmd!("toad");
+    cmd.current_dir(dir.path())
+        .args(["grep", "lilypad"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("notes.txt:2: find the lilypad"))
+        .stdout(predicate::str::contains("ignored.txt").not())
+        .stdout(predicate::str::contains("2 matches across 2 repositories"));
+
+    let mut cmd = cargo_bin_cmd!("toad");
+    let output = cmd
+        .current_dir(dir.path())
+        .args(["grep", "LILYPAD", "-i", "-c", "--json", "-q", "newt"])
+        .output()?;
+    let results: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(results[0]["project"], "newt");
+    assert_eq!(results[0]["hits"][0]["path"], "notes.txt");
+    assert_eq!(results[0]["hits"][0]["count"], 1);
+
+    let mut cmd = cargo_bin_cmd!("toad");
+    cmd.current_dir(dir.path())
+        .args(["grep", "lilypad", "-l"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("2 files across 2 repositories"));
+    Ok(())
+}
+
+#[test]
+fn test_change_start_status_finish() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let home = dir.path().join("home");
+    fs::create_dir_all(&home)?;
+    fs::write(dir.path().join(".toad-root"), "")?;
+    for name in ["newt", "frog"] {
+        let proj_path = dir.path().join("projects").join(name);
+        fs::create_dir_all(&proj_path)?;
+        fs::write(proj_path.join("README.md"), name)?;
+        init_committed_repo(&proj_path);
+    }
+    let newt = dir.path().join("projects/newt");
+
+    let mut cmd = cargo_bin_cmd!("toad");
+    cmd.current_dir(dir.path())
+        .env("HOME", &home)
+        .args(["change", "start", "feat-ponds", "-q", "newt", "-y"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("CHANGE SUMMARY"));
+    assert_eq!(
+        git_output(&newt, &["symbolic-ref", "--short", "HEAD"]),
+        "feat-ponds"
+    );
+    assert_eq!(
+        git_output(
+            &dir.path().join("projects/frog"),
+            &["symbolic-ref", "--short", "HEAD"]
+        ),
+        "main"
+    );
+
+    fs::write(newt.join("pond.rs"), "fn pond() {}\n")?;
+    git(&newt, &["add", "-A"]);
+    git(&newt, &["commit", "-q", "-m", "add pond"]);
+
+    let mut cmd = cargo_bin_cmd!("toad");
+    cmd.current_dir(dir.path())
+        .env("HOME", &home)
+        .args(["change", "status", "feat-ponds"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("add pond"))
+        .stdout(predicate::str::contains("unmerged"));
+
+    let mut cmd = cargo_bin_cmd!("toad");
+    cmd.current_dir(dir.path())
+        .env("HOME", &home)
+        .args(["change", "finish", "feat-ponds", "-y"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("--merge"));
+
+    let mut cmd = cargo_bin_cmd!("toad");
+    cmd.current_dir(dir.path())
+        .env("HOME", &home)
+        .args(["change", "finish", "feat-ponds", "--merge", "-y"])
+        .assert()
+        .success();
+    assert_eq!(
+        git_output(&newt, &["symbolic-ref", "--short", "HEAD"]),
+        "main"
+    );
+    assert!(newt.join("pond.rs").exists());
+    Ok(())
+}
+
+#[test]
+fn test_bisect_hub_pointers_to_submodule_commit() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let lib = dir.path().join("lib");
+    fs::create_dir_all(&lib)?;
+    fs::write(lib.join("value.txt"), "ok 1\n")?;
+    init_committed_repo(&lib);
+    let hub = dir.path().join("hub");
+    fs::create_dir_all(&hub)?;
+    fs::write(hub.join("README.md"), "hub\n")?;
+    init_committed_repo(&hub);
+    fs::write(hub.join(".toad-root"), "")?;
+    git(
+        &hub,
+        &[
+            "-c",
+            "protocol.file.allow=always",
+            "submodule",
+            "add",
+            "-q",
+            lib.to_str().unwrap(),
+            "lib",
+        ],
+    );
+    git(&hub, &["commit", "-q", "-m", "add lib"]);
+    let good = head_commit(&hub);
+
+    let checkout = hub.join("lib");
+    git(&checkout, &["config", "user.name", "Toad"]);
+    git(&checkout, &["config", "user.email", "toad@example.com"]);
+    let mut culprit = String::new();
+    for (hub_step, values) in [["ok 2", "ok 3"], ["broken", "still broken"]]
+        .iter()
+        .enumerate()
+    {
+        for value in values {
+            fs::write(checkout.join("value.txt"), format!("{}\n", value))?;
+            git(&checkout, &["commit", "-q", "-am", value]);
+            if *value == "broken" {
+                culprit = head_commit(&checkout)[..7].to_string();
+            }
+        }
+        git(
+            &hub,
+            &["commit", "-q", "-am", &format!("bump lib {}", hub_step)],
+        );
+    }
+    let bad = head_commit(&hub);
+
+    let mut cmd = cargo_bin_cmd!("toad");
+    cmd.current_dir(&hub)
+        .args(["bisect", "--good", &good, "--bad", &bad, "--"])
+        .args(["sh", "-c", "! grep -q broken lib/value.txt"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("First bad state: . "))
+        .stdout(predicate::str::contains("bump lib 1"))
+        .stdout(predicate::str::contains(format!(
+            "Culprit commit: lib {} broken",
+            culprit
+        )));
+    assert_eq!(head_commit(&hub), bad);
+    assert_eq!(
+        git_output(&hub, &["symbolic-ref", "--short", "HEAD"]),
+        "main"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_risk_reports_local_only_work() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let home = dir.path().join("home");
+    fs::create_dir_all(&home)?;
+    fs::write(dir.path().join(".toad-root"), "")?;
+    for name in ["newt", "frog"] {
+        let proj_path = dir.path().join("projects").join(name);
+        fs::create_dir_all(&proj_path)?;
+        fs::write(proj_path.join("README.md"), name)?;
+        init_committed_repo(&proj_path);
+    }
+    fs::write(dir.path().join("projects/newt/notes.txt"), "todo")?;
+
+    let mut cmd = cargo_bin_cmd!("toad");
+    cmd.current_dir(dir.path())
+        .env("HOME", &home)
+        .args(["risk", "-q", "newt"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("AT-RISK WORK"))
+        .stdout(predicate::str::contains("1 file untracked (notes.txt)"))
+        .stdout(predicate::str::contains("main: 1 commit on no remote"))
+        .stdout(predicate::str::contains("1 of 1 repositories"));
+    Ok(())
+}
+
+#[test]
+fn test_ggit_maintain_reports_reclaimed_bytes() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let home = dir.path().join("home");
+    fs::create_dir_all(&home)?;
+    fs::write(dir.path().join(".toad-root"), "")?;
+    let proj_path = dir.path().join("projects/newt");
+    fs::create_dir_all(&proj_path)?;
+    fs::write(proj_path.join("README.md"), "newt")?;
+    init_committed_repo(&proj_path);
+
+    let mut cmd = cargo_bin_cmd!("toad");
+    cmd.current_dir(dir.path())
+        .env("HOME", &home)
+        .args(["ggit", "maintain", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("loose"))
+        .stdout(predicate::str::contains("DRY RUN COMPLETE"));
+
+    let mut cmd = cargo_bin_cmd!("toad");
+    cmd.current_dir(dir.path())
+        .env("HOME", &home)
+        .args(["ggit", "maintain"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("MAINTENANCE SUMMARY"))
+        .stdout(predicate::str::contains("reclaimed"));
+    assert_eq!(
+        git_output(&proj_path, &["count-objects"]),
+        "0 objects, 0 kilobytes"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_hooks_install_status_remove() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let home = dir.path().join("home");
+    fs::create_dir_all(&home)?;
+    fs::write(dir.path().join(".toad-root"), "")?;
+    let templates = dir.path().join("git-hooks");
+    fs::create_dir_all(&templates)?;
+    fs::write(templates.join("pre-commit"), "#!/bin/sh\nexit 0\n")?;
+    let proj_path = dir.path().join("projects/newt");
+    fs::create_dir_all(&proj_path)?;
+    fs::write(proj_path.join("README.md"), "newt")?;
+    init_committed_repo(&proj_path);
+    let hook = proj_path.join(".git/hooks/pre-commit");
+    let from = templates.to_str().unwrap();
+
+    let mut cmd = cargo_bin_cmd!("toad");
+    cmd.current_dir(dir.path())
+        .env("HOME", &home)
+        .args(["hooks", "install", "--from", from])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("HOOK SUMMARY"));
+    assert_eq!(fs::read_to_string(&hook)?, "#!/bin/sh\nexit 0\n");
+
+    fs::write(&hook, "#!/bin/sh\nexit 1\n")?;
+    let mut cmd = cargo_bin_cmd!("toad");
+    cmd.current_dir(dir.path())
+        .env("HOME", &home)
+        .args(["hooks", "status", "--from", from])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("drifted: pre-commit"))
+        .stdout(predicate::str::contains("1 of 1 repositories differ"));
+
+    let mut cmd = cargo_bin_cmd!("toad");
+    cmd.current_dir(dir.path())
+        .env("HOME", &home)
+        .args(["hooks", "remove", "--force", "--from", from])
+        .assert()
+        .success();
+    assert!(!hook.exists());
+    Ok(())
+}
+
+#[test]
+fn test_journal_writes_markdown() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let home = dir.path().join("home");
+    fs::create_dir_all(&home)?;
+    fs::write(dir.path().join(".toad-root"), "")?;
+    for name in ["newt", "frog"] {
+        let proj_path = dir.path().join("projects").join(name);
+        fs::create_dir_all(&proj_path)?;
+        fs::write(proj_path.join("README.md"), name)?;
+        init_committed_repo(&proj_path);
+    }
+    fs::write(dir.path().join("projects/frog/todo.md"), "wip")?;
+    let notes = dir.path().join("notes.md");
+
+    let mut cmd = cargo_bin_cmd!("toad");
+    cmd.current_dir(dir.path())
+        .env("HOME", &home)
+        .args(["journal", "--since", "1 hour ago", "-o"])
+        .arg(&notes)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("written to"));
+    let md = fs::read_to_string(&notes)?;
+    assert!(md.starts_with("# Journal since 1 hour ago\n"));
+    assert!(md.contains("## newt\n"));
+    assert!(md.contains("- init `"));
+    assert!(md.contains("- `?? todo.md`"));
+    Ok(())
+}
+
+#[test]
+fn test_changelog_between_hub_tags() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let home = dir.path().join("home");
+    fs::create_dir_all(&home)?;
+    let lib = dir.path().join("lib");
+    fs::create_dir_all(&lib)?;
+    fs::write(lib.join("lib.rs"), "// lib\n")?;
+    init_committed_repo(&lib);
+    let hub = dir.path().join("hub");
+    fs::create_dir_all(&hub)?;
+    fs::write(hub.join("README.md"), "hub\n")?;
+    init_committed_repo(&hub);
+    fs::write(hub.join(".toad-root"), "")?;
+    git(
+        &hub,
+        &[
+            "-c",
+            "protocol.file.allow=always",
+            "submodule",
+            "add",
+            "-q",
+            lib.to_str().unwrap(),
+            "crates/lib",
+        ],
+    );
+    git(&hub, &["commit", "-q", "-m", "chore: add lib"]);
+    git(&hub, &["tag", "v1"]);
+
+    let checkout = hub.join("crates/lib");
+    git(&checkout, &["config", "user.name", "Toad"]);
+    git(&checkout, &["config", "user.email", "toad@example.com"]);
+    for subject in [
+        "feat(scan): add tiers",
+        "fix: skip broken links",
+        "docs: typo",
+    ] {
+        git(&checkout, &["commit", "-q", "--allow-empty", "-m", subject]);
+    }
+    git(&hub, &["commit", "-q", "-am", "feat: bump lib"]);
+    git(&hub, &["tag", "v2"]);
+
+    let mut cmd = cargo_bin_cmd!("toad");
+    cmd.current_dir(&hub)
+        .env("HOME", &home)
+        .args(["changelog", "v1..v2", "--write"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("SUCCESS:"));
+    let hub_log = fs::read_to_string(hub.join("CHANGELOG.md"))?;
+    assert!(hub_log.starts_with("# Changelog\n"));
+    assert!(hub_log.contains("## [v2] — "));
+    assert!(hub_log.contains("### Added\n\n- Bump lib (`"));
+    assert!(hub_log.contains("(scan):** Add tiers"));
+    assert!(hub_log.contains("### Fixed\n"));
+    assert!(!hub_log.contains("typo"));
+    Ok(())
+}
+
+#[test]
+fn test_audit_ignore_fixes_tracked_artifacts() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let home = dir.path().join("home");
+    fs::create_dir_all(&home)?;
+    fs::write(dir.path().join(".toad-root"), "")?;
+    let proj_path = dir.path().join("projects/newt");
+    fs::create_dir_all(proj_path.join("target/debug"))?;
+    fs::write(proj_path.join("Cargo.toml"), "")?;
+    fs::write(proj_path.join("target/debug/newt"), "bin")?;
+    init_committed_repo(&proj_path);
+
+    let mut cmd = cargo_bin_cmd!("toad");
+    cmd.current_dir(dir.path())
+        .env("HOME", &home)
+        .args(["audit-ignore"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("1 tracked file,"))
+        .stdout(predicate::str::contains("not in .gitignore"))
+        .stdout(predicate::str::contains("1 of 1 repositories"));
+
+    let mut cmd = cargo_bin_cmd!("toad");
+    cmd.current_dir(dir.path())
+        .env("HOME", &home)
+        .args(["audit-ignore", "--fix", "-y"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("+target/"))
+        .stdout(predicate::str::contains("-target/debug/newt (untrack)"))
+        .stdout(predicate::str::contains("IGNORE SUMMARY"));
+    assert_eq!(
+        fs::read_to_string(proj_path.join(".gitignore"))?,
+        "target/\n"
+    );
+    assert_eq!(git_output(&proj_path, &["ls-files", "target"]), "");
+    assert!(proj_path.join("target/debug/newt").exists());
+    Ok(())
+}
+
+#[test]
+fn test_mirror_sync_serves_offline_clones() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let home = dir.path().join("home");
+    let config = dir.path().join("config");
+    fs::create_dir_all(&home)?;
+    fs::write(dir.path().join(".toad-root"), "")?;
+
+    let upstream = dir.path().join("upstream");
+    let newt_src = upstream.join("newt-src");
+    fs::create_dir_all(&newt_src)?;
+    fs::write(newt_src.join("README.md"), "newt\n")?;
+    init_committed_repo(&newt_src);
+    git(
+        &upstream,
+        &["clone", "-q", "--bare", "newt-src", "newt.git"],
+    );
+    // git only rewrites URLs, not bare local paths.
+    let remote = format!("file://{}", upstream.join("newt.git").to_string_lossy());
+    fs::create_dir_all(dir.path().join("projects"))?;
+    git(
+        &dir.path().join("projects"),
+        &["clone", "-q", &remote, "newt"],
+    );
+
+    let mut cmd = cargo_bin_cmd!("toad");
+    cmd.current_dir(dir.path())
+        .env("HOME", &home)
+        .env("TOAD_CONFIG_DIR", &config)
+        .args(["mirror", "sync"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("MIRROR SUMMARY"))
+        .stdout(predicate::str::contains("CREATED"));
+
+    // The remote disappears, as it does on a train.
+    fs::rename(&upstream, dir.path().join("gone"))?;
+    let manifest = format!("[[project]]\nname = \"newt\"\nremote = \"{}\"\n", remote);
+    fs::write(dir.path().join("pond.toml"), manifest)?;
+    let dest = dir.path().join("pond");
+    let mut cmd = cargo_bin_cmd!("toad");
+    cmd.current_dir(dir.path())
+        .env("HOME", &home)
+        .env("TOAD_CONFIG_DIR", &config)
+        .args(["clone", "pond.toml", "--dest"])
+        .arg(&dest)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("1 local mirror(s) available"));
+    let clone = dest.join("projects/newt");
+    assert_eq!(fs::read_to_string(clone.join("README.md"))?, "newt\n");
+    assert_eq!(git_output(&clone, &["remote", "get-url", "origin"]), remote);
+
+    let mut cmd = cargo_bin_cmd!("toad");
+    cmd.current_dir(dir.path())
+        .env("HOME", &home)
+        .env("TOAD_CONFIG_DIR", &config)
+        .args(["mirror", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(remote.as_str()))
+        .stdout(predicate::str::contains("1 mirror(s)"));
+    Ok(())
+}
+
+#[test]
+fn test_stats_records_history_for_trend_and_diff() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let home = dir.path().join("home");
+    let config = dir.path().join("config");
+    fs::create_dir_all(&home)?;
+    fs::write(dir.path().join(".toad-root"), "")?;
+    let proj_path = dir.path().join("projects/newt");
+    fs::create_dir_all(&proj_path)?;
+    fs::write(proj_path.join("main.rs"), "0".repeat(100))?;
+
+    let stats = |args: &[&str]| {
+        let mut cmd = cargo_bin_cmd!("toad");
+        cmd.current_dir(dir.path())
+            .env("HOME", &home)
+            .env("TOAD_CONFIG_DIR", &config)
+            .arg("stats")
+            .args(args)
+            .assert()
+            .success()
+    };
+    stats(&[]);
+
+    // Backdate the first snapshot so it falls before the --diff point.
+    fn find(dir: &std::path::Path, name: &str) -> Option<std::path::PathBuf> {
+        fs::read_dir(dir).ok()?.flatten().find_map(|e| {
+            let path = e.path();
+            if path.is_dir() {
+                find(&path, name)
+            } else {
+                path.ends_with(name).then_some(path)
+            }
+        })
+    }
+    let history_path =
+        find(&config, "stats-history.json").expect("stats run should record a snapshot");
+    let mut history: serde_json::Value = serde_json::from_str(&fs::read_to_string(&history_path)?)?;
+    let taken_at = history["snapshots"][0]["taken_at"].as_u64().unwrap();
+    history["snapshots"][0]["taken_at"] = (taken_at - 10 * 24 * 60 * 60).into();
+    fs::write(&history_path, history.to_string())?;
+
+    stats(&["--trend", "30d"])
+        .stdout(predicate::str::contains("TREND (last 30d, 2 snapshots)"))
+        .stdout(predicate::str::contains("newt"))
+        .stdout(predicate::str::contains("Net:"));
+    stats(&["--diff", "7d"])
+        .stdout(predicate::str::contains("GROWTH SINCE 7d"))
+        .stdout(predicate::str::contains("newt"));
+    stats(&["--diff", "2000-01-01"]).stdout(predicate::str::contains(
+        "No stats were recorded at or before 2000-01-01",
+    ));
+
+    let mut cmd = cargo_bin_cmd!("toad");
+    cmd.current_dir(dir.path())
+        .env("HOME", &home)
+        .env("TOAD_CONFIG_DIR", &config)
+        .args(["stats", "--trend", "soon"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid window"));
+    Ok(())
+}